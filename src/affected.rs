@@ -0,0 +1,314 @@
+use std::path::Path;
+
+use globset::GlobMatcher;
+use typed_builder::TypedBuilder;
+
+use crate::workspace::build_glob;
+use crate::workspace::relative_path;
+use crate::Binary;
+use crate::DependencyKind;
+use crate::PackageJson;
+use crate::Workspace;
+use crate::WorkspaceMember;
+
+/// Decides which files of a package are published, following the `files`
+/// field of its `package.json`.
+///
+/// A package without a `files` field publishes every file. Otherwise a file
+/// is published when it matches a glob or lives inside a matching directory
+/// and no `!` glob excludes it. The `package.json`, readme, license, `main`
+/// and `bin` files are always published.
+///
+/// ```
+/// use package_json_schema::PackageJson;
+/// use package_json_schema::PublishedFiles;
+///
+/// let contents = r#"{ "main": "index.js", "files": ["dist", "!dist/**/*.map"] }"#;
+/// let package_json = PackageJson::try_from(contents).unwrap();
+/// let published = PublishedFiles::new(&package_json).unwrap();
+///
+/// assert!(published.matches("dist/esm/index.js"));
+/// assert!(published.matches("README.md"));
+/// assert!(published.matches("index.js"));
+/// assert!(!published.matches("dist/esm/index.js.map"));
+/// assert!(!published.matches("src/index.test.ts"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PublishedFiles {
+	includes: Option<Vec<GlobMatcher>>,
+	excludes: Vec<GlobMatcher>,
+	always: Vec<String>,
+}
+
+impl PublishedFiles {
+	/// Build the matcher from the `files`, `main` and `bin` fields.
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::InvalidGlob`] when an entry of `files` isn't a
+	/// valid glob.
+	pub fn new(package_json: &PackageJson) -> crate::Result<Self> {
+		let mut always: Vec<String> = package_json.main.iter().cloned().collect();
+
+		match &package_json.bin {
+			Some(Binary::Path(path)) => always.push(path.clone()),
+			Some(Binary::Object(paths)) => always.extend(paths.values().cloned()),
+			None => {}
+		}
+
+		let always = always
+			.iter()
+			.map(|path| normalize_file(path).to_string())
+			.collect();
+
+		let Some(files) = &package_json.files else {
+			return Ok(Self {
+				includes: None,
+				excludes: vec![],
+				always,
+			});
+		};
+
+		let mut includes = Vec::new();
+		let mut excludes = Vec::new();
+
+		for pattern in files {
+			match pattern.strip_prefix('!') {
+				Some(negated) => excludes.push(build_glob(normalize_file(negated))?),
+				None => includes.push(build_glob(normalize_file(pattern))?),
+			}
+		}
+
+		Ok(Self {
+			includes: Some(includes),
+			excludes,
+			always,
+		})
+	}
+
+	/// Check whether the file, relative to the package directory with `/`
+	/// separators, is published.
+	#[must_use]
+	pub fn matches(&self, path: &str) -> bool {
+		let path = normalize_file(path);
+
+		if !path.contains('/') {
+			let lowercase = path.to_lowercase();
+
+			if lowercase == "package.json"
+				|| ["readme", "license", "licence"]
+					.iter()
+					.any(|prefix| lowercase.starts_with(prefix))
+			{
+				return true;
+			}
+		}
+
+		if self.always.iter().any(|always| always == path) {
+			return true;
+		}
+
+		let Some(includes) = &self.includes else {
+			return true;
+		};
+
+		let matches = |glob: &GlobMatcher| ancestors(path).any(|ancestor| glob.is_match(ancestor));
+
+		includes.iter().any(matches) && !self.excludes.iter().any(matches)
+	}
+}
+
+/// Options for [`Workspace::affected`].
+#[derive(TypedBuilder, Debug, Clone, Copy, Default)]
+pub struct AffectedOptions {
+	/// Whether a member is affected when only one of its `devDependencies`
+	/// changed.
+	#[builder(default)]
+	pub include_dev_dependents: bool,
+}
+
+/// A member affected by a set of changed files.
+#[derive(Debug, Clone)]
+pub struct AffectedMember<'a> {
+	/// The index of the member in [`Workspace::members`].
+	pub index: usize,
+
+	/// The affected member.
+	pub member: &'a WorkspaceMember,
+
+	/// The changed files of the member which are published, relative to the
+	/// workspace root.
+	pub published_files: Vec<String>,
+
+	/// The changed files of the member which aren't published, such as tests.
+	pub unpublished_files: Vec<String>,
+
+	/// Whether the member depends, directly or indirectly, on a member with
+	/// published changes.
+	pub via_dependency: bool,
+}
+
+impl AffectedMember<'_> {
+	/// Whether the published contents of the member changed, either directly
+	/// or through one of its dependencies.
+	#[must_use]
+	pub fn is_published_change(&self) -> bool {
+		self.via_dependency || !self.published_files.is_empty()
+	}
+
+	/// Whether only files which aren't published changed, so that only the
+	/// tests of the member need to run.
+	#[must_use]
+	pub fn is_test_only(&self) -> bool {
+		!self.is_published_change()
+	}
+}
+
+/// The result of [`Workspace::affected`].
+#[derive(Debug, Clone, Default)]
+pub struct AffectedPackages<'a> {
+	/// The affected members ordered by their relative path.
+	pub members: Vec<AffectedMember<'a>>,
+
+	/// The changed files which don't belong to any member, relative to the
+	/// workspace root.
+	pub unowned_files: Vec<String>,
+}
+
+impl AffectedPackages<'_> {
+	/// The names of the affected members.
+	#[must_use]
+	pub fn names(&self) -> Vec<&str> {
+		self.members
+			.iter()
+			.filter_map(|affected| affected.member.name())
+			.collect()
+	}
+}
+
+impl Workspace {
+	/// Find the members affected by the changed files.
+	///
+	/// Each file belongs to the member with the deepest directory containing
+	/// it. Paths may be absolute or relative to the workspace root. Members
+	/// with published changes then affect every member which depends on
+	/// them, directly or transitively. Changes to unpublished files, like
+	/// tests, only affect the member itself.
+	///
+	/// ```
+	/// use package_json_schema::AffectedOptions;
+	/// use package_json_schema::Workspace;
+	///
+	/// let workspace = Workspace::discover("./tests/fixtures/workspaces/graph").unwrap();
+	/// let files = ["packages/core/dist/index.js"];
+	/// let options = AffectedOptions::default();
+	/// let affected = workspace.affected(&files, options).unwrap();
+	/// let expected = ["@graph/app", "@graph/core", "@graph/legacy", "@graph/ui"];
+	///
+	/// assert_eq!(affected.names(), expected);
+	/// ```
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::InvalidGlob`] when the `files` field of a
+	/// member contains an invalid glob.
+	pub fn affected<P: AsRef<Path>>(
+		&self,
+		files: &[P],
+		options: AffectedOptions,
+	) -> crate::Result<AffectedPackages<'_>> {
+		let members = self.members();
+		let mut affected: Vec<Option<AffectedMember<'_>>> = vec![None; members.len()];
+		let mut published_files: Vec<Option<PublishedFiles>> = vec![None; members.len()];
+		let mut unowned_files = Vec::new();
+
+		for file in files {
+			let path = file.as_ref();
+			let file = relative_path(self.root(), path).or_else(|| {
+				if path.is_absolute() {
+					None
+				} else {
+					relative_path(Path::new(""), path)
+				}
+			});
+
+			let Some(file) = file else {
+				unowned_files.push(path.to_string_lossy().into_owned());
+				continue;
+			};
+
+			let owner = members
+				.iter()
+				.enumerate()
+				.filter(|(_, member)| is_inside(&file, &member.relative_path))
+				.max_by_key(|(_, member)| member.relative_path.len());
+
+			let Some((index, member)) = owner else {
+				unowned_files.push(file);
+				continue;
+			};
+
+			let entry = affected[index].get_or_insert_with(|| new_affected(index, member));
+			let published = match &mut published_files[index] {
+				Some(published) => published,
+				slot => slot.insert(PublishedFiles::new(&member.package_json)?),
+			};
+
+			if published.matches(&file[member.relative_path.len() + 1..]) {
+				entry.published_files.push(file);
+			} else {
+				entry.unpublished_files.push(file);
+			}
+		}
+
+		let kinds: Vec<DependencyKind> = DependencyKind::ALL
+			.into_iter()
+			.filter(|kind| options.include_dev_dependents || *kind != DependencyKind::Dev)
+			.collect();
+		let graph = self.graph().with_kinds(&kinds);
+		let seeds: Vec<usize> = affected
+			.iter()
+			.flatten()
+			.filter(|entry| !entry.published_files.is_empty())
+			.map(|entry| entry.index)
+			.collect();
+
+		for index in graph.transitive_dependents(&seeds) {
+			affected[index]
+				.get_or_insert_with(|| new_affected(index, &members[index]))
+				.via_dependency = true;
+		}
+
+		Ok(AffectedPackages {
+			members: affected.into_iter().flatten().collect(),
+			unowned_files,
+		})
+	}
+}
+
+const fn new_affected(index: usize, member: &WorkspaceMember) -> AffectedMember<'_> {
+	AffectedMember {
+		index,
+		member,
+		published_files: vec![],
+		unpublished_files: vec![],
+		via_dependency: false,
+	}
+}
+
+fn is_inside(file: &str, directory: &str) -> bool {
+	file.strip_prefix(directory)
+		.is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Remove a leading `./` or `/` and trailing slashes from a path or glob.
+fn normalize_file(path: &str) -> &str {
+	path.trim_start_matches("./")
+		.trim_start_matches('/')
+		.trim_end_matches('/')
+}
+
+/// The path followed by each of its parent directories.
+fn ancestors(path: &str) -> impl Iterator<Item = &str> {
+	std::iter::once(path).chain(path.match_indices('/').map(|(index, _)| &path[..index]))
+}
@@ -0,0 +1,429 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use typed_builder::TypedBuilder;
+
+use crate::parse_version;
+use crate::AdditionalFields;
+use crate::DependencyKind;
+use crate::PackageJson;
+use crate::Workspace;
+
+/// The text lockfile written by bun, `bun.lock`.
+///
+/// The file is JSON with comments and trailing commas. Packages are stored
+/// as tuples keyed by their install path, where `debug/ms` is the copy of
+/// `ms` installed below `debug`. See <https://bun.sh/docs/install/lockfile>.
+///
+/// ```
+/// use package_json_schema::BunLock;
+///
+/// let contents = r#"{
+///   "lockfileVersion": 1,
+///   "workspaces": { "": { "name": "app", "dependencies": { "ms": "^2.1.0" } } },
+///   // Trailing commas and comments are allowed.
+///   "packages": { "ms": ["ms@2.1.3", "", {}, "sha512-abc"], },
+/// }"#;
+///
+/// let bun_lock = BunLock::try_from(contents).unwrap();
+/// let ms = &bun_lock.packages["ms"];
+///
+/// assert_eq!(ms.version(), Some("2.1.3"));
+/// assert_eq!(ms.integrity(), Some("sha512-abc"));
+/// ```
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BunLock {
+	/// The version of the lockfile format.
+	#[serde(
+		default,
+		rename = "lockfileVersion",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub lockfile_version: Option<u32>,
+
+	/// The manifests of the workspace keyed by their path relative to the
+	/// root, which is the empty string.
+	#[serde(default)]
+	#[builder(default, setter(into))]
+	pub workspaces: IndexMap<String, BunWorkspace>,
+
+	/// The dependencies which may run their install scripts.
+	#[serde(
+		default,
+		rename = "trustedDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub trusted_dependencies: Option<Vec<String>>,
+
+	/// The `overrides` of the root manifest.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub overrides: Option<IndexMap<String, String>>,
+
+	/// The patches applied to dependencies, keyed by `name@version`.
+	#[serde(
+		default,
+		rename = "patchedDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub patched_dependencies: Option<IndexMap<String, String>>,
+
+	/// The default catalog.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub catalog: Option<IndexMap<String, String>>,
+
+	/// The named catalogs.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub catalogs: Option<IndexMap<String, IndexMap<String, String>>>,
+
+	/// Every installed package keyed by its install path.
+	#[serde(default)]
+	#[builder(default, setter(into))]
+	pub packages: IndexMap<String, BunPackage>,
+
+	/// All addition field.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+impl TryFrom<&str> for BunLock {
+	type Error = crate::error::Error;
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		let bun_lock: Self =
+			serde_json::from_str(&strip_jsonc(value)).map_err(crate::Error::ParseBunLock)?;
+		Ok(bun_lock)
+	}
+}
+
+impl TryFrom<String> for BunLock {
+	type Error = crate::error::Error;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		Self::try_from(value.as_str())
+	}
+}
+
+impl BunLock {
+	/// Pair every workspace entry with the `package.json` of the workspace
+	/// found at its path. The empty path is the root `package.json`.
+	#[must_use]
+	pub fn workspace_package_jsons<'a>(
+		&self,
+		workspace: &'a Workspace,
+	) -> Vec<(&str, Option<&'a PackageJson>)> {
+		self.workspaces
+			.keys()
+			.map(|path| (path.as_str(), workspace.package_json_at(path)))
+			.collect()
+	}
+
+	/// Find the package which a dependency of the named workspace resolves
+	/// to. Copies installed below the workspace, e.g. `lib/ms`, win over the
+	/// hoisted copy.
+	#[must_use]
+	pub fn resolve(&self, workspace: Option<&str>, name: &str) -> Option<(&str, &BunPackage)> {
+		let nested = workspace.map(|workspace| format!("{workspace}/{name}"));
+
+		nested
+			.and_then(|key| self.packages.get_key_value(&key))
+			.or_else(|| self.packages.get_key_value(name))
+			.map(|(key, package)| (key.as_str(), package))
+	}
+
+	/// The locked package of every dependency declared by a `package.json`,
+	/// except the `peerDependencies`.
+	#[must_use]
+	pub fn locked_dependencies<'a>(
+		&'a self,
+		package_json: &'a PackageJson,
+	) -> Vec<BunLockedDependency<'a>> {
+		package_json
+			.all_dependencies()
+			.filter(|(kind, ..)| *kind != DependencyKind::Peer)
+			.map(|(kind, name, spec)| {
+				let resolved = self.resolve(package_json.name.as_deref(), name);
+
+				BunLockedDependency {
+					kind,
+					name,
+					spec,
+					key: resolved.map(|(key, _)| key),
+					package: resolved.map(|(_, package)| package),
+				}
+			})
+			.collect()
+	}
+}
+
+/// A dependency of a `package.json` with its [`BunLock`] package, when the
+/// lockfile has one.
+#[derive(Debug, Clone, Copy)]
+pub struct BunLockedDependency<'a> {
+	/// The field which declares the dependency.
+	pub kind: DependencyKind,
+
+	/// The name of the dependency.
+	pub name: &'a str,
+
+	/// The declared spec.
+	pub spec: &'a str,
+
+	/// The install path of the package in the lockfile.
+	pub key: Option<&'a str>,
+
+	/// The locked package.
+	pub package: Option<&'a BunPackage>,
+}
+
+/// An entry of the `workspaces` of a [`BunLock`], copied from the
+/// `package.json` of the workspace.
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BunWorkspace {
+	/// The name of the workspace.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub name: Option<String>,
+
+	/// The version of the workspace.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub version: Option<String>,
+
+	/// The declared `dependencies`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub dependencies: Option<IndexMap<String, String>>,
+
+	/// The declared `devDependencies`.
+	#[serde(
+		default,
+		rename = "devDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub dev_dependencies: Option<IndexMap<String, String>>,
+
+	/// The declared `optionalDependencies`.
+	#[serde(
+		default,
+		rename = "optionalDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub optional_dependencies: Option<IndexMap<String, String>>,
+
+	/// The declared `peerDependencies`.
+	#[serde(
+		default,
+		rename = "peerDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub peer_dependencies: Option<IndexMap<String, String>>,
+
+	/// The peers which are marked as optional.
+	#[serde(
+		default,
+		rename = "optionalPeers",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub optional_peers: Option<Vec<String>>,
+
+	/// All addition field.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+/// An entry of the `packages` of a [`BunLock`].
+///
+/// Registry packages are `[resolution, registry, info, integrity]`, where the
+/// registry is empty for the default one. Workspaces are only
+/// `[resolution]` and other sources omit the registry or the integrity.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct BunPackage(pub Vec<Value>);
+
+impl BunPackage {
+	/// The resolution of the package, e.g. `ms@2.1.3` or
+	/// `lib@workspace:packages/lib`.
+	#[must_use]
+	pub fn resolution(&self) -> Option<&str> {
+		self.0.first().and_then(Value::as_str)
+	}
+
+	/// The name of the package, read from the resolution.
+	#[must_use]
+	pub fn name(&self) -> Option<&str> {
+		self.split_resolution().map(|(name, _)| name)
+	}
+
+	/// The resolved version, for packages from a registry.
+	#[must_use]
+	pub fn version(&self) -> Option<&str> {
+		self.split_resolution()
+			.map(|(_, version)| version)
+			.filter(|version| parse_version(version).is_some())
+	}
+
+	/// The subresource integrity of the tarball.
+	#[must_use]
+	pub fn integrity(&self) -> Option<&str> {
+		self.0
+			.iter()
+			.skip(1)
+			.filter_map(Value::as_str)
+			.find(|value| {
+				["sha1-", "sha256-", "sha384-", "sha512-"]
+					.iter()
+					.any(|prefix| value.starts_with(prefix))
+			})
+	}
+
+	/// The dependencies and platform constraints of the package.
+	#[must_use]
+	pub fn info(&self) -> Option<BunPackageInfo> {
+		self.0
+			.iter()
+			.find(|value| value.is_object())
+			.and_then(|value| serde_json::from_value(value.clone()).ok())
+	}
+
+	fn split_resolution(&self) -> Option<(&str, &str)> {
+		let resolution = self.resolution()?;
+		let index = resolution.get(1..)?.find('@')? + 1;
+
+		Some((&resolution[..index], &resolution[index + 1..]))
+	}
+}
+
+/// The metadata object of a [`BunPackage`].
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BunPackageInfo {
+	/// The dependencies with the ranges they were required with.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub dependencies: Option<IndexMap<String, String>>,
+
+	/// The optional dependencies.
+	#[serde(
+		default,
+		rename = "optionalDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub optional_dependencies: Option<IndexMap<String, String>>,
+
+	/// The peer dependencies.
+	#[serde(
+		default,
+		rename = "peerDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub peer_dependencies: Option<IndexMap<String, String>>,
+
+	/// The peers which are marked as optional.
+	#[serde(
+		default,
+		rename = "optionalPeers",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub optional_peers: Option<Vec<String>>,
+
+	/// The executables of the package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub bin: Option<IndexMap<String, String>>,
+
+	/// The operating systems supported by the package, a string or a list.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub os: Option<Value>,
+
+	/// The CPU architectures supported by the package, a string or a list.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub cpu: Option<Value>,
+
+	/// All addition field.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+/// Remove the comments and trailing commas of JSONC content.
+fn strip_jsonc(contents: &str) -> String {
+	let mut output = String::with_capacity(contents.len());
+	let mut chars = contents.chars().peekable();
+	let mut in_string = false;
+	let mut pending_comma = None;
+
+	while let Some(char) = chars.next() {
+		if in_string {
+			output.push(char);
+
+			if char == '\\' {
+				output.extend(chars.next());
+			} else if char == '"' {
+				in_string = false;
+			}
+
+			continue;
+		}
+
+		match (char, chars.peek()) {
+			('/', Some('/')) => {
+				for next in chars.by_ref() {
+					if next == '\n' {
+						output.push('\n');
+						break;
+					}
+				}
+			}
+			('/', Some('*')) => {
+				chars.next();
+				let mut previous = ' ';
+
+				for next in chars.by_ref() {
+					if previous == '*' && next == '/' {
+						break;
+					}
+
+					previous = next;
+				}
+			}
+			(',', _) => {
+				pending_comma = Some(output.len());
+				output.push(',');
+			}
+			('}' | ']', _) => {
+				if let Some(index) = pending_comma.take() {
+					output.remove(index);
+				}
+
+				output.push(char);
+			}
+			_ => {
+				if !char.is_whitespace() {
+					pending_comma = None;
+				}
+
+				in_string = char == '"';
+				output.push(char);
+			}
+		}
+	}
+
+	output
+}
@@ -0,0 +1,317 @@
+use std::fmt::Display;
+
+use indexmap::IndexMap;
+
+use crate::DependencyKind;
+use crate::DependencySpec;
+use crate::PackageJson;
+use crate::PnpmWorkspace;
+use crate::Workspace;
+
+/// The name of the catalog used by a bare `catalog:` dependency.
+pub const DEFAULT_CATALOG: &str = "default";
+
+/// The catalogs of a pnpm workspace, combining the `catalog` and `catalogs`
+/// fields of `pnpm-workspace.yaml`. See <https://pnpm.io/catalogs>.
+///
+/// ```
+/// use package_json_schema::Catalogs;
+/// use package_json_schema::PackageJson;
+/// use package_json_schema::PnpmWorkspace;
+///
+/// let yaml = "catalog:\n  react: ^18.2.0\ncatalogs:\n  legacy:\n    react: ^16.14.0\n";
+/// let pnpm_workspace = PnpmWorkspace::try_from(yaml).unwrap();
+/// let catalogs = Catalogs::new(&pnpm_workspace).unwrap();
+///
+/// let contents = r#"{ "dependencies": { "react": "catalog:legacy" } }"#;
+/// let mut package_json = PackageJson::try_from(contents).unwrap();
+/// let diagnostics = catalogs.resolve(&mut package_json);
+///
+/// assert!(diagnostics.is_empty());
+/// assert_eq!(package_json.dependencies.unwrap()["react"], "^16.14.0");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Catalogs {
+	catalogs: IndexMap<String, IndexMap<String, String>>,
+}
+
+impl Catalogs {
+	/// Read the catalogs of a `pnpm-workspace.yaml`. The `catalog` field is
+	/// the `default` catalog.
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::DuplicateDefaultCatalog`] when both `catalog`
+	/// and `catalogs.default` are defined.
+	pub fn new(pnpm_workspace: &PnpmWorkspace) -> crate::Result<Self> {
+		let mut catalogs = pnpm_workspace.catalogs.clone().unwrap_or_default();
+
+		if let Some(catalog) = &pnpm_workspace.catalog {
+			if catalogs.contains_key(DEFAULT_CATALOG) {
+				return Err(crate::Error::DuplicateDefaultCatalog);
+			}
+
+			catalogs.shift_insert(0, DEFAULT_CATALOG.to_string(), catalog.clone());
+		}
+
+		Ok(Self { catalogs })
+	}
+
+	/// The catalogs keyed by their name.
+	#[must_use]
+	pub const fn catalogs(&self) -> &IndexMap<String, IndexMap<String, String>> {
+		&self.catalogs
+	}
+
+	/// The range of a package in the named catalog.
+	#[must_use]
+	pub fn get(&self, catalog: &str, name: &str) -> Option<&str> {
+		self.catalogs
+			.get(catalog)
+			.and_then(|entries| entries.get(name))
+			.map(String::as_str)
+	}
+
+	/// Find the range of a dependency which uses the `catalog:` protocol.
+	/// Returns `Ok(None)` when the dependency uses another protocol.
+	///
+	/// # Errors
+	///
+	/// Returns the problem when the catalog or the entry doesn't exist.
+	pub fn lookup(&self, name: &str, spec: &str) -> Result<Option<&str>, CatalogProblem> {
+		let DependencySpec::Catalog(catalog) = DependencySpec::parse(spec) else {
+			return Ok(None);
+		};
+
+		let Some(entries) = self.catalogs.get(catalog) else {
+			return Err(CatalogProblem::UnknownCatalog {
+				catalog: catalog.to_string(),
+			});
+		};
+
+		entries
+			.get(name)
+			.map(|range| Some(range.as_str()))
+			.ok_or_else(|| {
+				CatalogProblem::UnknownEntry {
+					catalog: catalog.to_string(),
+				}
+			})
+	}
+
+	/// Replace every `catalog:` dependency with its range from the catalogs,
+	/// returning the dependencies which couldn't be resolved. Unresolved
+	/// dependencies are left untouched.
+	pub fn resolve(&self, package_json: &mut PackageJson) -> Vec<CatalogDiagnostic> {
+		let mut diagnostics = Vec::new();
+
+		for kind in DependencyKind::ALL {
+			for (name, spec) in package_json.dependencies_of_mut(kind).iter_mut().flatten() {
+				match self.lookup(name, spec) {
+					Ok(Some(range)) => *spec = range.to_string(),
+					Ok(None) => {}
+					Err(problem) => {
+						diagnostics.push(CatalogDiagnostic {
+							kind,
+							name: name.clone(),
+							spec: spec.clone(),
+							problem,
+						});
+					}
+				}
+			}
+		}
+
+		diagnostics
+	}
+}
+
+/// The reason a `catalog:` dependency couldn't be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CatalogProblem {
+	/// The workspace doesn't define the catalog.
+	UnknownCatalog { catalog: String },
+
+	/// The catalog doesn't contain the package.
+	UnknownEntry { catalog: String },
+}
+
+/// A `catalog:` dependency which couldn't be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogDiagnostic {
+	/// The field which declares the dependency.
+	pub kind: DependencyKind,
+
+	/// The name of the dependency.
+	pub name: String,
+
+	/// The `catalog:` spec of the dependency.
+	pub spec: String,
+
+	/// The reason the dependency couldn't be resolved.
+	pub problem: CatalogProblem,
+}
+
+impl Display for CatalogDiagnostic {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let Self {
+			kind, name, spec, ..
+		} = self;
+
+		match &self.problem {
+			CatalogProblem::UnknownCatalog { catalog } => {
+				write!(
+					f,
+					"`{name}@{spec}` in {kind} refers to the unknown catalog `{catalog}`"
+				)
+			}
+			CatalogProblem::UnknownEntry { catalog } => {
+				write!(
+					f,
+					"`{name}@{spec}` in {kind} is missing from the catalog `{catalog}`"
+				)
+			}
+		}
+	}
+}
+
+/// The members and fields which use a range.
+type Usages = Vec<(usize, DependencyKind)>;
+
+/// A dependency which is replaced with `catalog:` by a [`CatalogMigration`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogEdit {
+	/// The index of the member in [`Workspace::members`].
+	pub member: usize,
+
+	/// The field which declares the dependency.
+	pub kind: DependencyKind,
+
+	/// The name of the dependency.
+	pub name: String,
+
+	/// The range which is moved to the catalog.
+	pub range: String,
+}
+
+/// Moves ranges which are repeated across the members of a workspace into the
+/// `default` catalog. Created by [`Workspace::catalog_migration`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CatalogMigration {
+	/// The entries to add to the `default` catalog.
+	pub catalog: IndexMap<String, String>,
+
+	/// The dependencies to replace with `catalog:`.
+	pub edits: Vec<CatalogEdit>,
+}
+
+impl CatalogMigration {
+	/// Replace the migrated dependencies of a member with `catalog:`.
+	pub fn apply(&self, member: usize, package_json: &mut PackageJson) {
+		for edit in self.edits.iter().filter(|edit| edit.member == member) {
+			let spec = package_json
+				.dependencies_of_mut(edit.kind)
+				.as_mut()
+				.and_then(|dependencies| dependencies.get_mut(&edit.name));
+
+			if let Some(spec) = spec {
+				*spec = "catalog:".to_string();
+			}
+		}
+	}
+
+	/// Add the new entries to the `catalog` field of a `pnpm-workspace.yaml`.
+	pub fn extend_catalog(&self, pnpm_workspace: &mut PnpmWorkspace) {
+		pnpm_workspace
+			.catalog
+			.get_or_insert_with(IndexMap::new)
+			.extend(self.catalog.clone());
+	}
+}
+
+impl Workspace {
+	/// The catalogs of the `pnpm-workspace.yaml`, which are empty for other
+	/// workspaces.
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::DuplicateDefaultCatalog`] when both `catalog`
+	/// and `catalogs.default` are defined.
+	pub fn catalogs(&self) -> crate::Result<Catalogs> {
+		self.pnpm_workspace()
+			.map_or_else(|| Ok(Catalogs::default()), Catalogs::new)
+	}
+
+	/// Find the literal ranges which at least `min_occurrences` dependencies
+	/// of the members share and plan to move them into the `default` catalog.
+	///
+	/// When a package is used with several ranges, the most common one is
+	/// migrated, preferring the first seen on ties, and the others are left
+	/// alone. Packages already in the `default` catalog are only migrated
+	/// when the range matches the catalog. Members of the workspace are never
+	/// migrated.
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::DuplicateDefaultCatalog`] when both `catalog`
+	/// and `catalogs.default` are defined.
+	pub fn catalog_migration(&self, min_occurrences: usize) -> crate::Result<CatalogMigration> {
+		let catalogs = self.catalogs()?;
+		let mut usages: IndexMap<&str, IndexMap<&str, Usages>> = IndexMap::new();
+
+		for (index, member) in self.members().iter().enumerate() {
+			for (kind, name, spec) in member.package_json.all_dependencies() {
+				if !matches!(DependencySpec::parse(spec), DependencySpec::Range(_))
+					|| self.member(name).is_some()
+				{
+					continue;
+				}
+
+				usages
+					.entry(name)
+					.or_default()
+					.entry(spec)
+					.or_default()
+					.push((index, kind));
+			}
+		}
+
+		let mut migration = CatalogMigration::default();
+
+		for (name, ranges) in usages {
+			let existing = catalogs.get(DEFAULT_CATALOG, name);
+			let selected = ranges
+				.iter()
+				.filter(|(range, _)| existing.map_or(true, |existing| existing == **range))
+				.rev()
+				.max_by_key(|(_, usages)| usages.len());
+
+			let Some((range, usages)) = selected else {
+				continue;
+			};
+
+			if usages.len() < min_occurrences.max(1) {
+				continue;
+			}
+
+			if existing.is_none() {
+				migration
+					.catalog
+					.insert((*name).to_string(), (*range).to_string());
+			}
+
+			migration.edits.extend(usages.iter().map(|(member, kind)| {
+				CatalogEdit {
+					member: *member,
+					kind: *kind,
+					name: (*name).to_string(),
+					range: (*range).to_string(),
+				}
+			}));
+		}
+
+		migration.edits.sort_by_key(|edit| (edit.member, edit.kind));
+
+		Ok(migration)
+	}
+}
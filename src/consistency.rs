@@ -0,0 +1,303 @@
+use std::cmp::Ordering;
+
+use globset::GlobMatcher;
+use indexmap::IndexMap;
+use semver::Version;
+use typed_builder::TypedBuilder;
+
+use crate::parse_version;
+use crate::workspace::build_glob;
+use crate::DependencyKind;
+use crate::DependencySpec;
+use crate::PackageJson;
+use crate::RangeUpdate;
+use crate::VersionRange;
+use crate::Workspace;
+
+/// Options for [`Workspace::check_consistency`].
+#[derive(TypedBuilder, Debug, Clone, Default)]
+pub struct ConsistencyOptions {
+	/// Packages which must use an exact range, keyed by package name. Other
+	/// packages use the highest range found in the workspace.
+	#[builder(default, setter(into))]
+	pub pins: IndexMap<String, String>,
+
+	/// Globs of the package names which are never checked, e.g. `@types/*`.
+	#[builder(default, setter(into))]
+	pub ignore: Vec<String>,
+
+	/// Accept `peerDependencies` with a different range as long as it
+	/// includes the version of the expected range, e.g. `^17.0.0 || ^18.0.0`
+	/// when the expected range is `^18.2.0`.
+	#[builder(default)]
+	pub allow_wider_peer_ranges: bool,
+}
+
+/// A single use of a dependency by a workspace member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyUsage {
+	/// The index of the member in [`Workspace::members`].
+	pub member: usize,
+
+	/// The field which declares the dependency.
+	pub kind: DependencyKind,
+
+	/// The spec used by the member.
+	pub spec: String,
+}
+
+/// A dependency used with different ranges across the workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+	/// The name of the dependency.
+	pub name: String,
+
+	/// The range every member should use.
+	pub expected: String,
+
+	/// Every use of the dependency, including the ones which already match.
+	pub usages: Vec<DependencyUsage>,
+
+	/// The edits which make every use consistent.
+	pub fixes: Vec<RangeUpdate>,
+}
+
+/// The dependencies which are used with inconsistent ranges. Created by
+/// [`Workspace::check_consistency`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsistencyReport {
+	/// The mismatched dependencies in the order they are first used.
+	pub mismatches: Vec<Mismatch>,
+}
+
+impl ConsistencyReport {
+	/// Whether every dependency is consistent.
+	#[must_use]
+	pub fn is_consistent(&self) -> bool {
+		self.mismatches.is_empty()
+	}
+
+	/// Every fix ordered by member.
+	#[must_use]
+	pub fn fixes(&self) -> Vec<&RangeUpdate> {
+		let mut fixes: Vec<&RangeUpdate> = self
+			.mismatches
+			.iter()
+			.flat_map(|mismatch| &mismatch.fixes)
+			.collect();
+		fixes.sort_by_key(|fix| (fix.member, fix.kind));
+		fixes
+	}
+
+	/// Apply the fixes of a member to its `package.json`.
+	pub fn apply(&self, member: usize, package_json: &mut PackageJson) {
+		for fix in self.fixes() {
+			if fix.member == member {
+				fix.apply(package_json);
+			}
+		}
+	}
+}
+
+impl Workspace {
+	/// Find the external dependencies which members declare with different
+	/// ranges, like [syncpack](https://jamiemason.github.io/syncpack/).
+	///
+	/// Only semver ranges are compared, other protocols such as `workspace:`,
+	/// `catalog:`, tags and urls are skipped, as are dependencies on members.
+	/// The expected range is the pinned range when there is one and the
+	/// highest range otherwise: ranges are ordered by their version and then
+	/// by how many versions they accept, so `^1.2.0` beats `~1.2.0` which
+	/// beats `1.2.0`. `*` only wins when no member uses a concrete range.
+	///
+	/// ```
+	/// use package_json_schema::ConsistencyOptions;
+	/// use package_json_schema::Workspace;
+	///
+	/// let workspace = Workspace::discover("./tests/fixtures/workspaces/catalog").unwrap();
+	/// let options = ConsistencyOptions::default();
+	/// let report = workspace.check_consistency(&options).unwrap();
+	/// let names: Vec<_> = report.mismatches.iter().map(|m| &m.name).collect();
+	///
+	/// assert_eq!(names, ["lodash", "zod"]);
+	/// assert_eq!(report.mismatches[1].expected, "^3.22.0");
+	/// ```
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::InvalidGlob`] when an ignore glob is invalid.
+	pub fn check_consistency(
+		&self,
+		options: &ConsistencyOptions,
+	) -> crate::Result<ConsistencyReport> {
+		let ignore = options
+			.ignore
+			.iter()
+			.map(|pattern| build_glob(pattern))
+			.collect::<crate::Result<Vec<GlobMatcher>>>()?;
+		let mut groups: IndexMap<&str, Vec<DependencyUsage>> = IndexMap::new();
+
+		for (index, member) in self.members().iter().enumerate() {
+			for (kind, name, spec) in member.package_json.all_dependencies() {
+				if !matches!(DependencySpec::parse(spec), DependencySpec::Range(_))
+					|| self.member(name).is_some()
+					|| ignore.iter().any(|glob| glob.is_match(name))
+				{
+					continue;
+				}
+
+				groups.entry(name).or_default().push(DependencyUsage {
+					member: index,
+					kind,
+					spec: spec.to_string(),
+				});
+			}
+		}
+
+		let mut mismatches = Vec::new();
+
+		for (name, usages) in groups {
+			let expected = if let Some(pin) = options.pins.get(name) {
+				pin.clone()
+			} else {
+				let Some(highest) = usages
+					.iter()
+					.filter_map(|usage| RangeKey::parse(&usage.spec).map(|key| (key, usage)))
+					.max_by(|(a, _), (b, _)| a.cmp(b))
+				else {
+					continue;
+				};
+
+				highest.1.spec.clone()
+			};
+			let expected_version = RangeKey::parse(&expected).map(|key| key.version);
+
+			let fixes: Vec<RangeUpdate> = usages
+				.iter()
+				.filter(|usage| usage.spec != expected)
+				.filter(|usage| {
+					!(options.allow_wider_peer_ranges
+						&& usage.kind == DependencyKind::Peer
+						&& includes(&usage.spec, expected_version.as_ref()))
+				})
+				.map(|usage| {
+					RangeUpdate {
+						member: usage.member,
+						dependent: self.members()[usage.member]
+							.name()
+							.unwrap_or_default()
+							.to_string(),
+						kind: usage.kind,
+						name: name.to_string(),
+						from: usage.spec.clone(),
+						to: expected.clone(),
+					}
+				})
+				.collect();
+
+			if !fixes.is_empty() {
+				mismatches.push(Mismatch {
+					name: name.to_string(),
+					expected,
+					usages,
+					fixes,
+				});
+			}
+		}
+
+		Ok(ConsistencyReport { mismatches })
+	}
+}
+
+fn includes(range: &str, version: Option<&Version>) -> bool {
+	let range = VersionRange::parse(range).ok();
+
+	range
+		.zip(version)
+		.is_some_and(|(range, version)| range.satisfies(version))
+}
+
+/// Orders simple ranges such as `^1.2.3`, `~1.2` or `1.x`, with `*` below
+/// every concrete range.
+#[derive(Debug, PartialEq, Eq)]
+struct RangeKey {
+	any: bool,
+	version: Version,
+	greed: u8,
+}
+
+impl RangeKey {
+	fn parse(range: &str) -> Option<Self> {
+		let range = range.trim();
+
+		if matches!(range, "*" | "" | "x" | "X") {
+			return Some(Self {
+				any: true,
+				version: Version::new(0, 0, 0),
+				greed: 0,
+			});
+		}
+
+		let (greed, rest) = [(">=", 3), (">", 4), ("^", 2), ("~", 1), ("=", 0)]
+			.into_iter()
+			.find_map(|(operator, greed)| range.strip_prefix(operator).map(|rest| (greed, rest)))
+			.unwrap_or((0, range));
+
+		let rest = rest.trim();
+
+		if let Some(version) = parse_version(rest) {
+			return Some(Self {
+				any: false,
+				version,
+				greed,
+			});
+		}
+
+		let parts: Vec<&str> = rest.trim_start_matches('v').split('.').collect();
+		let mut numbers = [0_u64; 3];
+		let mut wildcard = parts.len();
+
+		if parts.len() > 3 {
+			return None;
+		}
+
+		for (index, part) in parts.iter().enumerate() {
+			if matches!(*part, "x" | "X" | "*") {
+				wildcard = index;
+				break;
+			}
+
+			numbers[index] = part.parse().ok()?;
+		}
+
+		let greed = match wildcard {
+			0 => return Self::parse("*"),
+			1 => greed.max(2),
+			2 => greed.max(1),
+			_ => greed,
+		};
+		let version = Version::new(numbers[0], numbers[1], numbers[2]);
+
+		Some(Self {
+			any: false,
+			version,
+			greed,
+		})
+	}
+}
+
+impl PartialOrd for RangeKey {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for RangeKey {
+	fn cmp(&self, other: &Self) -> Ordering {
+		other
+			.any
+			.cmp(&self.any)
+			.then_with(|| self.version.cmp_precedence(&other.version))
+			.then_with(|| self.greed.cmp(&other.greed))
+	}
+}
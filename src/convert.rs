@@ -0,0 +1,1126 @@
+use std::collections::VecDeque;
+
+use indexmap::IndexMap;
+use indexmap::IndexSet;
+
+use crate::DependencyKind;
+use crate::LockPackage;
+use crate::LockedDependency;
+use crate::LockedPackage;
+use crate::LockfileGraph;
+use crate::PackageJson;
+use crate::PackageLock;
+use crate::PnpmImporter;
+use crate::PnpmImporterDependency;
+use crate::PnpmLock;
+use crate::PnpmLockSettings;
+use crate::PnpmLockfileVersion;
+use crate::PnpmPackage;
+use crate::PnpmResolution;
+use crate::PnpmSnapshot;
+use crate::YarnLock;
+use crate::YarnLockEntry;
+use crate::YarnLockFlavor;
+use crate::YarnLockMetadata;
+
+/// Something a lockfile conversion couldn't carry over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConversionIssue {
+	/// A dependency which doesn't resolve to a package of the source
+	/// lockfile, so it is left out.
+	Unresolved {
+		/// The importer path or the `name@version` of the dependent.
+		dependent: String,
+		/// The name of the dependency.
+		name: String,
+	},
+
+	/// A field the target format expects which the source doesn't record.
+	/// The package manager fills it in on the next install.
+	MissingField {
+		/// The `name@version` of the package.
+		package: String,
+		/// The name of the field in the target format.
+		field: &'static str,
+	},
+
+	/// A field of the source which the target format can't store.
+	DroppedField {
+		/// The `name@version` of the package.
+		package: String,
+		/// The name of the field in the source.
+		field: &'static str,
+	},
+
+	/// Copies of a package whose dependencies resolve differently, which the
+	/// target format stores as a single entry using the first copy.
+	Merged {
+		/// The `name@version` of the package.
+		package: String,
+	},
+
+	/// A package from a source other than a registry, a tarball url, git or
+	/// a directory which the target format can't record, so it is written
+	/// as a registry package.
+	UnsupportedSource {
+		/// The `name@version` of the package.
+		package: String,
+		/// The resolved source, e.g. `file:vendor/a.tgz`.
+		source: String,
+	},
+
+	/// A manifest without a name, which the target format needs to link it.
+	UnnamedImporter {
+		/// The path of the importer relative to the root.
+		path: String,
+	},
+}
+
+/// A converted lockfile with everything the conversion couldn't carry over.
+#[derive(Debug, Clone)]
+pub struct Conversion<T> {
+	/// The lockfile in the target format.
+	pub lockfile: T,
+
+	/// The problems found during the conversion.
+	pub issues: Vec<ConversionIssue>,
+}
+
+impl LockfileGraph {
+	/// Convert to a `package-lock.json` with `lockfileVersion` 3.
+	///
+	/// The manifests, keyed by their path relative to the root, provide the
+	/// importer entries. Packages are hoisted to the root `node_modules`
+	/// unless another version already uses the name, in which case they are
+	/// nested below their dependent like npm does.
+	///
+	/// ```
+	/// use package_json_schema::LockfileGraph;
+	/// use package_json_schema::PackageJson;
+	/// use package_json_schema::PnpmLock;
+	///
+	/// let contents = r#"
+	/// lockfileVersion: '9.0'
+	/// importers:
+	///   .:
+	///     dependencies:
+	///       ms:
+	///         specifier: ^2.1.0
+	///         version: 2.1.3
+	/// packages:
+	///   ms@2.1.3:
+	///     resolution: {integrity: sha512-abc}
+	/// snapshots:
+	///   ms@2.1.3: {}
+	/// "#;
+	/// let manifest = r#"{ "name": "app", "dependencies": { "ms": "^2.1.0" } }"#;
+	/// let package_json = PackageJson::try_from(manifest).unwrap();
+	/// let graph = LockfileGraph::from_pnpm_lock(&PnpmLock::try_from(contents).unwrap());
+	/// let conversion = graph.to_package_lock(&[(".", &package_json)]);
+	/// let ms = conversion.lockfile.package("node_modules/ms").unwrap();
+	///
+	/// assert_eq!(ms.version.as_deref(), Some("2.1.3"));
+	/// assert_eq!(ms.integrity.as_deref(), Some("sha512-abc"));
+	/// ```
+	#[must_use]
+	pub fn to_package_lock(&self, manifests: &[(&str, &PackageJson)]) -> Conversion<PackageLock> {
+		let mut tree = NpmTree::new(self);
+
+		for (path, package_json) in manifests {
+			tree.add_importer(path, package_json);
+		}
+
+		while let Some((location, dependent, dependencies)) = tree.queue.pop_front() {
+			for dependency in dependencies {
+				tree.resolve(&location, dependent, dependency);
+			}
+		}
+
+		let NpmTree {
+			mut entries,
+			edges,
+			issues,
+			..
+		} = tree;
+		mark_npm_flags(&mut entries, &edges, manifests);
+
+		let importers: Vec<String> = manifests
+			.iter()
+			.map(|(path, _)| npm_importer_path(path))
+			.collect();
+		entries.sort_by(|a, _, b, _| {
+			let rank = |path: &String| importers.iter().position(|importer| importer == path);
+			match (rank(a), rank(b)) {
+				(Some(a), Some(b)) => a.cmp(&b),
+				(Some(_), None) => std::cmp::Ordering::Less,
+				(None, Some(_)) => std::cmp::Ordering::Greater,
+				(None, None) => a.cmp(b),
+			}
+		});
+
+		let root = manifests
+			.iter()
+			.find(|(path, _)| npm_importer_path(path).is_empty())
+			.map(|(_, package_json)| *package_json);
+		let mut lockfile = PackageLock::builder()
+			.lockfile_version(3_u32)
+			.requires(true)
+			.packages(entries)
+			.build();
+		lockfile.name = root.and_then(|package_json| package_json.name.clone());
+		lockfile.version = root.and_then(|package_json| package_json.version.clone());
+
+		Conversion { lockfile, issues }
+	}
+
+	/// Convert to a `pnpm-lock.yaml` with `lockfileVersion` 9.0.
+	///
+	/// Every manifest, keyed by its path relative to the root, becomes an
+	/// importer. Peer dependency suffixes aren't recorded by the other
+	/// formats, so every package is keyed by its name and version. Tarball
+	/// urls, git commits and directories are kept, other sources are
+	/// reported.
+	#[must_use]
+	pub fn to_pnpm_lock(&self, manifests: &[(&str, &PackageJson)]) -> Conversion<PnpmLock> {
+		let mut issues = Vec::new();
+		let mut packages: IndexMap<String, PnpmPackage> = IndexMap::new();
+		let mut snapshots: IndexMap<String, PnpmSnapshot> = IndexMap::new();
+		let importers: IndexMap<String, PnpmImporter> = manifests
+			.iter()
+			.map(|(path, package_json)| {
+				let path = normalize_pnpm_importer(path);
+				let importer = self.pnpm_importer(&path, package_json, &mut issues);
+				(path, importer)
+			})
+			.collect();
+
+		for package in self.packages.values() {
+			if package.link.is_some() || package.version.is_empty() {
+				continue;
+			}
+
+			let key = describe(package);
+			let snapshot = self.pnpm_snapshot(package, &mut issues);
+
+			if let Some(existing) = snapshots.get(&key) {
+				let same = existing.dependencies == snapshot.dependencies
+					&& existing.optional_dependencies == snapshot.optional_dependencies;
+
+				if !same {
+					issues.push(ConversionIssue::Merged { package: key });
+				}
+
+				continue;
+			}
+
+			report_missing(
+				package,
+				&mut issues,
+				&[("integrity", package.integrity.is_none())],
+			);
+			report_source(package, &mut issues, |source| {
+				matches!(source, PackageSource::Other)
+			});
+			packages.insert(key.clone(), pnpm_package(package));
+			snapshots.insert(key, snapshot);
+		}
+
+		let mut lockfile = PnpmLock::builder()
+			.lockfile_version(PnpmLockfileVersion::from("9.0"))
+			.importers(importers)
+			.packages(packages)
+			.snapshots(snapshots)
+			.build();
+		lockfile.settings = Some(PnpmLockSettings {
+			auto_install_peers: Some(true),
+			exclude_links_from_lockfile: Some(false),
+			other: None,
+		});
+
+		Conversion { lockfile, issues }
+	}
+
+	/// The importer of a manifest with the versions the graph resolves its
+	/// dependencies to.
+	fn pnpm_importer(
+		&self,
+		path: &str,
+		package_json: &PackageJson,
+		issues: &mut Vec<ConversionIssue>,
+	) -> PnpmImporter {
+		let mut importer = PnpmImporter::default();
+
+		for (kind, name, specifier) in package_json.all_dependencies() {
+			let map = match kind {
+				DependencyKind::Prod => &mut importer.dependencies,
+				DependencyKind::Dev => &mut importer.dev_dependencies,
+				DependencyKind::Optional => &mut importer.optional_dependencies,
+				DependencyKind::Peer => continue,
+			};
+			let version = self
+				.importer(path)
+				.and_then(|locked| {
+					locked
+						.dependencies
+						.iter()
+						.find(|dependency| dependency.name == name)
+				})
+				.and_then(|dependency| self.target_of(dependency, None, path, issues))
+				.map(|package| pnpm_version(path, name, package));
+
+			let Some(version) = version else {
+				issues.push(ConversionIssue::Unresolved {
+					dependent: path.to_string(),
+					name: name.to_string(),
+				});
+				continue;
+			};
+
+			map.get_or_insert_with(IndexMap::new).insert(
+				name.to_string(),
+				PnpmImporterDependency::Resolved {
+					specifier: specifier.to_string(),
+					version,
+				},
+			);
+		}
+
+		importer
+	}
+
+	/// The snapshot with the resolved dependencies of a package.
+	fn pnpm_snapshot(
+		&self,
+		package: &LockedPackage,
+		issues: &mut Vec<ConversionIssue>,
+	) -> PnpmSnapshot {
+		let mut snapshot = PnpmSnapshot::default();
+		let dependent = describe(package);
+
+		for dependency in &package.dependencies {
+			let Some(target) = self.target_of(dependency, Some(package), &dependent, issues) else {
+				continue;
+			};
+			let map = if dependency.kind == DependencyKind::Optional {
+				&mut snapshot.optional_dependencies
+			} else {
+				&mut snapshot.dependencies
+			};
+
+			map.get_or_insert_with(IndexMap::new).insert(
+				dependency.name.clone(),
+				pnpm_version(".", &dependency.name, target),
+			);
+		}
+
+		snapshot
+	}
+
+	/// Convert to a `yarn.lock` of the provided flavor.
+	///
+	/// Entries are keyed by the ranges their dependents require them with.
+	/// Yarn classic doesn't record workspaces, peer dependencies or
+	/// platforms, while yarn berry doesn't record the integrity, which it
+	/// replaces with a checksum computed on the next install. Yarn berry
+	/// keeps tarball urls and git commits, other sources are reported.
+	#[must_use]
+	pub fn to_yarn_lock(
+		&self,
+		manifests: &[(&str, &PackageJson)],
+		flavor: YarnLockFlavor,
+	) -> Conversion<YarnLock> {
+		let mut issues = Vec::new();
+		let (entries, descriptors) = self.yarn_entries(manifests, flavor, &mut issues);
+		let mut result: Vec<YarnLockEntry> = entries
+			.into_iter()
+			.filter_map(|(key, mut entry)| {
+				entry.descriptors = descriptors.get(&key)?.iter().cloned().collect();
+				entry.descriptors.sort();
+				Some(entry)
+			})
+			.collect();
+
+		for (path, package_json) in manifests {
+			if package_json.name.is_none() {
+				issues.push(ConversionIssue::UnnamedImporter {
+					path: (*path).to_string(),
+				});
+			} else if flavor == YarnLockFlavor::Berry {
+				result.push(workspace_entry(path, package_json, &descriptors));
+			}
+		}
+
+		result.sort_by(|a, b| a.descriptors.cmp(&b.descriptors));
+
+		let metadata = (flavor == YarnLockFlavor::Berry).then(|| {
+			YarnLockMetadata {
+				version: Some(8),
+				cache_key: Some("10c0".into()),
+			}
+		});
+
+		Conversion {
+			lockfile: YarnLock::new(flavor, metadata, result),
+			issues,
+		}
+	}
+
+	/// The entry of every package keyed by [`package_key`], along with the
+	/// descriptors each package is required with.
+	fn yarn_entries(
+		&self,
+		manifests: &[(&str, &PackageJson)],
+		flavor: YarnLockFlavor,
+		issues: &mut Vec<ConversionIssue>,
+	) -> (
+		IndexMap<String, YarnLockEntry>,
+		IndexMap<String, IndexSet<String>>,
+	) {
+		let berry = flavor == YarnLockFlavor::Berry;
+		let mut descriptors: IndexMap<String, IndexSet<String>> = IndexMap::new();
+		let mut entries: IndexMap<String, YarnLockEntry> = IndexMap::new();
+		let importers = manifests.iter().filter_map(|(path, _)| {
+			self.importer(path)
+				.map(|importer| (normalize_pnpm_importer(path), None, &importer.dependencies))
+		});
+		let packages = self
+			.packages
+			.values()
+			.filter(|package| package.link.is_none() && !package.version.is_empty())
+			.map(|package| (describe(package), Some(package), &package.dependencies));
+
+		for (dependent, parent, dependencies) in importers.chain(packages) {
+			let mut entry = parent.map(|package| yarn_entry(package, flavor));
+
+			for dependency in dependencies {
+				let Some(package) = self.target_of(dependency, parent, &dependent, issues) else {
+					continue;
+				};
+				let spec = dependency
+					.spec
+					.clone()
+					.unwrap_or_else(|| package.version.clone());
+				let (key, range) = yarn_descriptor(&dependency.name, &spec, package, flavor);
+
+				if package.link.is_none() || berry {
+					descriptors
+						.entry(package_key(package))
+						.or_default()
+						.insert(key);
+				}
+
+				let Some(entry) = entry.as_mut() else {
+					continue;
+				};
+				let map = match dependency.kind {
+					DependencyKind::Peer if berry => &mut entry.peer_dependencies,
+					DependencyKind::Peer => continue,
+					DependencyKind::Optional if !berry => &mut entry.optional_dependencies,
+					_ => &mut entry.dependencies,
+				};
+
+				map.get_or_insert_with(IndexMap::new)
+					.insert(dependency.name.clone(), range);
+			}
+
+			let (Some(package), Some(entry)) = (parent, entry) else {
+				continue;
+			};
+
+			if let Some(existing) = entries.get(&package_key(package)) {
+				let same = existing.dependencies == entry.dependencies
+					&& existing.optional_dependencies == entry.optional_dependencies;
+
+				if !same {
+					issues.push(ConversionIssue::Merged {
+						package: describe(package),
+					});
+				}
+			} else {
+				report_yarn_fields(package, flavor, issues);
+				entries.insert(package_key(package), entry);
+			}
+		}
+
+		(entries, descriptors)
+	}
+
+	/// The package a dependency resolves to, reporting the dependencies
+	/// which don't resolve. Unresolved peers are expected and not reported.
+	fn target_of(
+		&self,
+		dependency: &LockedDependency,
+		dependent: Option<&LockedPackage>,
+		location: &str,
+		issues: &mut Vec<ConversionIssue>,
+	) -> Option<&LockedPackage> {
+		let package = dependency.target.as_deref().and_then(|id| self.package(id));
+
+		if package.is_none() && dependency.kind != DependencyKind::Peer {
+			issues.push(ConversionIssue::Unresolved {
+				dependent: dependent.map_or_else(|| location.to_string(), describe),
+				name: dependency.name.clone(),
+			});
+		}
+
+		package
+	}
+
+	/// The dependencies of a package with the spec they were required with,
+	/// using the locked version when the source doesn't record the spec.
+	fn resolved_dependencies(
+		&self,
+		package: &LockedPackage,
+	) -> Vec<(DependencyKind, (String, String))> {
+		package
+			.dependencies
+			.iter()
+			.filter_map(|dependency| {
+				let spec = dependency.spec.clone().or_else(|| {
+					dependency
+						.target
+						.as_deref()
+						.and_then(|id| self.package(id))
+						.map(|target| target.version.clone())
+				})?;
+
+				Some((dependency.kind, (dependency.name.clone(), spec)))
+			})
+			.collect()
+	}
+}
+
+/// The `node_modules` layout of a `package-lock.json` while it is built.
+struct NpmTree<'a> {
+	graph: &'a LockfileGraph,
+	/// The entries keyed by their `node_modules` path.
+	entries: IndexMap<String, LockPackage>,
+	/// The [`package_key`] of the package at each path.
+	placed: IndexMap<String, String>,
+	/// The kind and path of the dependencies of each path.
+	edges: IndexMap<String, Vec<(DependencyKind, String)>>,
+	/// The paths whose dependencies haven't been placed yet.
+	queue: VecDeque<(String, Option<&'a LockedPackage>, &'a [LockedDependency])>,
+	issues: Vec<ConversionIssue>,
+}
+
+impl<'a> NpmTree<'a> {
+	fn new(graph: &'a LockfileGraph) -> Self {
+		Self {
+			graph,
+			entries: IndexMap::new(),
+			placed: IndexMap::new(),
+			edges: IndexMap::new(),
+			queue: VecDeque::new(),
+			issues: Vec::new(),
+		}
+	}
+
+	/// Add the entry of an importer and link it from the root
+	/// `node_modules` when it is a workspace member.
+	fn add_importer(&mut self, path: &str, package_json: &PackageJson) {
+		let location = npm_importer_path(path);
+		let mut entry = importer_entry(package_json);
+
+		if location.is_empty() {
+			entry.workspaces.clone_from(&package_json.workspaces);
+		} else if let Some(name) = &package_json.name {
+			let link = format!("node_modules/{name}");
+			self.placed.insert(link.clone(), format!("link:{location}"));
+			self.entries.insert(
+				link,
+				LockPackage::builder()
+					.resolved(location.clone())
+					.link(true)
+					.build(),
+			);
+		} else {
+			self.issues.push(ConversionIssue::UnnamedImporter {
+				path: path.to_string(),
+			});
+		}
+
+		self.entries.insert(location.clone(), entry);
+
+		if let Some(importer) = self.graph.importer(path) {
+			self.queue
+				.push_back((location, None, &importer.dependencies));
+		}
+	}
+
+	/// Find or place the package a dependency of `location` resolves to.
+	fn resolve(
+		&mut self,
+		location: &str,
+		dependent: Option<&LockedPackage>,
+		dependency: &LockedDependency,
+	) {
+		let graph = self.graph;
+		let Some(package) = graph.target_of(dependency, dependent, location, &mut self.issues)
+		else {
+			return;
+		};
+		let key = package_key(package);
+		let candidates = node_modules_candidates(location, &dependency.name);
+		let nested = candidates[0].clone();
+		let mut found = None;
+
+		for candidate in candidates {
+			match self.placed.get(&candidate) {
+				Some(existing) if *existing == key => {
+					found = Some(candidate);
+					break;
+				}
+				Some(_) if candidate == nested => {
+					self.issues.push(ConversionIssue::Merged {
+						package: describe(package),
+					});
+					found = Some(candidate);
+					break;
+				}
+				Some(_) => {
+					found = Some(self.place(nested, &dependency.name, package));
+					break;
+				}
+				None => {}
+			}
+		}
+
+		let target = found.unwrap_or_else(|| {
+			self.place(
+				format!("node_modules/{}", dependency.name),
+				&dependency.name,
+				package,
+			)
+		});
+
+		self.edges
+			.entry(location.to_string())
+			.or_default()
+			.push((dependency.kind, target));
+	}
+
+	/// Add a package at a `node_modules` path and queue its dependencies.
+	fn place(&mut self, location: String, name: &str, package: &'a LockedPackage) -> String {
+		self.placed.insert(location.clone(), package_key(package));
+
+		let entry = package.link.as_ref().map_or_else(
+			|| {
+				self.queue
+					.push_back((location.clone(), Some(package), &package.dependencies));
+				self.package_entry(name, package)
+			},
+			|link| {
+				LockPackage::builder()
+					.resolved(npm_importer_path(link))
+					.link(true)
+					.build()
+			},
+		);
+
+		self.entries.insert(location.clone(), entry);
+		location
+	}
+
+	/// The entry of a package installed from the registry or a tarball.
+	fn package_entry(&mut self, name: &str, package: &LockedPackage) -> LockPackage {
+		let mut entry = LockPackage {
+			version: Some(package.version.clone()),
+			name: (package.name != name).then(|| package.name.clone()),
+			resolved: tarball_url(package),
+			integrity: package.integrity.clone(),
+			os: (!package.os.is_empty()).then(|| package.os.clone()),
+			cpu: (!package.cpu.is_empty()).then(|| package.cpu.clone()),
+			..LockPackage::default()
+		};
+
+		for (kind, (dependency, spec)) in self.graph.resolved_dependencies(package) {
+			let map = match kind {
+				DependencyKind::Optional => &mut entry.optional_dependencies,
+				DependencyKind::Peer => &mut entry.peer_dependencies,
+				_ => &mut entry.dependencies,
+			};
+			map.get_or_insert_with(IndexMap::new)
+				.insert(dependency, spec);
+		}
+
+		report_missing(
+			package,
+			&mut self.issues,
+			&[
+				("resolved", entry.resolved.is_none()),
+				("integrity", entry.integrity.is_none()),
+			],
+		);
+		entry
+	}
+}
+
+/// The `packages` entry of a package with its resolution and platforms.
+fn pnpm_package(package: &LockedPackage) -> PnpmPackage {
+	let mut resolution = PnpmResolution {
+		integrity: package.integrity.clone(),
+		..PnpmResolution::default()
+	};
+
+	match package_source(package) {
+		PackageSource::Registry | PackageSource::Other => {}
+		PackageSource::Tarball(url) => {
+			resolution.tarball =
+				(package.integrity.is_none() || !is_registry_url(url)).then(|| url.to_string());
+		}
+		PackageSource::Git { repo, commit } => {
+			resolution.type_ = Some("git".into());
+			resolution.repo = Some(repo.to_string());
+			resolution.commit = Some(commit.to_string());
+		}
+		PackageSource::Directory(path) => {
+			resolution.type_ = Some("directory".into());
+			resolution.directory = Some(path.to_string());
+		}
+	}
+
+	let peers: IndexMap<String, String> = package
+		.dependencies
+		.iter()
+		.filter(|dependency| dependency.kind == DependencyKind::Peer)
+		.map(|dependency| {
+			(
+				dependency.name.clone(),
+				dependency.spec.clone().unwrap_or_else(|| "*".into()),
+			)
+		})
+		.collect();
+
+	PnpmPackage {
+		os: (!package.os.is_empty()).then(|| package.os.clone()),
+		cpu: (!package.cpu.is_empty()).then(|| package.cpu.clone()),
+		peer_dependencies: (!peers.is_empty()).then_some(peers),
+		..PnpmPackage::builder().resolution(resolution).build()
+	}
+}
+
+/// The descriptor of a dependency and the range its dependent records.
+/// Yarn berry prefixes registry ranges with the `npm:` protocol.
+fn yarn_descriptor(
+	name: &str,
+	spec: &str,
+	package: &LockedPackage,
+	flavor: YarnLockFlavor,
+) -> (String, String) {
+	let spec = if package.name == name {
+		spec.to_string()
+	} else {
+		format!("npm:{}@{spec}", package.name)
+	};
+
+	if flavor == YarnLockFlavor::Berry && !spec.contains(':') {
+		(format!("{name}@npm:{spec}"), format!("npm:{spec}"))
+	} else {
+		(format!("{name}@{spec}"), spec)
+	}
+}
+
+/// The entry of a package without its dependencies.
+fn yarn_entry(package: &LockedPackage, flavor: YarnLockFlavor) -> YarnLockEntry {
+	let mut entry = YarnLockEntry::builder()
+		.version(package.version.clone())
+		.build();
+
+	if flavor == YarnLockFlavor::Berry {
+		let conditions: Vec<String> = [("os", &package.os), ("cpu", &package.cpu)]
+			.into_iter()
+			.flat_map(|(field, values)| values.iter().map(move |value| format!("{field}={value}")))
+			.collect();
+
+		let reference = match package_source(package) {
+			PackageSource::Tarball(url) if !is_registry_url(url) && !url.contains("/-/") => {
+				url.to_string()
+			}
+			PackageSource::Git { repo, commit } => format!("{repo}#commit={commit}"),
+			_ => format!("npm:{}", package.version),
+		};
+
+		entry.resolution = Some(format!("{}@{reference}", package.name));
+		entry.conditions = (!conditions.is_empty()).then(|| conditions.join(" & "));
+		entry.language_name = Some("node".into());
+		entry.link_type = Some("hard".into());
+	} else {
+		entry.resolved = tarball_url(package);
+		entry.integrity.clone_from(&package.integrity);
+	}
+
+	entry
+}
+
+/// The yarn berry entry of a workspace, which records the ranges of its
+/// manifest rather than the resolved versions.
+fn workspace_entry(
+	path: &str,
+	package_json: &PackageJson,
+	descriptors: &IndexMap<String, IndexSet<String>>,
+) -> YarnLockEntry {
+	let path = normalize_pnpm_importer(path);
+	let name = package_json.name.as_deref().unwrap_or_default();
+	let locator = format!("{name}@workspace:{path}");
+	let mut keys: Vec<String> = descriptors
+		.get(&format!("link:{path}"))
+		.into_iter()
+		.flatten()
+		.cloned()
+		.collect();
+	keys.push(locator.clone());
+	keys.sort();
+	keys.dedup();
+
+	let mut entry = YarnLockEntry::builder()
+		.descriptors(keys)
+		.version("0.0.0-use.local")
+		.resolution(locator)
+		.language_name("unknown")
+		.link_type("soft")
+		.build();
+	let mut optional = serde_json::Map::new();
+
+	for (kind, name, spec) in package_json.all_dependencies() {
+		let spec = if spec.contains(':') {
+			spec.to_string()
+		} else {
+			format!("npm:{spec}")
+		};
+		let map = if kind == DependencyKind::Peer {
+			&mut entry.peer_dependencies
+		} else {
+			&mut entry.dependencies
+		};
+
+		if kind == DependencyKind::Optional {
+			optional.insert(name.to_string(), serde_json::json!({ "optional": true }));
+		}
+
+		map.get_or_insert_with(IndexMap::new)
+			.insert(name.to_string(), spec);
+	}
+
+	entry.dependencies_meta = (!optional.is_empty()).then(|| serde_json::Value::Object(optional));
+	entry
+}
+
+/// Report the fields of a package which a `yarn.lock` can't store or which
+/// the graph doesn't record.
+fn report_yarn_fields(
+	package: &LockedPackage,
+	flavor: YarnLockFlavor,
+	issues: &mut Vec<ConversionIssue>,
+) {
+	if flavor == YarnLockFlavor::Berry {
+		if package.integrity.is_some() {
+			issues.push(ConversionIssue::DroppedField {
+				package: describe(package),
+				field: "integrity",
+			});
+		}
+
+		report_source(package, issues, |source| {
+			matches!(source, PackageSource::Directory(_) | PackageSource::Other)
+		});
+		return;
+	}
+
+	let has_peers = package
+		.dependencies
+		.iter()
+		.any(|dependency| dependency.kind == DependencyKind::Peer);
+	let dropped = [
+		("os", !package.os.is_empty()),
+		("cpu", !package.cpu.is_empty()),
+		("peerDependencies", has_peers),
+	];
+
+	for (field, _) in dropped.into_iter().filter(|(_, dropped)| *dropped) {
+		issues.push(ConversionIssue::DroppedField {
+			package: describe(package),
+			field,
+		});
+	}
+
+	report_missing(
+		package,
+		issues,
+		&[
+			("resolved", tarball_url(package).is_none()),
+			("integrity", package.integrity.is_none()),
+		],
+	);
+}
+
+/// Report the source of a package when the target format can't record it.
+fn report_source(
+	package: &LockedPackage,
+	issues: &mut Vec<ConversionIssue>,
+	unsupported: impl Fn(&PackageSource<'_>) -> bool,
+) {
+	let source = package_source(package);
+
+	if let (true, Some(resolved)) = (unsupported(&source), &package.resolved) {
+		issues.push(ConversionIssue::UnsupportedSource {
+			package: describe(package),
+			source: resolved.clone(),
+		});
+	}
+}
+
+/// Report the fields which are missing.
+fn report_missing(
+	package: &LockedPackage,
+	issues: &mut Vec<ConversionIssue>,
+	fields: &[(&'static str, bool)],
+) {
+	for (field, _) in fields.iter().filter(|(_, missing)| *missing) {
+		issues.push(ConversionIssue::MissingField {
+			package: describe(package),
+			field,
+		});
+	}
+}
+
+/// Set `dev`, `optional` and `devOptional` the way npm does from how the
+/// packages are reached from the importers.
+fn mark_npm_flags(
+	entries: &mut IndexMap<String, LockPackage>,
+	edges: &IndexMap<String, Vec<(DependencyKind, String)>>,
+	manifests: &[(&str, &PackageJson)],
+) {
+	let importers: Vec<String> = manifests
+		.iter()
+		.map(|(path, _)| npm_importer_path(path))
+		.collect();
+	let reach = |root_kinds: &[DependencyKind], follow_optional: bool| {
+		let mut seen: IndexSet<String> = IndexSet::new();
+		let mut queue: Vec<String> = importers
+			.iter()
+			.flat_map(|importer| edges.get(importer).into_iter().flatten())
+			.filter(|(kind, _)| root_kinds.contains(kind))
+			.map(|(_, path)| path.clone())
+			.collect();
+
+		while let Some(path) = queue.pop() {
+			if !seen.insert(path.clone()) {
+				continue;
+			}
+
+			queue.extend(
+				edges
+					.get(&path)
+					.into_iter()
+					.flatten()
+					.filter(|(kind, _)| follow_optional || *kind != DependencyKind::Optional)
+					.map(|(_, path)| path.clone()),
+			);
+		}
+
+		seen
+	};
+	let non_dev = reach(
+		&[
+			DependencyKind::Prod,
+			DependencyKind::Optional,
+			DependencyKind::Peer,
+		],
+		true,
+	);
+	let non_optional = reach(
+		&[
+			DependencyKind::Prod,
+			DependencyKind::Dev,
+			DependencyKind::Peer,
+		],
+		false,
+	);
+	let required = reach(&[DependencyKind::Prod, DependencyKind::Peer], false);
+
+	for (path, entry) in entries.iter_mut() {
+		if importers.contains(path) || entry.link == Some(true) {
+			continue;
+		}
+
+		let dev = !non_dev.contains(path);
+		let optional = !non_optional.contains(path);
+
+		if !dev && !optional && !required.contains(path) {
+			entry.dev_optional = Some(true);
+		} else {
+			entry.dev = dev.then_some(true);
+			entry.optional = optional.then_some(true);
+		}
+	}
+}
+
+/// The entry of an importer with the dependencies of its manifest.
+fn importer_entry(package_json: &PackageJson) -> LockPackage {
+	LockPackage {
+		name: package_json.name.clone(),
+		version: package_json.version.clone(),
+		dependencies: package_json.dependencies.clone(),
+		dev_dependencies: package_json.dev_dependencies.clone(),
+		optional_dependencies: package_json.optional_dependencies.clone(),
+		peer_dependencies: package_json.peer_dependencies.clone(),
+		..LockPackage::default()
+	}
+}
+
+/// The `node_modules` folders node searches for a dependency, nearest first.
+fn node_modules_candidates(location: &str, name: &str) -> Vec<String> {
+	let mut candidates = Vec::new();
+	let mut current = location;
+
+	while !current.is_empty() {
+		candidates.push(format!("{current}/node_modules/{name}"));
+		current = current
+			.rfind("/node_modules/")
+			.map_or("", |index| &current[..index]);
+	}
+
+	candidates.push(format!("node_modules/{name}"));
+	candidates
+}
+
+/// The path of an importer in a `package-lock.json`, which is empty for the
+/// root.
+fn npm_importer_path(path: &str) -> String {
+	let path = path.trim_start_matches("./").trim_end_matches('/');
+
+	if path == "." {
+		String::new()
+	} else {
+		path.to_string()
+	}
+}
+
+/// The path of an importer in a `pnpm-lock.yaml`, which is `.` for the root.
+fn normalize_pnpm_importer(path: &str) -> String {
+	let path = npm_importer_path(path);
+
+	if path.is_empty() {
+		".".to_string()
+	} else {
+		path
+	}
+}
+
+/// The version of a dependency in a `pnpm-lock.yaml`, which is `link:` a
+/// relative path for workspace packages and `name@version` for aliases.
+fn pnpm_version(importer: &str, name: &str, package: &LockedPackage) -> String {
+	if let Some(link) = &package.link {
+		return format!("link:{}", relative_path(importer, link));
+	}
+
+	if package.name == name {
+		package.version.clone()
+	} else {
+		format!("{}@{}", package.name, package.version)
+	}
+}
+
+/// The path of `to` relative to `from`, both relative to the root.
+fn relative_path(from: &str, to: &str) -> String {
+	let segments = |path: &str| -> Vec<String> {
+		npm_importer_path(path)
+			.split('/')
+			.filter(|segment| !segment.is_empty())
+			.map(ToString::to_string)
+			.collect()
+	};
+	let from = segments(from);
+	let to = segments(to);
+	let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+	let path: Vec<String> = std::iter::repeat("..".to_string())
+		.take(from.len() - common)
+		.chain(to[common..].iter().cloned())
+		.collect();
+
+	if path.is_empty() {
+		".".to_string()
+	} else {
+		path.join("/")
+	}
+}
+
+/// A key which is equal for every copy of the same package.
+fn package_key(package: &LockedPackage) -> String {
+	package.link.as_ref().map_or_else(
+		|| format!("{}@{}", package.name, package.version),
+		|link| format!("link:{link}"),
+	)
+}
+
+fn describe(package: &LockedPackage) -> String {
+	format!("{}@{}", package.name, package.version)
+}
+
+/// The tarball url of a package without the hash fragment yarn classic
+/// appends.
+fn tarball_url(package: &LockedPackage) -> Option<String> {
+	package
+		.resolved
+		.as_deref()
+		.filter(|resolved| resolved.starts_with("https://") || resolved.starts_with("http://"))
+		.map(|resolved| resolved.split('#').next().unwrap_or(resolved).to_string())
+}
+
+/// Where a package is fetched from, read from its `resolved` field.
+enum PackageSource<'a> {
+	/// A registry, which is assumed when nothing is resolved.
+	Registry,
+
+	/// A tarball url, which may be in a registry.
+	Tarball(&'a str),
+
+	/// A commit of a git repository.
+	Git { repo: &'a str, commit: &'a str },
+
+	/// A local directory.
+	Directory(&'a str),
+
+	/// Any other source such as a local tarball or a git url without a
+	/// commit.
+	Other,
+}
+
+fn package_source(package: &LockedPackage) -> PackageSource<'_> {
+	let Some(resolved) = package.resolved.as_deref() else {
+		return PackageSource::Registry;
+	};
+	let is_tarball = |path: &str| {
+		[".tgz", ".tar.gz", ".tar"]
+			.iter()
+			.any(|extension| path.ends_with(extension))
+	};
+
+	if resolved.starts_with("https://") || resolved.starts_with("http://") {
+		return PackageSource::Tarball(resolved.split('#').next().unwrap_or(resolved));
+	}
+
+	if let Some(url) = resolved
+		.strip_prefix("git+")
+		.or_else(|| resolved.starts_with("git://").then_some(resolved))
+	{
+		if let Some((repo, commit)) = url.split_once('#').filter(|(_, commit)| !commit.is_empty()) {
+			return PackageSource::Git { repo, commit };
+		}
+	}
+
+	match resolved.strip_prefix("file:") {
+		Some(path) if !is_tarball(path) => PackageSource::Directory(path),
+		_ => PackageSource::Other,
+	}
+}
+
+fn is_registry_url(url: &str) -> bool {
+	[
+		"https://registry.npmjs.org/",
+		"https://registry.yarnpkg.com/",
+	]
+	.iter()
+	.any(|registry| url.starts_with(registry))
+}
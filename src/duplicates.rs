@@ -0,0 +1,182 @@
+use indexmap::IndexMap;
+
+use crate::parse_version;
+use crate::parse_yarn_descriptor;
+use crate::LockfileGraph;
+use crate::VersionRange;
+
+/// A package or project which depends on a duplicated package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateDependent {
+	/// The importer path for projects and `name@version` for packages.
+	pub dependent: String,
+
+	/// The range the dependency is required with, when the lockfile records
+	/// it.
+	pub range: Option<String>,
+}
+
+/// A single version of a [`DuplicatePackage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateVersion {
+	/// The locked version.
+	pub version: String,
+
+	/// The ids of the packages installed with this version.
+	pub ids: Vec<String>,
+
+	/// The dependents which resolve to this version.
+	pub dependents: Vec<DuplicateDependent>,
+}
+
+/// A package installed with more than one version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicatePackage {
+	/// The name of the package.
+	pub name: String,
+
+	/// The installed versions, lowest first.
+	pub versions: Vec<DuplicateVersion>,
+
+	/// Whether a single version satisfies the range of every dependent.
+	/// `None` when a range isn't known or isn't a semver range, e.g. for
+	/// pnpm lockfiles which only record the resolved versions.
+	pub dedupable: Option<bool>,
+
+	/// The highest installed version which satisfies every range.
+	pub dedupe_to: Option<String>,
+}
+
+impl LockfileGraph {
+	/// Find the packages which are installed with more than one version,
+	/// ordered by name.
+	///
+	/// ```
+	/// use package_json_schema::LockfileGraph;
+	/// use package_json_schema::PackageLock;
+	///
+	/// let contents = r#"{
+	///   "lockfileVersion": 3,
+	///   "packages": {
+	///     "": { "dependencies": { "a": "^1.0.0", "ms": "^2.1.0" } },
+	///     "node_modules/a": { "version": "1.0.0", "dependencies": { "ms": "^2.0.0" } },
+	///     "node_modules/a/node_modules/ms": { "version": "2.0.0" },
+	///     "node_modules/ms": { "version": "2.1.3" }
+	///   }
+	/// }"#;
+	/// let package_lock = PackageLock::try_from(contents).unwrap();
+	/// let graph = LockfileGraph::from_package_lock(&package_lock);
+	/// let duplicates = graph.duplicates();
+	///
+	/// assert_eq!(duplicates[0].name, "ms");
+	/// assert_eq!(duplicates[0].dedupable, Some(true));
+	/// assert_eq!(duplicates[0].dedupe_to.as_deref(), Some("2.1.3"));
+	/// ```
+	#[must_use]
+	pub fn duplicates(&self) -> Vec<DuplicatePackage> {
+		let mut names: IndexMap<&str, IndexMap<&str, DuplicateVersion>> = IndexMap::new();
+
+		for (id, package) in &self.packages {
+			if package.link.is_some() || package.version.is_empty() {
+				continue;
+			}
+
+			names
+				.entry(package.name.as_str())
+				.or_default()
+				.entry(package.version.as_str())
+				.or_insert_with(|| {
+					DuplicateVersion {
+						version: package.version.clone(),
+						ids: Vec::new(),
+						dependents: Vec::new(),
+					}
+				})
+				.ids
+				.push(id.clone());
+		}
+
+		names.retain(|_, versions| versions.len() > 1);
+
+		let importers = self
+			.importers
+			.iter()
+			.map(|(path, importer)| (path.clone(), &importer.dependencies));
+		let packages = self.packages.values().map(|package| {
+			(
+				format!("{}@{}", package.name, package.version),
+				&package.dependencies,
+			)
+		});
+
+		for (dependent, dependencies) in importers.chain(packages) {
+			for dependency in dependencies {
+				let Some(target) = dependency.target.as_deref().and_then(|id| self.package(id))
+				else {
+					continue;
+				};
+				let Some(version) = names
+					.get_mut(target.name.as_str())
+					.and_then(|versions| versions.get_mut(target.version.as_str()))
+				else {
+					continue;
+				};
+
+				version.dependents.push(DuplicateDependent {
+					dependent: dependent.clone(),
+					range: dependency.spec.clone(),
+				});
+			}
+		}
+
+		let mut duplicates: Vec<DuplicatePackage> = names
+			.into_iter()
+			.map(|(name, versions)| {
+				let mut versions: Vec<DuplicateVersion> = versions.into_values().collect();
+				versions.sort_by(|a, b| {
+					match (parse_version(&a.version), parse_version(&b.version)) {
+						(Some(a), Some(b)) => a.cmp_precedence(&b),
+						_ => a.version.cmp(&b.version),
+					}
+				});
+
+				let ranges: Option<Vec<VersionRange>> = versions
+					.iter()
+					.flat_map(|version| &version.dependents)
+					.map(|dependent| dependent.range.as_deref().and_then(parse_dependent_range))
+					.collect();
+				let dedupable = ranges.as_deref().map(VersionRange::all_intersect);
+				let dedupe_to = ranges.as_ref().and_then(|ranges| {
+					versions
+						.iter()
+						.rev()
+						.find(|version| {
+							parse_version(&version.version).is_some_and(|parsed| {
+								ranges.iter().all(|range| range.satisfies(&parsed))
+							})
+						})
+						.map(|version| version.version.clone())
+				});
+
+				DuplicatePackage {
+					name: name.to_string(),
+					versions,
+					dedupable,
+					dedupe_to,
+				}
+			})
+			.collect();
+
+		duplicates.sort_by(|a, b| a.name.cmp(&b.name));
+		duplicates
+	}
+}
+
+/// The range of a dependency, looking through `npm:` aliases.
+fn parse_dependent_range(spec: &str) -> Option<VersionRange> {
+	let spec = spec.strip_prefix("npm:").map_or(spec, |alias| {
+		parse_yarn_descriptor(alias).map_or(alias, |(_, range)| range)
+	});
+
+	VersionRange::parse(spec).ok()
+}
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 /// The errors resulting from parsing files.
 ///
 /// Structure taken from [here](https://kazlauskas.me/entries/errors).
@@ -12,6 +14,130 @@ pub enum Error {
 
 	#[error("failure to serialize the current `PackageJson` instance")]
 	SerializePackageJson(#[source] serde_json::Error),
+
+	#[error("invalid resolution pattern `{pattern}`: {reason}")]
+	InvalidResolutionPattern { pattern: String, reason: String },
+
+	#[error("invalid version range `{range}`: {reason}")]
+	InvalidVersionRange { range: String, reason: String },
+
+	#[error("failure to read the file `{path}`")]
+	ReadFile {
+		path: PathBuf,
+		#[source]
+		source: std::io::Error,
+	},
+
+	#[error("failure to parse the package.json file `{path}`")]
+	ParsePackageJsonFile {
+		path: PathBuf,
+		#[source]
+		source: serde_json::Error,
+	},
+
+	#[error("failure to parse a pnpm-workspace.yaml from provided content")]
+	ParsePnpmWorkspace(#[source] serde_yaml::Error),
+
+	#[error("invalid glob pattern `{pattern}`")]
+	InvalidGlob {
+		pattern: String,
+		#[source]
+		source: globset::Error,
+	},
+
+	#[error("dependency cycle detected: {}", cycle.join(" -> "))]
+	DependencyCycle { cycle: Vec<String> },
+
+	#[error("failure to resolve the workspace dependency `{name}` with `{spec}`")]
+	UnresolvedWorkspaceDependency { name: String, spec: String },
+
+	#[error("failure to resolve the catalog dependency `{name}` with `{spec}`")]
+	UnresolvedCatalogDependency { name: String, spec: String },
+
+	#[error("the default catalog is defined by both `catalog` and `catalogs.default`")]
+	DuplicateDefaultCatalog,
+
+	#[error("the package.json has no version")]
+	MissingVersion,
+
+	#[error("invalid version `{version}`")]
+	InvalidVersion { version: String },
+
+	#[error("invalid release type `{0}`")]
+	InvalidReleaseType(String),
+
+	#[error("invalid prerelease identifier `{0}`")]
+	InvalidPrereleaseIdentifier(String),
+
+	#[error("the prerelease number of the version `{version}` is too large to increment")]
+	PrereleaseOverflow { version: String },
+
+	#[error("the version `{next}` must be greater than the current version `{current}`")]
+	VersionNotIncreased { current: String, next: String },
+
+	#[error("the package `{0}` is not a member of the workspace")]
+	UnknownWorkspaceMember(String),
+
+	#[error("failure to parse a package-lock.json from provided content")]
+	ParsePackageLock(#[source] serde_json::Error),
+
+	#[error("failure to serialize the current `PackageLock` instance")]
+	SerializePackageLock(#[source] serde_json::Error),
+
+	#[error("failure to parse a pnpm-lock.yaml from provided content")]
+	ParsePnpmLock(#[source] serde_yaml::Error),
+
+	#[error("failure to serialize the current `PnpmLock` instance")]
+	SerializePnpmLock(#[source] serde_yaml::Error),
+
+	#[error("failure to parse a yarn.lock from provided content")]
+	ParseYarnLock(#[source] serde_yaml::Error),
+
+	#[error("invalid yarn.lock at line {line}: {reason}")]
+	InvalidYarnLock { line: usize, reason: String },
+
+	#[error("failure to convert the yarn.lock to a string")]
+	SerializeYarnLock(#[source] serde_yaml::Error),
+
+	#[error("failure to parse a bun.lock from provided content")]
+	ParseBunLock(#[source] serde_json::Error),
+
+	#[error("failure to parse a packument from provided content")]
+	ParsePackument(#[source] serde_json::Error),
+
+	#[error("failure to parse the packument file `{path}`")]
+	ParsePackumentFile {
+		path: PathBuf,
+		#[source]
+		source: serde_json::Error,
+	},
+
+	#[cfg(feature = "registry")]
+	#[error("failure to send the registry request to `{url}`")]
+	RegistryRequest {
+		url: String,
+		#[source]
+		source: Box<ureq::Transport>,
+	},
+
+	#[cfg(feature = "registry")]
+	#[error("the registry responded to `{url}` with the status {status}")]
+	RegistryStatus { url: String, status: u16 },
+
+	#[cfg(feature = "registry")]
+	#[error("failure to read the registry response from `{url}`")]
+	RegistryResponse {
+		url: String,
+		#[source]
+		source: std::io::Error,
+	},
+
+	#[cfg(feature = "registry")]
+	#[error("the manifest of `{name}@{version}` has no tarball url")]
+	MissingTarball { name: String, version: String },
+
+	#[error("failure to convert the lockfile diff to a string")]
+	SerializeLockfileDiff(#[source] serde_json::Error),
 }
 
 /// A result type for `package_json_schema`.
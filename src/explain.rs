@@ -0,0 +1,242 @@
+use std::fmt::Display;
+
+use indexmap::IndexMap;
+use indexmap::IndexSet;
+
+use crate::parse_version;
+use crate::parse_yarn_descriptor;
+use crate::DependencyKind;
+use crate::LockedDependency;
+use crate::LockfileGraph;
+use crate::PackageJson;
+use crate::VersionRange;
+
+/// A package along a [`DependencyPath`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyStep {
+	/// The id of the package in [`LockfileGraph::packages`].
+	pub id: String,
+
+	/// The name the package is installed under.
+	pub name: String,
+
+	/// The locked version, empty when the lockfile doesn't record one.
+	pub version: String,
+}
+
+impl Display for DependencyStep {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.version.is_empty() {
+			write!(f, "{}", self.name)
+		} else {
+			write!(f, "{}@{}", self.name, self.version)
+		}
+	}
+}
+
+/// A chain of dependencies from a dependency declared by the root
+/// `package.json` to a package matching a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyPath {
+	/// The field of the root `package.json` which declares the first step.
+	pub kind: DependencyKind,
+
+	/// The packages from the declared dependency to the matching package,
+	/// which is the last step.
+	pub steps: Vec<DependencyStep>,
+}
+
+impl DependencyPath {
+	/// The matching package.
+	#[must_use]
+	pub fn target(&self) -> &DependencyStep {
+		&self.steps[self.steps.len() - 1]
+	}
+}
+
+impl Display for DependencyPath {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let steps: Vec<String> = self.steps.iter().map(ToString::to_string).collect();
+		write!(f, "{}: {}", self.kind, steps.join(" > "))
+	}
+}
+
+/// How the search of [`LockfileGraph::explain`] first reached a package.
+struct Reached<'a> {
+	/// The package which depends on it, `None` for dependencies of the root.
+	parent: Option<&'a str>,
+
+	/// The name it is installed under.
+	name: &'a str,
+
+	/// The position of the root declaration the search started from.
+	root: usize,
+
+	/// The field of the root declaration.
+	kind: DependencyKind,
+}
+
+impl LockfileGraph {
+	/// Explain why a package is installed, like `npm explain`, by listing
+	/// how the installed packages matching the query are reached from the
+	/// dependencies declared by the root `package.json`.
+	///
+	/// The query is a package name, optionally followed by `@` and a version
+	/// or a range. Every matching package gets one path for each declaration
+	/// and each package which depends on it, so diamonds don't multiply the
+	/// paths. A dependent is reached along its shortest chain, going through
+	/// workspace links and never through another matching package.
+	///
+	/// ```
+	/// use package_json_schema::LockfileGraph;
+	/// use package_json_schema::PackageJson;
+	/// use package_json_schema::PackageLock;
+	///
+	/// let contents = r#"{
+	///   "lockfileVersion": 3,
+	///   "packages": {
+	///     "": { "devDependencies": { "mkdirp": "^0.5.1" } },
+	///     "node_modules/mkdirp": {
+	///       "version": "0.5.1",
+	///       "dependencies": { "minimist": "0.0.8" }
+	///     },
+	///     "node_modules/minimist": { "version": "0.0.8" }
+	///   }
+	/// }"#;
+	/// let manifest = r#"{ "devDependencies": { "mkdirp": "^0.5.1" } }"#;
+	/// let package_json = PackageJson::try_from(manifest).unwrap();
+	/// let package_lock = PackageLock::try_from(contents).unwrap();
+	/// let graph = LockfileGraph::from_package_lock(&package_lock);
+	/// let paths = graph.explain(&package_json, "minimist@0.0.8");
+	/// let expected = "devDependencies: mkdirp@0.5.1 > minimist@0.0.8";
+	///
+	/// assert_eq!(paths[0].to_string(), expected);
+	/// ```
+	#[must_use]
+	pub fn explain(&self, package_json: &PackageJson, query: &str) -> Vec<DependencyPath> {
+		let (name, spec) = parse_yarn_descriptor(query).unwrap_or((query, ""));
+		let range = VersionRange::parse(spec).ok().filter(|_| !spec.is_empty());
+		let matches = |id: &str| {
+			self.packages.get(id).is_some_and(|package| {
+				package.name == name
+					&& (spec.is_empty()
+						|| package.version == spec
+						|| range.as_ref().is_some_and(|range| {
+							parse_version(&package.version)
+								.is_some_and(|version| range.satisfies(&version))
+						}))
+			})
+		};
+		let root = self.importer(".");
+		let mut reached: IndexMap<&str, Reached<'_>> = IndexMap::new();
+		let mut paths: Vec<(usize, DependencyPath)> = Vec::new();
+
+		for (index, (kind, declared, _)) in package_json.all_dependencies().enumerate() {
+			let targets = root
+				.into_iter()
+				.flat_map(|importer| &importer.dependencies)
+				.filter(|dependency| dependency.name == declared);
+
+			for dependency in targets {
+				let Some(id) = self.target_id(dependency) else {
+					continue;
+				};
+
+				if matches(id) {
+					let steps = vec![self.step(id, &dependency.name)];
+					paths.push((index, DependencyPath { kind, steps }));
+				}
+
+				reached.entry(id).or_insert(Reached {
+					parent: None,
+					name: &dependency.name,
+					root: index,
+					kind,
+				});
+			}
+		}
+
+		// Search breadth first so every package is reached along its shortest
+		// chain, and record the dependents of the matching packages.
+		let mut next = 0;
+
+		while let Some((&id, current)) = reached.get_index(next) {
+			let (root, kind) = (current.root, current.kind);
+			next += 1;
+
+			if matches(id) {
+				continue;
+			}
+
+			let mut seen = IndexSet::new();
+
+			for edge in self
+				.edges(id)
+				.iter()
+				.filter(|edge| edge.kind != DependencyKind::Dev)
+			{
+				let Some(target) = self.target_id(edge).filter(|target| seen.insert(*target))
+				else {
+					continue;
+				};
+
+				if matches(target) {
+					let mut steps = self.chain(&reached, id);
+					steps.push(self.step(target, &edge.name));
+					paths.push((root, DependencyPath { kind, steps }));
+				}
+
+				reached.entry(target).or_insert(Reached {
+					parent: Some(id),
+					name: &edge.name,
+					root,
+					kind,
+				});
+			}
+		}
+
+		paths.sort_by_cached_key(|(root, path)| (*root, path.to_string()));
+		paths.into_iter().map(|(_, path)| path).collect()
+	}
+
+	/// The id of the package a dependency resolves to.
+	fn target_id<'a>(&'a self, dependency: &LockedDependency) -> Option<&'a str> {
+		let id = dependency.target.as_deref()?;
+		self.packages.get_key_value(id).map(|(id, _)| id.as_str())
+	}
+
+	/// The dependencies of a package, following workspace links to their
+	/// importer.
+	fn edges(&self, id: &str) -> &[LockedDependency] {
+		let package = &self.packages[id];
+
+		package
+			.link
+			.as_deref()
+			.and_then(|path| self.importer(path))
+			.map_or(&package.dependencies, |importer| &importer.dependencies)
+	}
+
+	/// The steps from the root to a reached package.
+	fn chain(&self, reached: &IndexMap<&str, Reached<'_>>, id: &str) -> Vec<DependencyStep> {
+		let mut steps = Vec::new();
+		let mut current = Some(id);
+
+		while let Some(id) = current {
+			let entry = &reached[id];
+			steps.push(self.step(id, entry.name));
+			current = entry.parent;
+		}
+
+		steps.reverse();
+		steps
+	}
+
+	fn step(&self, id: &str, name: &str) -> DependencyStep {
+		DependencyStep {
+			id: id.to_string(),
+			name: name.to_string(),
+			version: self.packages[id].version.clone(),
+		}
+	}
+}
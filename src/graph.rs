@@ -0,0 +1,406 @@
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::parse_version;
+use crate::DependencyKind;
+use crate::DependencySpec;
+use crate::VersionRange;
+use crate::Workspace;
+use crate::WorkspaceMember;
+
+/// A dependency of one workspace member on another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceEdge {
+	/// The index of the member declaring the dependency.
+	pub from: usize,
+
+	/// The index of the member which is depended on.
+	pub to: usize,
+
+	/// The field which declares the dependency.
+	pub kind: DependencyKind,
+
+	/// The name used as the key of the dependency field. This differs from
+	/// the name of the target when the dependency is aliased.
+	pub name: String,
+
+	/// The value of the dependency field.
+	pub spec: String,
+}
+
+/// The dependencies between the members of a [`Workspace`].
+///
+/// A dependency links two members when it uses the `workspace:` protocol, an
+/// `npm:` alias or a semver range which the version of the sibling satisfies,
+/// or a `file:` / `link:` path to the directory of the sibling. Nodes are
+/// identified by their index in [`Workspace::members`].
+///
+/// ```
+/// use package_json_schema::Workspace;
+/// use package_json_schema::WorkspaceGraph;
+///
+/// let workspace = Workspace::discover("./tests/fixtures/workspaces/graph").unwrap();
+/// let graph = WorkspaceGraph::new(&workspace);
+/// let batches = graph.topological_batches().unwrap();
+/// let names = batches[0].iter().map(|member| member.name());
+/// let first: Vec<_> = names.flatten().collect();
+///
+/// assert_eq!(first, ["@graph/config", "@graph/core"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WorkspaceGraph<'a> {
+	workspace: &'a Workspace,
+	edges: Vec<WorkspaceEdge>,
+}
+
+impl<'a> WorkspaceGraph<'a> {
+	/// Build the graph from the dependency fields of every member.
+	#[must_use]
+	pub fn new(workspace: &'a Workspace) -> Self {
+		let members = workspace.members();
+		let mut edges = Vec::new();
+
+		for (from, member) in members.iter().enumerate() {
+			for (kind, name, spec) in member.package_json.all_dependencies() {
+				if let Some(to) = find_target(members, member, name, spec) {
+					edges.push(WorkspaceEdge {
+						from,
+						to,
+						kind,
+						name: name.to_string(),
+						spec: spec.to_string(),
+					});
+				}
+			}
+		}
+
+		Self { workspace, edges }
+	}
+
+	/// A graph which only keeps the edges of the provided kinds. This is
+	/// useful to ignore `devDependencies` when ordering a build.
+	#[must_use]
+	pub fn with_kinds(&self, kinds: &[DependencyKind]) -> Self {
+		Self {
+			workspace: self.workspace,
+			edges: self
+				.edges
+				.iter()
+				.filter(|edge| kinds.contains(&edge.kind))
+				.cloned()
+				.collect(),
+		}
+	}
+
+	/// The workspace of the graph.
+	#[must_use]
+	pub const fn workspace(&self) -> &'a Workspace {
+		self.workspace
+	}
+
+	/// The member with the provided index.
+	#[must_use]
+	pub fn member(&self, index: usize) -> &'a WorkspaceMember {
+		&self.workspace.members()[index]
+	}
+
+	/// Every edge of the graph.
+	#[must_use]
+	pub fn edges(&self) -> &[WorkspaceEdge] {
+		&self.edges
+	}
+
+	/// The edges from the named member to the members it depends on.
+	#[must_use]
+	pub fn dependencies(&self, name: &str) -> Vec<&WorkspaceEdge> {
+		self.edges
+			.iter()
+			.filter(|edge| self.member(edge.from).name() == Some(name))
+			.collect()
+	}
+
+	/// The edges to the named member from the members which depend on it.
+	#[must_use]
+	pub fn dependents(&self, name: &str) -> Vec<&WorkspaceEdge> {
+		self.edges
+			.iter()
+			.filter(|edge| self.member(edge.to).name() == Some(name))
+			.collect()
+	}
+
+	/// The indices of every member which directly or indirectly depends on
+	/// one of the provided members, excluding the provided members
+	/// themselves unless they are part of a cycle.
+	#[must_use]
+	pub fn transitive_dependents(&self, indices: &[usize]) -> BTreeSet<usize> {
+		let mut found = BTreeSet::new();
+		let mut queue: VecDeque<usize> = indices.iter().copied().collect();
+
+		while let Some(index) = queue.pop_front() {
+			for edge in self.edges.iter().filter(|edge| edge.to == index) {
+				if found.insert(edge.from) {
+					queue.push_back(edge.from);
+				}
+			}
+		}
+
+		found
+	}
+
+	/// Group the members into batches where every member only depends on
+	/// members from earlier batches. Members of a batch are ordered by their
+	/// relative path and can be processed in parallel.
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::DependencyCycle`] with the first cycle when the
+	/// members can't be ordered.
+	pub fn topological_batches(&self) -> crate::Result<Vec<Vec<&'a WorkspaceMember>>> {
+		if let Some(cycle) = self.cycles().into_iter().next() {
+			return Err(crate::Error::DependencyCycle {
+				cycle: cycle
+					.iter()
+					.map(|member| display_name(member).to_string())
+					.collect(),
+			});
+		}
+
+		let count = self.workspace.members().len();
+		let mut remaining = vec![0_usize; count];
+
+		for (from, _) in self.unique_pairs() {
+			remaining[from] += 1;
+		}
+
+		let mut batches = Vec::new();
+		let mut current: Vec<usize> = (0..count).filter(|index| remaining[*index] == 0).collect();
+
+		while !current.is_empty() {
+			let mut next = Vec::new();
+
+			for (from, to) in self.unique_pairs() {
+				if current.contains(&to) {
+					remaining[from] -= 1;
+
+					if remaining[from] == 0 {
+						next.push(from);
+					}
+				}
+			}
+
+			next.sort_unstable();
+			batches.push(current.iter().map(|index| self.member(*index)).collect());
+			current = next;
+		}
+
+		Ok(batches)
+	}
+
+	/// Every dependency cycle as a path which starts and ends with the same
+	/// member, e.g. `[a, b, a]`. One cycle is reported for each group of
+	/// members which depend on each other.
+	#[must_use]
+	pub fn cycles(&self) -> Vec<Vec<&'a WorkspaceMember>> {
+		let count = self.workspace.members().len();
+		let mut adjacency = vec![Vec::new(); count];
+
+		for (from, to) in self.unique_pairs() {
+			adjacency[from].push(to);
+		}
+
+		strongly_connected_components(&adjacency)
+			.into_iter()
+			.filter(|component| {
+				component.len() > 1 || adjacency[component[0]].contains(&component[0])
+			})
+			.filter_map(|component| cycle_in_component(&adjacency, &component))
+			.map(|path| path.into_iter().map(|index| self.member(index)).collect())
+			.collect()
+	}
+
+	/// The distinct `(from, to)` pairs, since a member can depend on the same
+	/// sibling through several fields.
+	fn unique_pairs(&self) -> BTreeSet<(usize, usize)> {
+		self.edges.iter().map(|edge| (edge.from, edge.to)).collect()
+	}
+}
+
+impl Workspace {
+	/// The dependency graph of the members. See [`WorkspaceGraph`].
+	#[must_use]
+	pub fn graph(&self) -> WorkspaceGraph<'_> {
+		WorkspaceGraph::new(self)
+	}
+}
+
+fn display_name(member: &WorkspaceMember) -> &str {
+	member.name().unwrap_or(&member.relative_path)
+}
+
+/// Find the sibling which a dependency resolves to.
+fn find_target(
+	members: &[WorkspaceMember],
+	member: &WorkspaceMember,
+	name: &str,
+	spec: &str,
+) -> Option<usize> {
+	let satisfies = |sibling: &WorkspaceMember, range: &str| {
+		let version = sibling
+			.package_json
+			.version
+			.as_deref()
+			.and_then(parse_version);
+		let range = VersionRange::parse(range).ok();
+		version
+			.zip(range)
+			.is_some_and(|(version, range)| range.satisfies(&version))
+	};
+	let position = |target: &str, predicate: &dyn Fn(&WorkspaceMember) -> bool| {
+		members
+			.iter()
+			.position(|sibling| sibling.name() == Some(target) && predicate(sibling))
+	};
+
+	match DependencySpec::parse(spec) {
+		DependencySpec::Workspace { name: alias, .. } => position(alias.unwrap_or(name), &|_| true),
+		DependencySpec::Alias { name, range } => {
+			position(name, &|sibling| satisfies(sibling, range))
+		}
+		DependencySpec::Range(range) => position(name, &|sibling| satisfies(sibling, range)),
+		DependencySpec::Path { path, .. } => {
+			let target = normalize_path(&member.path.join(path));
+			members
+				.iter()
+				.position(|sibling| normalize_path(&sibling.path) == target)
+		}
+		DependencySpec::Tag(_) | DependencySpec::Catalog(_) | DependencySpec::Url(_) => None,
+	}
+}
+
+/// Resolve `.` and `..` components without touching the file system.
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+	let mut normalized = PathBuf::new();
+
+	for component in path.components() {
+		match component {
+			Component::CurDir => {}
+			Component::ParentDir => {
+				if !normalized.pop() {
+					normalized.push(component);
+				}
+			}
+			_ => normalized.push(component),
+		}
+	}
+
+	normalized
+}
+
+/// Tarjan's algorithm, returning each component with its indices sorted.
+fn strongly_connected_components(adjacency: &[Vec<usize>]) -> Vec<Vec<usize>> {
+	struct State<'a> {
+		adjacency: &'a [Vec<usize>],
+		index: usize,
+		indices: Vec<Option<usize>>,
+		low_links: Vec<usize>,
+		stack: Vec<usize>,
+		on_stack: Vec<bool>,
+		components: Vec<Vec<usize>>,
+	}
+
+	fn connect(state: &mut State<'_>, node: usize) {
+		state.indices[node] = Some(state.index);
+		state.low_links[node] = state.index;
+		state.index += 1;
+		state.stack.push(node);
+		state.on_stack[node] = true;
+
+		for &next in &state.adjacency[node] {
+			match state.indices[next] {
+				None => {
+					connect(state, next);
+					state.low_links[node] = state.low_links[node].min(state.low_links[next]);
+				}
+				Some(index) if state.on_stack[next] => {
+					state.low_links[node] = state.low_links[node].min(index);
+				}
+				Some(_) => {}
+			}
+		}
+
+		if Some(state.low_links[node]) == state.indices[node] {
+			let mut component = Vec::new();
+
+			while let Some(member) = state.stack.pop() {
+				state.on_stack[member] = false;
+				component.push(member);
+
+				if member == node {
+					break;
+				}
+			}
+
+			component.sort_unstable();
+			state.components.push(component);
+		}
+	}
+
+	let count = adjacency.len();
+	let mut state = State {
+		adjacency,
+		index: 0,
+		indices: vec![None; count],
+		low_links: vec![0; count],
+		stack: Vec::new(),
+		on_stack: vec![false; count],
+		components: Vec::new(),
+	};
+
+	for node in 0..count {
+		if state.indices[node].is_none() {
+			connect(&mut state, node);
+		}
+	}
+
+	state.components.sort();
+	state.components
+}
+
+/// The shortest cycle through the first member of a strongly connected
+/// component, found with a breadth first search that stays inside it.
+fn cycle_in_component(adjacency: &[Vec<usize>], component: &[usize]) -> Option<Vec<usize>> {
+	let start = component[0];
+	let mut parents: Vec<Option<usize>> = vec![None; adjacency.len()];
+	let mut queue = VecDeque::from([start]);
+
+	while let Some(node) = queue.pop_front() {
+		for &next in &adjacency[node] {
+			if !component.contains(&next) {
+				continue;
+			}
+
+			if next == start {
+				let mut path = vec![start, node];
+				let mut current = node;
+
+				while let Some(parent) = parents[current] {
+					path.push(parent);
+					current = parent;
+				}
+
+				path.reverse();
+				return Some(path);
+			}
+
+			if parents[next].is_none() && next != start {
+				parents[next] = Some(node);
+				queue.push_back(next);
+			}
+		}
+	}
+
+	None
+}
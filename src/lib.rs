@@ -6,12 +6,68 @@
 
 doc_comment::doctest!("../readme.md");
 
+pub mod affected;
+pub mod bun_lock;
+pub mod catalog;
+pub mod consistency;
+pub mod convert;
+pub mod duplicates;
 pub mod error;
+pub mod explain;
+pub mod graph;
+pub mod lockfile;
+pub mod lockfile_diff;
+pub mod omit;
+pub mod outdated;
 pub mod package_json;
+pub mod package_lock;
+pub mod packument;
+pub mod packument_cache;
+pub mod pnpm;
+pub mod pnpm_lock;
+pub mod publish;
+pub mod range;
+#[cfg(feature = "registry")]
+pub mod registry;
+pub mod release;
+pub mod resolutions;
+pub mod spec;
+pub mod sync;
 #[cfg(feature = "validate")]
 mod utils;
+pub mod version;
+pub mod workspace;
+pub mod yarn_lock;
+pub use affected::*;
+pub use bun_lock::*;
+pub use catalog::*;
+pub use consistency::*;
+pub use convert::*;
+pub use duplicates::*;
 pub use error::Error;
 pub use error::Result;
+pub use explain::*;
+pub use graph::*;
+pub use lockfile::*;
+pub use lockfile_diff::*;
+pub use omit::*;
+pub use outdated::*;
 pub use package_json::*;
+pub use package_lock::*;
+pub use packument::*;
+pub use packument_cache::*;
+pub use pnpm::*;
+pub use pnpm_lock::*;
+pub use publish::*;
+pub use range::*;
+#[cfg(feature = "registry")]
+pub use registry::*;
+pub use release::*;
+pub use resolutions::*;
+pub use spec::*;
+pub use sync::*;
 #[cfg(feature = "validate")]
 pub use validator;
+pub use version::*;
+pub use workspace::*;
+pub use yarn_lock::*;
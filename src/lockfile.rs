@@ -0,0 +1,815 @@
+use std::path::Path;
+
+use indexmap::IndexMap;
+
+use crate::graph::normalize_path;
+use crate::package_name_from_path;
+use crate::parse_pnpm_package_key;
+use crate::parse_version;
+use crate::parse_yarn_descriptor;
+use crate::BunLock;
+use crate::DependencyKind;
+use crate::LockDependency;
+use crate::PackageJson;
+use crate::PackageLock;
+use crate::PnpmLock;
+use crate::YarnLock;
+use crate::YarnLockEntry;
+use crate::YarnLockFlavor;
+use crate::ROOT_IMPORTER;
+
+/// The package manager which wrote a lockfile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LockfileFormat {
+	/// `package-lock.json` or `npm-shrinkwrap.json`.
+	Npm,
+	/// `pnpm-lock.yaml`.
+	Pnpm,
+	/// `yarn.lock`, classic or berry.
+	Yarn,
+	/// `bun.lock`.
+	Bun,
+}
+
+/// A dependency edge of a [`LockfileGraph`], from an importer or a package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedDependency {
+	/// The field which declares the dependency.
+	pub kind: DependencyKind,
+
+	/// The name the dependency is installed under.
+	pub name: String,
+
+	/// The range the dependency was required with, when the lockfile records
+	/// it.
+	pub spec: Option<String>,
+
+	/// The id of the package in [`LockfileGraph::packages`] which the
+	/// dependency resolves to.
+	pub target: Option<String>,
+}
+
+/// A project of the workspace recorded by a lockfile.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LockedImporter {
+	/// The name of the project, when the lockfile records it.
+	pub name: Option<String>,
+
+	/// The direct dependencies of the project.
+	pub dependencies: Vec<LockedDependency>,
+}
+
+/// A package installed by a lockfile.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LockedPackage {
+	/// The name of the package, which differs from the name of the
+	/// dependency for aliases.
+	pub name: String,
+
+	/// The locked version, empty when the lockfile doesn't record one.
+	pub version: String,
+
+	/// Where the package is fetched from, as recorded by the lockfile.
+	pub resolved: Option<String>,
+
+	/// The subresource integrity of the tarball.
+	pub integrity: Option<String>,
+
+	/// The importer this package links to, for workspace packages.
+	pub link: Option<String>,
+
+	/// Whether the lockfile marks the package as only needed for development.
+	pub dev: Option<bool>,
+
+	/// Whether the lockfile marks the package as only needed by optional
+	/// dependencies.
+	pub optional: Option<bool>,
+
+	/// The operating systems supported by the package.
+	pub os: Vec<String>,
+
+	/// The CPU architectures supported by the package.
+	pub cpu: Vec<String>,
+
+	/// The dependencies of the package.
+	pub dependencies: Vec<LockedDependency>,
+}
+
+/// The dependency graph of a lockfile, in the same shape for every package
+/// manager.
+///
+/// Importers are keyed by their path relative to the workspace root, with
+/// `.` for the root. Packages are keyed by an id which is unique within the
+/// lockfile: the `node_modules` path for npm, `name@version` with the peer
+/// suffix for pnpm, `name@version` or the berry resolution for yarn and the
+/// install path for bun.
+///
+/// ```
+/// use package_json_schema::LockfileGraph;
+/// use package_json_schema::PackageLock;
+///
+/// let contents = r#"{
+///   "lockfileVersion": 3,
+///   "packages": {
+///     "": { "dependencies": { "ms": "^2.1.0" } },
+///     "node_modules/ms": { "version": "2.1.3" }
+///   }
+/// }"#;
+///
+/// let package_lock = PackageLock::try_from(contents).unwrap();
+/// let graph = LockfileGraph::from_package_lock(&package_lock);
+/// let root = graph.importer(".").unwrap();
+/// let target = root.dependencies[0].target.as_deref().unwrap();
+///
+/// assert_eq!(graph.packages[target].version, "2.1.3");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockfileGraph {
+	/// The package manager which wrote the lockfile.
+	pub format: LockfileFormat,
+
+	/// The projects of the workspace keyed by their relative path.
+	pub importers: IndexMap<String, LockedImporter>,
+
+	/// The installed packages keyed by their id.
+	pub packages: IndexMap<String, LockedPackage>,
+}
+
+impl LockfileGraph {
+	/// The importer at a path relative to the root. The empty path is the
+	/// root, like `.`.
+	#[must_use]
+	pub fn importer(&self, path: &str) -> Option<&LockedImporter> {
+		self.importers.get(normalize_importer(path).as_str())
+	}
+
+	/// The package with the provided id.
+	#[must_use]
+	pub fn package(&self, id: &str) -> Option<&LockedPackage> {
+		self.packages.get(id)
+	}
+
+	/// Read the graph of a `package-lock.json`. The `packages` map is used
+	/// when present and the legacy `dependencies` tree otherwise, in which
+	/// case the root only records which packages are installed.
+	#[must_use]
+	pub fn from_package_lock(package_lock: &PackageLock) -> Self {
+		let mut graph = Self::new(LockfileFormat::Npm);
+
+		let Some(entries) = &package_lock.packages else {
+			graph.read_legacy_package_lock(package_lock);
+			return graph;
+		};
+
+		let resolve = |from: &str, name: &str| {
+			resolve_node_modules(from, name, |path| entries.contains_key(path))
+		};
+
+		for (path, entry) in entries {
+			let mut dependencies = Vec::new();
+			let mut kinds = vec![
+				(DependencyKind::Prod, &entry.dependencies),
+				(DependencyKind::Optional, &entry.optional_dependencies),
+				(DependencyKind::Peer, &entry.peer_dependencies),
+			];
+
+			let is_importer = package_name_from_path(path).is_none();
+
+			if is_importer {
+				kinds.insert(1, (DependencyKind::Dev, &entry.dev_dependencies));
+			}
+
+			for (kind, map) in kinds {
+				for (name, spec) in map.iter().flatten() {
+					dependencies.push(LockedDependency {
+						kind,
+						name: name.clone(),
+						spec: Some(spec.clone()),
+						target: resolve(path, name),
+					});
+				}
+			}
+
+			if is_importer {
+				graph.importers.insert(
+					normalize_importer(path),
+					LockedImporter {
+						name: entry.name.clone(),
+						dependencies,
+					},
+				);
+				continue;
+			}
+
+			let folder = package_name_from_path(path).unwrap_or_default();
+			let link = entry
+				.is_link()
+				.then(|| entry.resolved.as_deref().map(normalize_importer))
+				.flatten();
+			let version = entry.version.clone().or_else(|| {
+				entry
+					.resolved
+					.as_ref()
+					.and_then(|target| entries.get(target))
+					.and_then(|target| target.version.clone())
+			});
+
+			graph.packages.insert(
+				path.clone(),
+				LockedPackage {
+					name: entry.name.clone().unwrap_or_else(|| folder.to_string()),
+					version: version.unwrap_or_default(),
+					resolved: if link.is_some() {
+						None
+					} else {
+						entry.resolved.clone()
+					},
+					integrity: entry.integrity.clone(),
+					link,
+					dev: entry.dev,
+					optional: entry.optional,
+					os: entry.os.clone().unwrap_or_default(),
+					cpu: entry.cpu.clone().unwrap_or_default(),
+					dependencies,
+				},
+			);
+		}
+
+		graph
+	}
+
+	fn read_legacy_package_lock(&mut self, package_lock: &PackageLock) {
+		let legacy: IndexMap<String, &LockDependency> =
+			package_lock.legacy_packages().into_iter().collect();
+		let resolve = |from: &str, name: &str| {
+			resolve_node_modules(from, name, |path| legacy.contains_key(path))
+		};
+		let mut root = LockedImporter {
+			name: package_lock.name.clone(),
+			dependencies: Vec::new(),
+		};
+
+		for (path, dependency) in &legacy {
+			let folder = package_name_from_path(path).unwrap_or_default();
+			let version = dependency.version.as_deref().unwrap_or_default();
+			let (name, version, link) = version.strip_prefix("npm:").map_or_else(
+				|| {
+					version
+						.strip_prefix("file:")
+						.map_or((folder, version, None), |target| {
+							(folder, "", Some(normalize_importer(target)))
+						})
+				},
+				|alias| {
+					let (name, version) = parse_yarn_descriptor(alias).unwrap_or((folder, alias));
+					(name, version, None)
+				},
+			);
+
+			if path == &format!("{NODE_MODULES_PREFIX}{folder}") {
+				let kind = if dependency.dev == Some(true) {
+					DependencyKind::Dev
+				} else if dependency.optional == Some(true) {
+					DependencyKind::Optional
+				} else {
+					DependencyKind::Prod
+				};
+
+				root.dependencies.push(LockedDependency {
+					kind,
+					name: folder.to_string(),
+					spec: None,
+					target: Some(path.clone()),
+				});
+			}
+
+			let dependencies = dependency
+				.requires
+				.iter()
+				.flatten()
+				.map(|(name, spec)| {
+					LockedDependency {
+						kind: DependencyKind::Prod,
+						name: name.clone(),
+						spec: Some(spec.clone()),
+						target: resolve(path, name),
+					}
+				})
+				.collect();
+
+			self.packages.insert(
+				path.clone(),
+				LockedPackage {
+					name: name.to_string(),
+					version: version.to_string(),
+					resolved: dependency.resolved.clone(),
+					integrity: dependency.integrity.clone(),
+					link,
+					dev: dependency.dev,
+					optional: dependency.optional,
+					dependencies,
+					..LockedPackage::default()
+				},
+			);
+		}
+
+		self.importers.insert(ROOT_IMPORTER.to_string(), root);
+	}
+
+	/// Read the graph of a `pnpm-lock.yaml`. `link:` dependencies become
+	/// packages which link to the importer at the target path.
+	#[must_use]
+	pub fn from_pnpm_lock(pnpm_lock: &PnpmLock) -> Self {
+		let mut graph = Self::new(LockfileFormat::Pnpm);
+		let major = pnpm_lock.lockfile_version.major().unwrap_or(9);
+		let keys: IndexMap<String, &str> = pnpm_lock
+			.snapshots
+			.as_ref()
+			.filter(|_| major >= 9)
+			.map_or_else(
+				|| {
+					pnpm_lock
+						.packages
+						.iter()
+						.flatten()
+						.map(|(key, _)| key)
+						.collect::<Vec<_>>()
+				},
+				|snapshots| snapshots.keys().collect(),
+			)
+			.into_iter()
+			.map(|key| (pnpm_id(key), key.as_str()))
+			.collect();
+
+		let mut links: IndexMap<String, LockedPackage> = IndexMap::new();
+		let mut target = |from: &str, name: &str, version: &str| {
+			if let Some(path) = version.strip_prefix("link:") {
+				let path = normalize_importer(
+					&normalize_path(&Path::new(from).join(path)).to_string_lossy(),
+				);
+				let id = format!("{name}@link:{path}");
+
+				links.entry(id.clone()).or_insert_with(|| {
+					LockedPackage {
+						name: name.to_string(),
+						link: Some(path),
+						..LockedPackage::default()
+					}
+				});
+
+				return Some(id);
+			}
+
+			let aliased = parse_pnpm_package_key(version).is_some()
+				&& !version.starts_with(|c: char| c.is_ascii_digit());
+			let id = if aliased {
+				pnpm_id(version)
+			} else {
+				format!("{name}@{version}")
+			};
+
+			keys.contains_key(&id).then_some(id)
+		};
+
+		for (path, importer) in pnpm_lock.importers() {
+			let dependencies = importer
+				.entries()
+				.into_iter()
+				.map(|entry| {
+					LockedDependency {
+						kind: entry.kind,
+						name: entry.name.to_string(),
+						spec: entry.specifier.map(ToString::to_string),
+						target: target(&path, entry.name, entry.version),
+					}
+				})
+				.collect();
+
+			graph.importers.insert(
+				normalize_importer(&path),
+				LockedImporter {
+					name: None,
+					dependencies,
+				},
+			);
+		}
+
+		for (id, key) in &keys {
+			let (mut package, edges) = read_pnpm_package(pnpm_lock, key, major);
+
+			package.dependencies = edges
+				.into_iter()
+				.map(|(kind, dependency, version)| {
+					LockedDependency {
+						kind,
+						name: dependency.clone(),
+						spec: None,
+						target: target(".", dependency, version),
+					}
+				})
+				.collect();
+			graph.packages.insert(id.clone(), package);
+		}
+
+		graph.packages.extend(links);
+		graph
+	}
+
+	/// Read the graph of a `yarn.lock`. Yarn doesn't record the dependencies
+	/// of the projects, so they are read from the provided manifests, keyed
+	/// by their path relative to the root.
+	#[must_use]
+	pub fn from_yarn_lock(yarn_lock: &YarnLock, manifests: &[(&str, &PackageJson)]) -> Self {
+		let mut graph = Self::new(LockfileFormat::Yarn);
+		let berry = yarn_lock.flavor() == YarnLockFlavor::Berry;
+		let id_of = |entry: &YarnLockEntry| {
+			if berry {
+				entry.resolution.clone()
+			} else {
+				entry
+					.name()
+					.map(|name| format!("{name}@{}", entry.version.as_deref().unwrap_or_default()))
+			}
+		};
+		let edge = |kind: DependencyKind, name: &str, spec: &str| {
+			let target = yarn_lock.find(name, spec).and_then(id_of);
+			let spec = if berry {
+				spec.strip_prefix("npm:")
+					.filter(|range| parse_yarn_descriptor(range).is_none())
+					.unwrap_or(spec)
+			} else {
+				spec
+			};
+
+			LockedDependency {
+				kind,
+				name: name.to_string(),
+				spec: Some(spec.to_string()),
+				target,
+			}
+		};
+
+		for entry in yarn_lock.entries() {
+			let Some(id) = id_of(entry) else {
+				continue;
+			};
+
+			if graph.packages.contains_key(&id) {
+				continue;
+			}
+
+			let link = entry
+				.resolution
+				.as_deref()
+				.and_then(|resolution| resolution.split_once("@workspace:"))
+				.map(|(_, path)| normalize_importer(path));
+			let dependencies = [
+				(DependencyKind::Prod, &entry.dependencies),
+				(DependencyKind::Optional, &entry.optional_dependencies),
+				(DependencyKind::Peer, &entry.peer_dependencies),
+			]
+			.into_iter()
+			.filter(|_| link.is_none())
+			.flat_map(|(kind, map)| {
+				map.iter()
+					.flatten()
+					.map(move |(name, spec)| (kind, name, spec))
+			})
+			.map(|(kind, name, spec)| edge(kind, name, spec))
+			.collect();
+
+			graph.packages.insert(
+				id,
+				LockedPackage {
+					name: entry.name().unwrap_or_default().to_string(),
+					version: entry.version.clone().unwrap_or_default(),
+					resolved: if berry {
+						entry.resolution.clone()
+					} else {
+						entry.resolved.clone()
+					},
+					integrity: entry.integrity.clone(),
+					link,
+					os: yarn_conditions(entry, "os"),
+					cpu: yarn_conditions(entry, "cpu"),
+					dependencies,
+					..LockedPackage::default()
+				},
+			);
+		}
+
+		for (path, package_json) in manifests {
+			let dependencies = package_json
+				.all_dependencies()
+				.map(|(kind, name, spec)| edge(kind, name, spec))
+				.collect();
+
+			graph.importers.insert(
+				normalize_importer(path),
+				LockedImporter {
+					name: package_json.name.clone(),
+					dependencies,
+				},
+			);
+		}
+
+		graph
+	}
+
+	/// Read the graph of a `bun.lock`.
+	#[must_use]
+	pub fn from_bun_lock(bun_lock: &BunLock) -> Self {
+		let mut graph = Self::new(LockfileFormat::Bun);
+
+		for (path, workspace) in &bun_lock.workspaces {
+			let dependencies = [
+				(DependencyKind::Prod, &workspace.dependencies),
+				(DependencyKind::Dev, &workspace.dev_dependencies),
+				(DependencyKind::Optional, &workspace.optional_dependencies),
+				(DependencyKind::Peer, &workspace.peer_dependencies),
+			]
+			.into_iter()
+			.flat_map(|(kind, map)| {
+				map.iter()
+					.flatten()
+					.map(move |(name, spec)| (kind, name, spec))
+			})
+			.map(|(kind, name, spec)| {
+				let scope = workspace.name.as_deref().filter(|_| !path.is_empty());
+
+				LockedDependency {
+					kind,
+					name: name.clone(),
+					spec: Some(spec.clone()),
+					target: bun_lock
+						.resolve(scope, name)
+						.map(|(key, _)| key.to_string()),
+				}
+			})
+			.collect();
+
+			graph.importers.insert(
+				normalize_importer(path),
+				LockedImporter {
+					name: workspace.name.clone(),
+					dependencies,
+				},
+			);
+		}
+
+		for (key, package) in &bun_lock.packages {
+			let info = package.info().unwrap_or_default();
+			let link = package
+				.resolution()
+				.and_then(|resolution| resolution.split_once("@workspace:"))
+				.map(|(_, path)| normalize_importer(path));
+			let version = package.version().map(ToString::to_string).or_else(|| {
+				link.as_ref()
+					.and_then(|path| bun_lock.workspaces.get(path.trim_start_matches('.')))
+					.and_then(|workspace| workspace.version.clone())
+			});
+			let dependencies = [
+				(DependencyKind::Prod, &info.dependencies),
+				(DependencyKind::Optional, &info.optional_dependencies),
+				(DependencyKind::Peer, &info.peer_dependencies),
+			]
+			.into_iter()
+			.flat_map(|(kind, map)| {
+				map.iter()
+					.flatten()
+					.map(move |(name, spec)| (kind, name, spec))
+			})
+			.map(|(kind, name, spec)| {
+				LockedDependency {
+					kind,
+					name: name.clone(),
+					spec: Some(spec.clone()),
+					target: resolve_bun_key(bun_lock, key, name),
+				}
+			})
+			.collect();
+			let registry = package
+				.0
+				.get(1)
+				.and_then(serde_json::Value::as_str)
+				.filter(|registry| !registry.is_empty() && link.is_none());
+
+			graph.packages.insert(
+				key.clone(),
+				LockedPackage {
+					name: package.name().unwrap_or_default().to_string(),
+					version: version.unwrap_or_default(),
+					resolved: registry.map(ToString::to_string),
+					integrity: package.integrity().map(ToString::to_string),
+					link,
+					os: string_or_list(info.os.as_ref()),
+					cpu: string_or_list(info.cpu.as_ref()),
+					dependencies,
+					..LockedPackage::default()
+				},
+			);
+		}
+
+		graph
+	}
+
+	fn new(format: LockfileFormat) -> Self {
+		Self {
+			format,
+			importers: IndexMap::new(),
+			packages: IndexMap::new(),
+		}
+	}
+}
+
+const NODE_MODULES_PREFIX: &str = "node_modules/";
+
+/// Use `.` for the root and drop the leading `./` of other paths.
+fn normalize_importer(path: &str) -> String {
+	let path = path.trim_start_matches("./").trim_end_matches('/');
+
+	if path.is_empty() {
+		ROOT_IMPORTER.to_string()
+	} else {
+		path.to_string()
+	}
+}
+
+/// Find the folder node would load a dependency from, walking up the
+/// `node_modules` folders of the dependent.
+fn resolve_node_modules(from: &str, name: &str, exists: impl Fn(&str) -> bool) -> Option<String> {
+	let mut base = from.to_string();
+
+	loop {
+		let candidate = if base.is_empty() {
+			format!("{NODE_MODULES_PREFIX}{name}")
+		} else {
+			format!("{base}/{NODE_MODULES_PREFIX}{name}")
+		};
+
+		if exists(&candidate) {
+			return Some(candidate);
+		}
+
+		if base.is_empty() {
+			return None;
+		}
+
+		base = base
+			.rfind(&format!("/{NODE_MODULES_PREFIX}"))
+			.map_or_else(String::new, |index| base[..index].to_string());
+	}
+}
+
+/// Resolve a dependency of the bun package at `key`, preferring the copies
+/// nested below it.
+fn resolve_bun_key(bun_lock: &BunLock, key: &str, name: &str) -> Option<String> {
+	let mut segments: Vec<String> = Vec::new();
+
+	for part in key.split('/') {
+		match segments.last_mut() {
+			Some(last) if last.starts_with('@') && !last.contains('/') => {
+				last.push('/');
+				last.push_str(part);
+			}
+			_ => segments.push(part.to_string()),
+		}
+	}
+
+	(0..=segments.len()).rev().find_map(|length| {
+		let mut candidate = segments[..length].join("/");
+
+		if !candidate.is_empty() {
+			candidate.push('/');
+		}
+
+		candidate.push_str(name);
+		bun_lock
+			.packages
+			.contains_key(&candidate)
+			.then_some(candidate)
+	})
+}
+
+/// The id of a pnpm package: `name@version` with the peer suffix, without
+/// the leading `/` of lockfiles before `9.0`.
+fn pnpm_id(key: &str) -> String {
+	let key = key.strip_prefix('/').unwrap_or(key);
+
+	match parse_pnpm_package_key(key) {
+		Some((name, version)) if key.starts_with(&format!("{name}/{version}")) => {
+			format!("{name}@{}", &key[name.len() + 1..])
+		}
+		_ => key.to_string(),
+	}
+}
+
+/// Read a pnpm package along with its dependencies, keyed by the snapshot
+/// key for `9.0` and by the package key before.
+fn read_pnpm_package<'a>(
+	pnpm_lock: &'a PnpmLock,
+	key: &str,
+	major: u32,
+) -> (LockedPackage, Vec<(DependencyKind, &'a String, &'a String)>) {
+	let packages = pnpm_lock.packages.as_ref();
+	let snapshot = pnpm_lock
+		.snapshots
+		.as_ref()
+		.filter(|_| major >= 9)
+		.and_then(|snapshots| snapshots.get(key));
+	let package = if snapshot.is_some() {
+		let base = key.find('(').map_or(key, |index| &key[..index]);
+		packages.and_then(|packages| packages.get(base))
+	} else {
+		packages.and_then(|packages| packages.get(key))
+	};
+	let (name, version) = parse_pnpm_package_key(key).unwrap_or_default();
+	let (dependencies, optional_dependencies) = snapshot.map_or_else(
+		|| {
+			package.map_or((None, None), |package| {
+				(
+					package.dependencies.as_ref(),
+					package.optional_dependencies.as_ref(),
+				)
+			})
+		},
+		|snapshot| {
+			(
+				snapshot.dependencies.as_ref(),
+				snapshot.optional_dependencies.as_ref(),
+			)
+		},
+	);
+	let edges = [
+		(DependencyKind::Prod, dependencies),
+		(DependencyKind::Optional, optional_dependencies),
+	]
+	.into_iter()
+	.flat_map(|(kind, map)| {
+		map.into_iter()
+			.flatten()
+			.map(move |(name, version)| (kind, name, version))
+	})
+	.collect();
+	let resolution = package.and_then(|package| package.resolution.as_ref());
+	let package = LockedPackage {
+		name: package
+			.and_then(|package| package.name.clone())
+			.unwrap_or_else(|| name.to_string()),
+		version: package
+			.and_then(|package| package.version.clone())
+			.unwrap_or_else(|| version.to_string()),
+		resolved: resolution.and_then(|resolution| resolution.tarball.clone()),
+		integrity: resolution.and_then(|resolution| resolution.integrity.clone()),
+		link: None,
+		dev: package.and_then(|package| package.dev),
+		optional: snapshot
+			.and_then(|snapshot| snapshot.optional)
+			.or_else(|| package.and_then(|package| package.optional)),
+		os: package
+			.and_then(|package| package.os.clone())
+			.unwrap_or_default(),
+		cpu: package
+			.and_then(|package| package.cpu.clone())
+			.unwrap_or_default(),
+		dependencies: Vec::new(),
+	};
+
+	(package, edges)
+}
+
+/// Read a constraint such as `os=darwin` from the `conditions` of a berry
+/// entry.
+fn yarn_conditions(entry: &YarnLockEntry, field: &str) -> Vec<String> {
+	entry
+		.conditions
+		.iter()
+		.flat_map(|conditions| conditions.split('&'))
+		.filter_map(|condition| condition.trim().split_once('='))
+		.filter(|(key, _)| *key == field)
+		.map(|(_, value)| value.to_string())
+		.collect()
+}
+
+fn string_or_list(value: Option<&serde_json::Value>) -> Vec<String> {
+	match value {
+		Some(serde_json::Value::String(value)) => vec![value.clone()],
+		Some(serde_json::Value::Array(values)) => {
+			values
+				.iter()
+				.filter_map(serde_json::Value::as_str)
+				.map(ToString::to_string)
+				.collect()
+		}
+		_ => Vec::new(),
+	}
+}
+
+/// Whether a locked version is accepted by a range, or `None` when either
+/// can't be compared.
+pub(crate) fn locked_version_satisfies(range: &str, version: &str) -> Option<bool> {
+	let version = parse_version(version)?;
+	let range = crate::VersionRange::parse(range).ok()?;
+
+	Some(range.satisfies(&version))
+}
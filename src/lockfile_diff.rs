@@ -0,0 +1,365 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::parse_version;
+use crate::LockfileGraph;
+use crate::PackageJson;
+
+/// How a package changed between two lockfiles.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum LockfileChangeKind {
+	/// A version which wasn't installed before.
+	Added,
+	/// A version which is no longer installed.
+	Removed,
+	/// A version replaced by a higher one.
+	Upgraded,
+	/// A version replaced by a lower one.
+	Downgraded,
+	/// The same version with a different integrity.
+	Integrity,
+}
+
+impl LockfileChangeKind {
+	/// The lowercase name used by the table and the JSON output.
+	#[must_use]
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Added => "added",
+			Self::Removed => "removed",
+			Self::Upgraded => "upgraded",
+			Self::Downgraded => "downgraded",
+			Self::Integrity => "integrity",
+		}
+	}
+}
+
+/// A single change of a [`LockfileDiff`].
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockfileChange {
+	/// The name of the package.
+	pub name: String,
+
+	/// How the package changed.
+	pub kind: LockfileChangeKind,
+
+	/// The version in the old lockfile.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub from: Option<String>,
+
+	/// The version in the new lockfile.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub to: Option<String>,
+
+	/// The integrity in the old lockfile.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub from_integrity: Option<String>,
+
+	/// The integrity in the new lockfile.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub to_integrity: Option<String>,
+
+	/// The direct dependencies of the manifests which install the package.
+	pub direct: Vec<String>,
+}
+
+/// The packages which differ between two lockfiles. Created by
+/// [`LockfileGraph::diff`].
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct LockfileDiff {
+	/// The changes ordered by package name and then by version.
+	pub changes: Vec<LockfileChange>,
+}
+
+impl LockfileDiff {
+	/// Whether both lockfiles install the same packages.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.changes.is_empty()
+	}
+
+	/// The changes of a single kind.
+	#[must_use]
+	pub fn of_kind(&self, kind: LockfileChangeKind) -> Vec<&LockfileChange> {
+		self.changes
+			.iter()
+			.filter(|change| change.kind == kind)
+			.collect()
+	}
+
+	/// Render the changes as a plain text table with the columns `package`,
+	/// `change`, `from`, `to` and `direct`.
+	#[must_use]
+	pub fn to_table(&self) -> String {
+		let header = ["package", "change", "from", "to", "direct"].map(ToString::to_string);
+		let rows: Vec<[String; 5]> = std::iter::once(header)
+			.chain(self.changes.iter().map(|change| {
+				[
+					change.name.clone(),
+					change.kind.as_str().to_string(),
+					change.from.clone().unwrap_or_else(|| "-".into()),
+					change.to.clone().unwrap_or_else(|| "-".into()),
+					if change.direct.is_empty() {
+						"-".into()
+					} else {
+						change.direct.join(", ")
+					},
+				]
+			}))
+			.collect();
+
+		format_table(&rows)
+	}
+
+	/// Render the diff as pretty printed JSON.
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::SerializeLockfileDiff`] when serialization
+	/// fails.
+	pub fn to_json(&self) -> crate::Result<String> {
+		serde_json::to_string_pretty(self).map_err(crate::Error::SerializeLockfileDiff)
+	}
+}
+
+impl LockfileGraph {
+	/// Compare the packages installed by this lockfile with a newer one of
+	/// the same format.
+	///
+	/// Packages are compared by name and version, ignoring where they are
+	/// installed. When a name loses and gains versions, they are paired in
+	/// order as upgrades or downgrades and the rest are added or removed.
+	/// Every change is attributed to the direct dependencies of the provided
+	/// manifests, keyed by their path relative to the root, which install it.
+	///
+	/// ```
+	/// use package_json_schema::LockfileChangeKind;
+	/// use package_json_schema::LockfileGraph;
+	/// use package_json_schema::PackageJson;
+	/// use package_json_schema::PackageLock;
+	///
+	/// let contents = r#"{
+	///   "lockfileVersion": 3,
+	///   "packages": {
+	///     "": { "dependencies": { "ms": "^2.1.0" } },
+	///     "node_modules/ms": { "version": "2.1.2" }
+	///   }
+	/// }"#;
+	/// let old = PackageLock::try_from(contents).unwrap();
+	/// let new = PackageLock::try_from(contents.replace("2.1.2", "2.1.3")).unwrap();
+	/// let old = LockfileGraph::from_package_lock(&old);
+	/// let new = LockfileGraph::from_package_lock(&new);
+	/// let package_json = PackageJson::try_from(r#"{ "dependencies": { "ms": "^2.1.0" } }"#).unwrap();
+	/// let diff = old.diff(&new, &[(".", &package_json)]);
+	///
+	/// assert_eq!(diff.changes[0].kind, LockfileChangeKind::Upgraded);
+	/// assert_eq!(diff.changes[0].direct, ["ms"]);
+	/// ```
+	#[must_use]
+	pub fn diff(&self, new: &Self, manifests: &[(&str, &PackageJson)]) -> LockfileDiff {
+		let old_versions = self.versions();
+		let new_versions = new.versions();
+		let old_direct = self.direct_packages(manifests);
+		let new_direct = new.direct_packages(manifests);
+		let names: BTreeSet<&str> = old_versions
+			.keys()
+			.chain(new_versions.keys())
+			.copied()
+			.collect();
+		let mut changes = Vec::new();
+
+		for name in names {
+			let empty = IndexMap::new();
+			let before = old_versions.get(name).unwrap_or(&empty);
+			let after = new_versions.get(name).unwrap_or(&empty);
+			let change = |kind, from: Option<&str>, to: Option<&str>| {
+				let direct_of = |graph: &[(String, HashSet<(String, String)>)], version: &str| {
+					graph
+						.iter()
+						.filter(|(_, reachable)| {
+							reachable.contains(&(name.to_string(), version.to_string()))
+						})
+						.map(|(direct, _)| direct.clone())
+						.collect::<BTreeSet<_>>()
+				};
+				let mut direct = to.map(|to| direct_of(&new_direct, to)).unwrap_or_default();
+
+				if direct.is_empty() {
+					direct = from
+						.map(|from| direct_of(&old_direct, from))
+						.unwrap_or_default();
+				}
+
+				LockfileChange {
+					name: name.to_string(),
+					kind,
+					from: from.map(ToString::to_string),
+					to: to.map(ToString::to_string),
+					from_integrity: from.and_then(|from| before.get(from).cloned().flatten()),
+					to_integrity: to.and_then(|to| after.get(to).cloned().flatten()),
+					direct: direct.into_iter().collect(),
+				}
+			};
+
+			for (version, integrity) in before {
+				if after.get(version).is_some_and(|other| other != integrity) {
+					changes.push(change(
+						LockfileChangeKind::Integrity,
+						Some(version),
+						Some(version),
+					));
+				}
+			}
+
+			let mut removed: Vec<&str> = before
+				.keys()
+				.filter(|version| !after.contains_key(*version))
+				.map(String::as_str)
+				.collect();
+			let mut added: Vec<&str> = after
+				.keys()
+				.filter(|version| !before.contains_key(*version))
+				.map(String::as_str)
+				.collect();
+			removed.sort_by(|a, b| compare_versions(a, b));
+			added.sort_by(|a, b| compare_versions(a, b));
+
+			let paired = removed.len().min(added.len());
+			let comparable =
+				|from: &str, to: &str| parse_version(from).is_some() && parse_version(to).is_some();
+
+			for (from, to) in removed.iter().zip(&added).take(paired) {
+				if !comparable(from, to) {
+					changes.push(change(LockfileChangeKind::Removed, Some(from), None));
+					changes.push(change(LockfileChangeKind::Added, None, Some(to)));
+				} else if compare_versions(from, to) == Ordering::Less {
+					changes.push(change(LockfileChangeKind::Upgraded, Some(from), Some(to)));
+				} else {
+					changes.push(change(LockfileChangeKind::Downgraded, Some(from), Some(to)));
+				}
+			}
+
+			for from in &removed[paired..] {
+				changes.push(change(LockfileChangeKind::Removed, Some(from), None));
+			}
+
+			for to in &added[paired..] {
+				changes.push(change(LockfileChangeKind::Added, None, Some(to)));
+			}
+		}
+
+		LockfileDiff { changes }
+	}
+
+	/// The versions of every package name with their integrity, skipping
+	/// workspace links.
+	fn versions(&self) -> IndexMap<&str, IndexMap<String, Option<String>>> {
+		let mut versions: IndexMap<&str, IndexMap<String, Option<String>>> = IndexMap::new();
+
+		for package in self.packages.values() {
+			if package.link.is_some() || package.version.is_empty() {
+				continue;
+			}
+
+			versions
+				.entry(package.name.as_str())
+				.or_default()
+				.entry(package.version.clone())
+				.or_insert_with(|| package.integrity.clone());
+		}
+
+		versions
+	}
+
+	/// The `(name, version)` of every package reachable from each direct
+	/// dependency declared by the manifests.
+	fn direct_packages(
+		&self,
+		manifests: &[(&str, &PackageJson)],
+	) -> Vec<(String, HashSet<(String, String)>)> {
+		let mut direct: IndexMap<String, HashSet<(String, String)>> = IndexMap::new();
+
+		for (path, package_json) in manifests {
+			let Some(importer) = self.importer(path) else {
+				continue;
+			};
+
+			for (_, name, _) in package_json.all_dependencies() {
+				let roots = importer
+					.dependencies
+					.iter()
+					.filter(|dependency| dependency.name == name)
+					.filter_map(|dependency| dependency.target.clone());
+				let reachable = direct.entry(name.to_string()).or_default();
+
+				for id in self.reachable(roots) {
+					let package = &self.packages[&id];
+					reachable.insert((package.name.clone(), package.version.clone()));
+				}
+			}
+		}
+
+		direct.into_iter().collect()
+	}
+
+	/// The ids of the packages reachable from the provided ids, including
+	/// themselves.
+	pub(crate) fn reachable(&self, roots: impl IntoIterator<Item = String>) -> Vec<String> {
+		let mut seen: IndexMap<String, ()> = IndexMap::new();
+		let mut queue: Vec<String> = roots.into_iter().collect();
+
+		while let Some(id) = queue.pop() {
+			let Some(package) = self.packages.get(&id) else {
+				continue;
+			};
+
+			if seen.insert(id, ()).is_some() {
+				continue;
+			}
+
+			queue.extend(
+				package
+					.dependencies
+					.iter()
+					.filter_map(|dependency| dependency.target.clone()),
+			);
+		}
+
+		seen.into_keys().collect()
+	}
+}
+
+/// Order semver versions by precedence and other versions as strings.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+	match (parse_version(a), parse_version(b)) {
+		(Some(a), Some(b)) => a.cmp(&b),
+		_ => a.cmp(b),
+	}
+}
+
+/// Align the columns of the rows, separated by two spaces, with one line per
+/// row.
+pub(crate) fn format_table<const N: usize>(rows: &[[String; N]]) -> String {
+	let widths: Vec<usize> = (0..N)
+		.map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or(0))
+		.collect();
+	let mut table = String::new();
+
+	for row in rows {
+		let line: Vec<String> = row
+			.iter()
+			.zip(&widths)
+			.map(|(cell, width)| format!("{cell:width$}"))
+			.collect();
+		table.push_str(line.join("  ").trim_end());
+		table.push('\n');
+	}
+
+	table
+}
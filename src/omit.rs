@@ -0,0 +1,214 @@
+use indexmap::IndexSet;
+use typed_builder::TypedBuilder;
+
+use crate::DependencyKind;
+use crate::LockedDependency;
+use crate::LockedPackage;
+use crate::LockfileGraph;
+use crate::PackageJson;
+
+/// Options for [`LockfileGraph::install_subset`].
+#[derive(TypedBuilder, Debug, Clone, Default)]
+pub struct OmitOptions {
+	/// The kinds of dependencies which aren't installed, like npm's `--omit`.
+	#[builder(default, setter(into))]
+	pub omit: Vec<DependencyKind>,
+
+	/// The operating system of the target, e.g. `linux`. Every platform is
+	/// accepted when unset.
+	#[builder(default, setter(into, strip_option))]
+	pub os: Option<String>,
+
+	/// The CPU architecture of the target, e.g. `x64`. Every architecture is
+	/// accepted when unset.
+	#[builder(default, setter(into, strip_option))]
+	pub cpu: Option<String>,
+}
+
+/// A package which doesn't support the target platform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedPackage {
+	/// The id of the package in [`LockfileGraph::packages`].
+	pub id: String,
+
+	/// Whether the package is only reached through optional dependencies, in
+	/// which case package managers skip it instead of failing.
+	pub optional: bool,
+}
+
+/// The packages kept by an install. Created by
+/// [`LockfileGraph::install_subset`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstallSubset {
+	/// The ids of the installed packages in lockfile order.
+	pub packages: Vec<String>,
+
+	/// The packages which were left out because of the target platform.
+	pub unsupported: Vec<UnsupportedPackage>,
+}
+
+impl InstallSubset {
+	/// Whether the package with the provided id is installed.
+	#[must_use]
+	pub fn contains(&self, id: &str) -> bool {
+		self.packages.iter().any(|package| package == id)
+	}
+}
+
+impl LockfileGraph {
+	/// The packages an install of the root `package.json` keeps, following
+	/// npm's rules for `--omit=dev`, `--omit=optional` and `--omit=peer`.
+	///
+	/// The dependency closure is walked from the declared dependencies which
+	/// aren't omitted, so a package reached through both a dev and a prod
+	/// dependency stays. Packages whose `os` or `cpu` exclude the target are
+	/// left out along with the dependencies only they need.
+	///
+	/// ```
+	/// use package_json_schema::DependencyKind;
+	/// use package_json_schema::LockfileGraph;
+	/// use package_json_schema::OmitOptions;
+	/// use package_json_schema::PackageJson;
+	/// use package_json_schema::PackageLock;
+	///
+	/// let manifest = r#"{
+	///   "dependencies": { "ms": "^2.1.0" },
+	///   "devDependencies": { "tsc": "^1.0.0" }
+	/// }"#;
+	/// let contents = r#"{
+	///   "lockfileVersion": 3,
+	///   "packages": {
+	///     "": {
+	///       "dependencies": { "ms": "^2.1.0" },
+	///       "devDependencies": { "tsc": "^1.0.0" }
+	///     },
+	///     "node_modules/ms": { "version": "2.1.3" },
+	///     "node_modules/tsc": { "version": "1.0.0", "dev": true }
+	///   }
+	/// }"#;
+	/// let package_json = PackageJson::try_from(manifest).unwrap();
+	/// let package_lock = PackageLock::try_from(contents).unwrap();
+	/// let graph = LockfileGraph::from_package_lock(&package_lock);
+	/// let omit = vec![DependencyKind::Dev];
+	/// let options = OmitOptions::builder().omit(omit).build();
+	/// let subset = graph.install_subset(&package_json, &options);
+	///
+	/// assert_eq!(subset.packages, ["node_modules/ms"]);
+	/// ```
+	#[must_use]
+	pub fn install_subset(
+		&self,
+		package_json: &PackageJson,
+		options: &OmitOptions,
+	) -> InstallSubset {
+		let mut installed: IndexSet<&str> = IndexSet::new();
+		let mut walked: IndexSet<(&str, bool)> = IndexSet::new();
+		let mut unsupported: IndexSet<&str> = IndexSet::new();
+		let mut required: IndexSet<&str> = IndexSet::new();
+		let mut queue: Vec<(&LockedDependency, bool)> = Vec::new();
+		let included = |dependency: &LockedDependency| !options.omit.contains(&dependency.kind);
+
+		if let Some(root) = self.importer(".") {
+			for (kind, name, _) in package_json.all_dependencies() {
+				if options.omit.contains(&kind) {
+					continue;
+				}
+
+				queue.extend(
+					root.dependencies
+						.iter()
+						.filter(|dependency| dependency.name == name)
+						.map(|dependency| (dependency, kind == DependencyKind::Optional)),
+				);
+			}
+		}
+
+		while let Some((dependency, optional)) = queue.pop() {
+			let Some((id, package)) = dependency
+				.target
+				.as_deref()
+				.and_then(|id| self.packages.get_key_value(id))
+			else {
+				continue;
+			};
+
+			if !supports_platform(package, options) {
+				unsupported.insert(id);
+
+				if !optional {
+					required.insert(id);
+				}
+
+				continue;
+			}
+
+			// A package first walked through optional edges is walked again when
+			// a required edge reaches it, so its dependencies become required.
+			if walked.contains(&(id.as_str(), false)) || !walked.insert((id, optional)) {
+				continue;
+			}
+
+			installed.insert(id);
+
+			let edges = package
+				.link
+				.as_deref()
+				.map_or(&package.dependencies, |path| {
+					self.importer(path)
+						.map_or(&package.dependencies, |importer| &importer.dependencies)
+				});
+
+			queue.extend(
+				edges
+					.iter()
+					.filter(|edge| included(edge))
+					.map(|edge| (edge, optional || edge.kind == DependencyKind::Optional)),
+			);
+		}
+
+		InstallSubset {
+			packages: self
+				.packages
+				.keys()
+				.filter(|id| installed.contains(id.as_str()))
+				.cloned()
+				.collect(),
+			unsupported: self
+				.packages
+				.keys()
+				.filter(|id| unsupported.contains(id.as_str()))
+				.map(|id| {
+					UnsupportedPackage {
+						id: id.clone(),
+						optional: !required.contains(id.as_str()),
+					}
+				})
+				.collect(),
+		}
+	}
+}
+
+/// Check the `os` and `cpu` of a package, where entries starting with `!`
+/// exclude a platform.
+fn supports_platform(package: &LockedPackage, options: &OmitOptions) -> bool {
+	let allows = |list: &[String], value: Option<&String>| {
+		let Some(value) = value else {
+			return true;
+		};
+
+		if list
+			.iter()
+			.any(|entry| entry.strip_prefix('!') == Some(value))
+		{
+			return false;
+		}
+
+		let mut allowed = list
+			.iter()
+			.filter(|entry| !entry.starts_with('!'))
+			.peekable();
+		allowed.peek().is_none() || allowed.any(|entry| entry == value)
+	};
+
+	allows(&package.os, options.os.as_ref()) && allows(&package.cpu, options.cpu.as_ref())
+}
@@ -0,0 +1,353 @@
+use semver::Version;
+
+use crate::lockfile_diff::format_table;
+use crate::packument::is_deprecated;
+use crate::packument_cache::registry_spec;
+use crate::parse_version;
+use crate::DependencyKind;
+use crate::LockfileGraph;
+use crate::PackageJson;
+use crate::PackumentCache;
+use crate::PickOptions;
+
+/// The versions of a dependency, like a row of `npm outdated`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutdatedDependency {
+	/// The path of the project relative to the root, `.` for the root.
+	pub importer: String,
+
+	/// The field which declares the dependency.
+	pub kind: DependencyKind,
+
+	/// The name of the dependency.
+	pub name: String,
+
+	/// The declared spec.
+	pub spec: String,
+
+	/// The version installed by the lockfile.
+	pub current: Option<String>,
+
+	/// The version the spec resolves to today.
+	pub wanted: Option<String>,
+
+	/// The version of the `latest` dist-tag.
+	pub latest: Option<String>,
+}
+
+impl OutdatedDependency {
+	/// Whether the installed version isn't the wanted or the latest version.
+	#[must_use]
+	pub fn is_outdated(&self) -> bool {
+		self.current.is_none() || self.current != self.wanted || self.current != self.latest
+	}
+}
+
+/// The versions of every registry dependency of the manifests. Created by
+/// [`LockfileGraph::outdated`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutdatedReport {
+	/// The dependencies ordered by project and then by declaration.
+	pub dependencies: Vec<OutdatedDependency>,
+}
+
+impl OutdatedReport {
+	/// The dependencies which aren't up to date.
+	pub fn outdated(&self) -> impl Iterator<Item = &OutdatedDependency> {
+		self.dependencies
+			.iter()
+			.filter(|dependency| dependency.is_outdated())
+	}
+
+	/// Render the outdated dependencies as an aligned table like
+	/// `npm outdated`.
+	#[must_use]
+	pub fn to_table(&self) -> String {
+		let header =
+			["package", "current", "wanted", "latest", "location"].map(ToString::to_string);
+		let cell = |version: &Option<String>| version.clone().unwrap_or_else(|| "-".into());
+		let rows: Vec<[String; 5]> = std::iter::once(header)
+			.chain(self.outdated().map(|dependency| {
+				[
+					dependency.name.clone(),
+					cell(&dependency.current),
+					cell(&dependency.wanted),
+					cell(&dependency.latest),
+					dependency.importer.clone(),
+				]
+			}))
+			.collect();
+
+		format_table(&rows)
+	}
+}
+
+impl LockfileGraph {
+	/// Compare the locked version of every registry dependency of the
+	/// manifests, keyed by their path relative to the root, with the
+	/// versions in the cached packuments.
+	///
+	/// The wanted version is picked like an install of the declared spec
+	/// would. Dependencies using `workspace:`, `catalog:`, path or url specs
+	/// are left out.
+	#[must_use]
+	pub fn outdated(
+		&self,
+		manifests: &[(&str, &PackageJson)],
+		cache: &PackumentCache,
+		options: &PickOptions,
+	) -> OutdatedReport {
+		let mut report = OutdatedReport::default();
+
+		for (path, package_json) in manifests {
+			for (kind, name, spec) in package_json.all_dependencies() {
+				let Some((package, _)) = registry_spec(name, spec) else {
+					continue;
+				};
+				let version = |manifest: &PackageJson| manifest.version.clone();
+				let current = self
+					.importer(path)
+					.and_then(|importer| {
+						importer
+							.dependencies
+							.iter()
+							.find(|dependency| dependency.name == name)
+					})
+					.and_then(|dependency| self.package(dependency.target.as_deref()?))
+					.filter(|locked| locked.link.is_none())
+					.map(|locked| locked.version.clone());
+
+				report.dependencies.push(OutdatedDependency {
+					importer: (*path).to_string(),
+					kind,
+					name: name.to_string(),
+					spec: spec.to_string(),
+					current,
+					wanted: cache.pick_manifest(name, spec, options).and_then(version),
+					latest: cache
+						.get(package)
+						.and_then(|packument| packument.dist_tag("latest"))
+						.and_then(version),
+				});
+			}
+		}
+
+		report
+	}
+}
+
+/// The largest change an upgrade may make to a range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UpgradeTarget {
+	/// Upgrade to the `latest` dist-tag, including new major versions.
+	Major,
+
+	/// Upgrade within the major version of the range.
+	Minor,
+
+	/// Upgrade within the major and minor version of the range.
+	Patch,
+}
+
+/// A range which is rewritten by an [`UpgradePlan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeUpgrade {
+	/// The field which declares the dependency.
+	pub kind: DependencyKind,
+
+	/// The name of the dependency.
+	pub name: String,
+
+	/// The declared spec.
+	pub from: String,
+
+	/// The spec after the upgrade.
+	pub to: String,
+}
+
+/// Rewrites ranges to newer versions like `npm-check-updates`. Created by
+/// [`PackumentCache::upgrade_plan`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpgradePlan {
+	/// The ranges to rewrite in declaration order.
+	pub upgrades: Vec<RangeUpgrade>,
+}
+
+impl UpgradePlan {
+	/// Write the upgraded ranges into a `package.json`.
+	pub fn apply(&self, package_json: &mut PackageJson) {
+		for upgrade in &self.upgrades {
+			let spec = package_json
+				.dependencies_of_mut(upgrade.kind)
+				.as_mut()
+				.and_then(|dependencies| dependencies.get_mut(&upgrade.name));
+
+			if let Some(spec) = spec {
+				spec.clone_from(&upgrade.to);
+			}
+		}
+	}
+}
+
+impl PackumentCache {
+	/// Plan the upgrade of every range of a `package.json` to the newest
+	/// version the target allows.
+	///
+	/// Versions newer than the `latest` dist-tag, prereleases and deprecated
+	/// versions are skipped. Ranges keep their operator, see
+	/// [`upgrade_range`], and ranges which can't be rewritten are left out.
+	#[must_use]
+	pub fn upgrade_plan(&self, package_json: &PackageJson, target: UpgradeTarget) -> UpgradePlan {
+		let mut plan = UpgradePlan::default();
+
+		for (kind, name, spec) in package_json.all_dependencies() {
+			let Some((package, range)) = registry_spec(name, spec) else {
+				continue;
+			};
+			let Some(base) = range_base(range) else {
+				continue;
+			};
+			let Some(version) = self.upgrade_version(package, &base, target) else {
+				continue;
+			};
+			let Some(upgraded) = upgrade_range(range, &version) else {
+				continue;
+			};
+			let to = spec.replacen(range, &upgraded, 1);
+
+			if to != spec {
+				plan.upgrades.push(RangeUpgrade {
+					kind,
+					name: name.to_string(),
+					from: spec.to_string(),
+					to,
+				});
+			}
+		}
+
+		plan
+	}
+
+	/// The newest version above `base` which the target allows.
+	fn upgrade_version(
+		&self,
+		name: &str,
+		base: &Version,
+		target: UpgradeTarget,
+	) -> Option<Version> {
+		let packument = self.get(name)?;
+		let latest = packument
+			.dist_tags
+			.get("latest")
+			.and_then(|latest| parse_version(latest))?;
+
+		packument
+			.versions
+			.iter()
+			.filter(|(_, manifest)| !is_deprecated(manifest))
+			.filter_map(|(version, _)| parse_version(version))
+			.filter(|version| version.pre.is_empty() && *version <= latest && version > base)
+			.filter(|version| {
+				match target {
+					UpgradeTarget::Major => true,
+					UpgradeTarget::Minor => version.major == base.major,
+					UpgradeTarget::Patch => {
+						version.major == base.major && version.minor == base.minor
+					}
+				}
+			})
+			.max()
+	}
+}
+
+/// Rewrite a range to a newer version, keeping the operator and the
+/// precision of the range. Returns `None` for ranges with several
+/// comparators or an operator other than `^`, `~`, `>=` or `=`.
+///
+/// ```
+/// use package_json_schema::upgrade_range;
+/// use semver::Version;
+///
+/// let version = Version::parse("2.4.1").unwrap();
+/// let upgrade = |range| upgrade_range(range, &version);
+///
+/// assert_eq!(upgrade("^1.2.3").as_deref(), Some("^2.4.1"));
+/// assert_eq!(upgrade("~1.2").as_deref(), Some("~2.4"));
+/// assert_eq!(upgrade("1.x").as_deref(), Some("2.x"));
+/// assert_eq!(upgrade(">=1.0.0").as_deref(), Some(">=2.4.1"));
+/// assert_eq!(upgrade("1.0.0 - 2.0.0"), None);
+/// ```
+#[must_use]
+pub fn upgrade_range(range: &str, version: &Version) -> Option<String> {
+	let (operator, prefix, parts) = split_range(range)?;
+	let precision = parts.iter().take_while(|part| !is_wildcard(part)).count();
+	let numbers = [version.major, version.minor, version.patch];
+	let mut segments: Vec<String> = if precision == 3 {
+		vec![version.to_string()]
+	} else {
+		numbers[..precision]
+			.iter()
+			.map(ToString::to_string)
+			.collect()
+	};
+
+	segments.extend(parts[precision..].iter().map(ToString::to_string));
+
+	(precision > 0).then(|| format!("{operator}{prefix}{}", segments.join(".")))
+}
+
+/// The lowest version a single comparator range allows.
+fn range_base(range: &str) -> Option<Version> {
+	let (_, _, parts) = split_range(range)?;
+	let mut numbers = [0_u64; 3];
+
+	for (index, part) in parts
+		.iter()
+		.take_while(|part| !is_wildcard(part))
+		.enumerate()
+	{
+		numbers[index] = part.split(['-', '+']).next()?.parse().ok()?;
+	}
+
+	let base = Version::new(numbers[0], numbers[1], numbers[2]);
+
+	Some(if parts.len() == 3 && !is_wildcard(parts[2]) {
+		parse_version(&parts.join(".")).unwrap_or(base)
+	} else {
+		base
+	})
+}
+
+/// Split a single comparator range into its operator, `v` prefix and the
+/// dot separated parts of its version.
+fn split_range(range: &str) -> Option<(&str, &str, Vec<&str>)> {
+	let range = range.trim();
+
+	if range.is_empty() || range.contains(char::is_whitespace) || range.contains("||") {
+		return None;
+	}
+
+	let operator = [">=", "^", "~", "="]
+		.into_iter()
+		.find(|operator| range.starts_with(operator))
+		.unwrap_or("");
+	let rest = &range[operator.len()..];
+
+	if rest.starts_with(['<', '>', '~', '=']) {
+		return None;
+	}
+
+	let prefix = if rest.starts_with('v') { "v" } else { "" };
+	let parts: Vec<&str> = rest[prefix.len()..].splitn(3, '.').collect();
+	let valid = parts.iter().enumerate().all(|(index, part)| {
+		is_wildcard(part)
+			|| (index < 2 && part.chars().all(|char| char.is_ascii_digit()) && !part.is_empty())
+			|| (index == 2 && part.starts_with(|char: char| char.is_ascii_digit()))
+	});
+
+	valid.then_some((operator, prefix, parts))
+}
+
+fn is_wildcard(part: &str) -> bool {
+	matches!(part, "x" | "X" | "*")
+}
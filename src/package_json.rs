@@ -9,6 +9,8 @@ use serde::Serialize;
 use serde_json::Value;
 use typed_builder::TypedBuilder;
 
+use crate::PnpmConfig;
+
 cfg_if! {
   if #[cfg(feature = "validate")] {
 	use validator::Validate;
@@ -17,6 +19,7 @@ cfg_if! {
 	use crate::utils::validate_version;
 	use crate::utils::validate_email_or_url;
 	use crate::utils::validate_exports_path;
+	use crate::utils::validate_peer_dependencies_meta;
 	use crate::utils::PACKAGE_MANAGER_REGEX;
 	use crate::utils::PACKAGE_NAME_REGEX;
   }
@@ -48,6 +51,10 @@ pub type AdditionalFields = IndexMap<String, Value>;
 /// assert_eq!(package_json.version.unwrap(), "0.1.0");
 /// ```
 #[cfg_attr(feature = "validate", derive(Validate))]
+#[cfg_attr(
+	feature = "validate",
+	validate(schema(function = "validate_peer_dependencies_meta"))
+)]
 #[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone)]
 pub struct PackageJson {
 	/// The name of the package.
@@ -237,6 +244,27 @@ pub struct PackageJson {
 	#[builder(default, setter(into, strip_option))]
 	pub peer_dependencies: Option<IndexMap<String, String>>,
 
+	/// Provides more information on how the peer dependencies are used. Peer
+	/// dependencies marked as `optional` don't emit warnings when they are
+	/// missing.
+	#[serde(
+		default,
+		rename = "peerDependenciesMeta",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub peer_dependencies_meta: Option<IndexMap<String, PeerDependencyMeta>>,
+
+	/// Additional installation settings for dependencies, keyed by the package
+	/// name (optionally with a version in yarn). Supported by pnpm and yarn.
+	#[serde(
+		default,
+		rename = "dependenciesMeta",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub dependencies_meta: Option<IndexMap<String, DependencyMeta>>,
+
 	/// Array of package names that will be bundled when publishing the package.
 	#[serde(
 		default,
@@ -305,6 +333,25 @@ pub struct PackageJson {
 	#[builder(default, setter(into, strip_option))]
 	pub dist: Option<Dist>,
 
+	/// The deprecation message of a published version.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub deprecated: Option<String>,
+
+	/// The account which published the version.
+	#[serde(default, rename = "_npmUser", skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub npm_user: Option<Person>,
+
+	/// Whether the published tarball contains an `npm-shrinkwrap.json`.
+	#[serde(
+		default,
+		rename = "_hasShrinkwrap",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub has_shrinkwrap: Option<bool>,
+
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	#[builder(default, setter(into, strip_option))]
 	pub readme: Option<String>,
@@ -328,6 +375,11 @@ pub struct PackageJson {
 	#[builder(default, setter(into, strip_option))]
 	pub workspaces: Option<Workspaces>,
 
+	/// Settings which are specific to pnpm.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub pnpm: Option<PnpmConfig>,
+
 	/// All addition field.
 	#[serde(flatten, skip_serializing_if = "Option::is_none")]
 	#[builder(default, setter(into, strip_option))]
@@ -364,6 +416,118 @@ impl PackageJson {
 		let content = serde_json::to_string(self).map_err(crate::Error::SerializePackageJson)?;
 		Ok(content)
 	}
+
+	/// Combine the `peerDependencies` and `peerDependenciesMeta` fields into a
+	/// single view of every peer.
+	///
+	/// Peers only listed in `peerDependenciesMeta` are treated the way package
+	/// managers do, as an optional peer accepting any version (`*`).
+	///
+	/// ```
+	/// use package_json_schema::PackageJson;
+	///
+	/// let contents = r###"
+	/// {
+	///   "peerDependencies": { "react": "^18.0.0", "react-dom": "^18.0.0" },
+	///   "peerDependenciesMeta": { "react-dom": { "optional": true } }
+	/// }
+	/// "###;
+	///
+	/// let package_json = PackageJson::try_from(contents).unwrap();
+	/// let peers = package_json.peer_requirements();
+	///
+	/// assert_eq!(peers[0].name, "react");
+	/// assert!(!peers[0].optional);
+	/// assert_eq!(peers[1].range, "^18.0.0");
+	/// assert!(peers[1].optional);
+	/// ```
+	#[must_use]
+	pub fn peer_requirements(&self) -> Vec<PeerRequirement> {
+		let meta = self.peer_dependencies_meta.as_ref();
+		let is_optional = |name: &str| {
+			meta.and_then(|meta| meta.get(name))
+				.and_then(|meta| meta.optional)
+				.unwrap_or(false)
+		};
+
+		let mut requirements: Vec<PeerRequirement> = self
+			.peer_dependencies
+			.iter()
+			.flatten()
+			.map(|(name, range)| {
+				PeerRequirement {
+					name: name.clone(),
+					range: range.clone(),
+					optional: is_optional(name),
+					declared: true,
+				}
+			})
+			.collect();
+
+		requirements.extend(
+			self.undeclared_peer_dependencies_meta()
+				.into_iter()
+				.map(|name| {
+					PeerRequirement {
+						name: name.to_string(),
+						range: "*".to_string(),
+						optional: true,
+						declared: false,
+					}
+				}),
+		);
+
+		requirements
+	}
+
+	/// The names in `peerDependenciesMeta` which have no matching entry in
+	/// `peerDependencies`.
+	#[must_use]
+	pub fn undeclared_peer_dependencies_meta(&self) -> Vec<&str> {
+		let peers = self.peer_dependencies.as_ref();
+
+		self.peer_dependencies_meta
+			.iter()
+			.flat_map(IndexMap::keys)
+			.filter(|name| !peers.is_some_and(|peers| peers.contains_key(*name)))
+			.map(String::as_str)
+			.collect()
+	}
+
+	/// The dependency field of the provided kind.
+	#[must_use]
+	pub const fn dependencies_of(&self, kind: DependencyKind) -> Option<&IndexMap<String, String>> {
+		match kind {
+			DependencyKind::Prod => self.dependencies.as_ref(),
+			DependencyKind::Dev => self.dev_dependencies.as_ref(),
+			DependencyKind::Peer => self.peer_dependencies.as_ref(),
+			DependencyKind::Optional => self.optional_dependencies.as_ref(),
+		}
+	}
+
+	/// A mutable reference to the dependency field of the provided kind.
+	pub fn dependencies_of_mut(
+		&mut self,
+		kind: DependencyKind,
+	) -> &mut Option<IndexMap<String, String>> {
+		match kind {
+			DependencyKind::Prod => &mut self.dependencies,
+			DependencyKind::Dev => &mut self.dev_dependencies,
+			DependencyKind::Peer => &mut self.peer_dependencies,
+			DependencyKind::Optional => &mut self.optional_dependencies,
+		}
+	}
+
+	/// Every entry of the dependency fields as `(kind, name, spec)`, ordered by
+	/// [`DependencyKind::ALL`] and then by declaration.
+	pub fn all_dependencies(&self) -> impl Iterator<Item = (DependencyKind, &str, &str)> {
+		DependencyKind::ALL.into_iter().flat_map(move |kind| {
+			self.dependencies_of(kind)
+				.into_iter()
+				.flatten()
+				.map(move |(name, spec)| (kind, name.as_str(), spec.as_str()))
+		})
+	}
 }
 
 impl TryFrom<PackageJson> for String {
@@ -380,6 +544,101 @@ impl Display for PackageJson {
 	}
 }
 
+/// Extra information about a single entry of `peerDependencies`.
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone)]
+pub struct PeerDependencyMeta {
+	/// Marks the peer dependency as optional. Package managers won't warn when
+	/// it is missing.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub optional: Option<bool>,
+
+	/// All additional custom fields.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+/// Installation settings for a single dependency, as used by the
+/// `dependenciesMeta` field.
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone)]
+pub struct DependencyMeta {
+	/// Hard link the dependency instead of symlinking it so that it resolves
+	/// the peer dependencies of the dependent (pnpm).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub injected: Option<bool>,
+
+	/// Whether the build scripts of the dependency should run (yarn).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub built: Option<bool>,
+
+	/// Whether the dependency should be extracted to the filesystem instead of
+	/// being kept in its archive (yarn).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub unplugged: Option<bool>,
+
+	/// All additional custom fields.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+/// A peer dependency along with whether it must be installed. Created by
+/// [`PackageJson::peer_requirements`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerRequirement {
+	/// The name of the peer dependency.
+	pub name: String,
+
+	/// The range which the installed peer must satisfy.
+	pub range: String,
+
+	/// Whether the peer may be left out.
+	pub optional: bool,
+
+	/// Whether the peer is listed in `peerDependencies`. Peers only listed in
+	/// `peerDependenciesMeta` accept any version.
+	pub declared: bool,
+}
+
+/// The `package.json` fields which declare dependencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DependencyKind {
+	/// `dependencies`
+	Prod,
+	/// `devDependencies`
+	Dev,
+	/// `peerDependencies`
+	Peer,
+	/// `optionalDependencies`
+	Optional,
+}
+
+impl DependencyKind {
+	/// Every kind in the order package managers list them.
+	pub const ALL: [Self; 4] = [Self::Prod, Self::Dev, Self::Peer, Self::Optional];
+
+	/// The name of the `package.json` field.
+	#[must_use]
+	pub const fn field(self) -> &'static str {
+		match self {
+			Self::Prod => "dependencies",
+			Self::Dev => "devDependencies",
+			Self::Peer => "peerDependencies",
+			Self::Optional => "optionalDependencies",
+		}
+	}
+}
+
+impl Display for DependencyKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.field())
+	}
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum Repository {
@@ -448,6 +707,76 @@ pub struct PublishConfig {
 	#[builder(default, setter(into, strip_option))]
 	pub registry: Option<String>,
 
+	/// Replaces `main` in the published manifest (pnpm and yarn).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub main: Option<String>,
+
+	/// Replaces `module` in the published manifest (pnpm and yarn).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub module: Option<EsNext>,
+
+	/// Replaces `browser` in the published manifest (pnpm and yarn).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub browser: Option<EsNext>,
+
+	/// Replaces `exports` in the published manifest (pnpm and yarn).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub exports: Option<Exports>,
+
+	/// Replaces `types` in the published manifest (pnpm and yarn).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub types: Option<String>,
+
+	/// Replaces `typings` in the published manifest (pnpm and yarn).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub typings: Option<String>,
+
+	/// Replaces `typesVersions` in the published manifest (pnpm).
+	#[serde(
+		default,
+		rename = "typesVersions",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub types_versions: Option<IndexMap<String, TypesVersion>>,
+
+	/// Replaces `bin` in the published manifest (pnpm and yarn).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub bin: Option<Binary>,
+
+	/// Replaces `type` in the published manifest (pnpm).
+	#[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub type_: Option<Type>,
+
+	/// Replaces `os` in the published manifest (pnpm).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub os: Option<Vec<String>>,
+
+	/// Replaces `cpu` in the published manifest (pnpm).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub cpu: Option<Vec<String>>,
+
+	/// The subdirectory which is published instead of the package root
+	/// (pnpm).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub directory: Option<String>,
+
+	/// Generate provenance statements when publishing (npm).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub provenance: Option<bool>,
+
 	/// All additional custom fields.
 	#[serde(flatten, skip_serializing_if = "Option::is_none")]
 	#[builder(default, setter(into, strip_option))]
@@ -464,6 +793,65 @@ pub struct Dist {
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	#[builder(default, setter(into, strip_option))]
 	pub tarball: Option<String>,
+
+	/// The subresource integrity of the tarball, e.g. `sha512-...`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub integrity: Option<String>,
+
+	/// The number of files in the tarball.
+	#[serde(default, rename = "fileCount", skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub file_count: Option<u64>,
+
+	/// The size of the tarball contents in bytes.
+	#[serde(
+		default,
+		rename = "unpackedSize",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub unpacked_size: Option<u64>,
+
+	/// The registry signatures of the `name@version:integrity` string.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub signatures: Option<Vec<DistSignature>>,
+
+	/// The provenance attestations published with the package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub attestations: Option<DistAttestations>,
+
+	/// All additional custom fields, e.g. the legacy `npm-signature`.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+/// A registry signature in [`Dist::signatures`].
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DistSignature {
+	/// The id of the registry key which made the signature.
+	#[builder(setter(into))]
+	pub keyid: String,
+
+	/// The base64 encoded signature.
+	#[builder(setter(into))]
+	pub sig: String,
+}
+
+/// The location of the attestations in [`Dist::attestations`].
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone)]
+pub struct DistAttestations {
+	/// The url of the attestation bundle.
+	#[builder(setter(into))]
+	pub url: String,
+
+	/// The provenance statement with its `predicateType`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub provenance: Option<Value>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -504,19 +892,14 @@ pub enum Binary {
 	Object(IndexMap<String, String>),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum Type {
+	#[default]
 	CommonJS,
 	Module,
 }
 
-impl Default for Type {
-	fn default() -> Self {
-		Self::CommonJS
-	}
-}
-
 /// A person who has been involved in creating or maintaining this package.
 #[cfg_attr(feature = "validate", derive(Validate))]
 #[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone)]
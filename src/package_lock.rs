@@ -0,0 +1,463 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use typed_builder::TypedBuilder;
+
+use crate::AdditionalFields;
+use crate::Binary;
+use crate::BundledDependencies;
+use crate::PeerDependencyMeta;
+use crate::Workspaces;
+
+/// The prefix of every installed package in the `packages` map.
+const NODE_MODULES: &str = "node_modules/";
+
+/// The `package-lock.json` and `npm-shrinkwrap.json` files written by npm.
+///
+/// Lockfile version 1 (npm 5 and 6) only has the nested `dependencies` tree,
+/// version 2 (npm 7 and 8) has both the `packages` map and the
+/// `dependencies` tree and version 3 (npm 9 and later) only has `packages`.
+/// Unknown fields are kept so that the file can be written back without
+/// losing information. See <https://docs.npmjs.com/cli/configuring-npm/package-lock-json>.
+///
+/// ```
+/// use package_json_schema::PackageLock;
+///
+/// let contents = r#"{
+///   "name": "app",
+///   "lockfileVersion": 3,
+///   "packages": {
+///     "": { "name": "app", "dependencies": { "ms": "^2.1.0" } },
+///     "node_modules/ms": { "version": "2.1.3" }
+///   }
+/// }"#;
+///
+/// let package_lock = PackageLock::try_from(contents).unwrap();
+/// let installed = package_lock.installed("ms");
+///
+/// assert_eq!(installed[0].0, "node_modules/ms");
+/// assert_eq!(installed[0].1.version.as_deref(), Some("2.1.3"));
+/// ```
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PackageLock {
+	/// The name of the root package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub name: Option<String>,
+
+	/// The version of the root package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub version: Option<String>,
+
+	/// The version of the lockfile format, `1`, `2` or `3`.
+	#[serde(
+		default,
+		rename = "lockfileVersion",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub lockfile_version: Option<u32>,
+
+	/// Always `true` for lockfiles written by npm 7 and later.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub requires: Option<bool>,
+
+	/// Every package of the tree keyed by its location relative to the root,
+	/// e.g. `node_modules/foo/node_modules/bar`. The root package has the
+	/// empty key.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub packages: Option<IndexMap<String, LockPackage>>,
+
+	/// The legacy tree of the installed dependencies, used by lockfile
+	/// versions 1 and 2.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub dependencies: Option<LockDependencies>,
+
+	/// All addition field.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+impl TryFrom<&str> for PackageLock {
+	type Error = crate::error::Error;
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		let package_lock: Self =
+			serde_json::from_str(value).map_err(crate::Error::ParsePackageLock)?;
+		Ok(package_lock)
+	}
+}
+
+impl TryFrom<String> for PackageLock {
+	type Error = crate::error::Error;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		Self::try_from(value.as_str())
+	}
+}
+
+impl PackageLock {
+	/// Convert the [`PackageLock`] to a [`String`] formatted the way npm
+	/// writes it: indented with two spaces and ending with a newline.
+	///
+	/// # Errors
+	///
+	/// This will return an error when the [`PackageLock`] cannot be
+	/// serialized.
+	pub fn try_to_string(&self) -> Result<String, crate::error::Error> {
+		let content =
+			serde_json::to_string_pretty(self).map_err(crate::Error::SerializePackageLock)?;
+		Ok(format!("{content}\n"))
+	}
+
+	/// The entry of the root package in `packages`.
+	#[must_use]
+	pub fn root(&self) -> Option<&LockPackage> {
+		self.package("")
+	}
+
+	/// The entry of `packages` at a location, e.g. `node_modules/foo`.
+	#[must_use]
+	pub fn package(&self, path: &str) -> Option<&LockPackage> {
+		self.packages
+			.as_ref()
+			.and_then(|packages| packages.get(path))
+	}
+
+	/// The installed copies of a package in `packages` with their locations.
+	/// The name is the folder the package is installed in, which differs from
+	/// the `name` of aliased packages.
+	#[must_use]
+	pub fn installed(&self, name: &str) -> Vec<(&str, &LockPackage)> {
+		self.packages
+			.iter()
+			.flatten()
+			.filter(|(path, _)| package_name_from_path(path) == Some(name))
+			.map(|(path, package)| (path.as_str(), package))
+			.collect()
+	}
+
+	/// Flatten the legacy `dependencies` tree into the locations used by
+	/// `packages`, e.g. `node_modules/foo/node_modules/bar`.
+	#[must_use]
+	pub fn legacy_packages(&self) -> Vec<(String, &LockDependency)> {
+		let mut entries = Vec::new();
+		let mut stack: Vec<(String, &LockDependencies)> = self
+			.dependencies
+			.iter()
+			.map(|dependencies| (String::new(), dependencies))
+			.collect();
+
+		while let Some((parent, dependencies)) = stack.pop() {
+			for (name, dependency) in dependencies {
+				let path = format!("{parent}{NODE_MODULES}{name}");
+
+				if let Some(nested) = &dependency.dependencies {
+					stack.push((format!("{path}/"), nested));
+				}
+
+				entries.push((path, dependency));
+			}
+		}
+
+		entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+		entries
+	}
+}
+
+impl TryFrom<PackageLock> for String {
+	type Error = crate::error::Error;
+
+	fn try_from(value: PackageLock) -> Result<Self, Self::Error> {
+		value.try_to_string()
+	}
+}
+
+/// The name of the package installed at a location of the `packages` map.
+///
+/// The name is taken from the last `node_modules/` segment, so the root and
+/// workspace folders which aren't inside `node_modules` have no name.
+///
+/// ```
+/// use package_json_schema::package_name_from_path;
+///
+/// let path = "node_modules/a/node_modules/@scope/b";
+///
+/// assert_eq!(package_name_from_path(path), Some("@scope/b"));
+/// assert_eq!(package_name_from_path("packages/a"), None);
+/// ```
+#[must_use]
+pub fn package_name_from_path(path: &str) -> Option<&str> {
+	path.rfind(NODE_MODULES)
+		.map(|index| &path[index + NODE_MODULES.len()..])
+		.filter(|name| !name.is_empty())
+}
+
+/// An entry of the `packages` map of a [`PackageLock`].
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct LockPackage {
+	/// The name of the package, only set for the root, workspaces and aliased
+	/// packages.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub name: Option<String>,
+
+	/// The installed version.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub version: Option<String>,
+
+	/// Where the package was fetched from. For links this is the location of
+	/// the target relative to the root.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub resolved: Option<String>,
+
+	/// The subresource integrity of the tarball, e.g. `sha512-...`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub integrity: Option<String>,
+
+	/// Whether this is a symbolic link to the `resolved` location.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub link: Option<bool>,
+
+	/// Only required through `devDependencies`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub dev: Option<bool>,
+
+	/// Only required through `optionalDependencies`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub optional: Option<bool>,
+
+	/// Only required through optional dependencies of `devDependencies`.
+	#[serde(
+		default,
+		rename = "devOptional",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub dev_optional: Option<bool>,
+
+	/// Bundled in the tarball of its parent.
+	#[serde(default, rename = "inBundle", skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub in_bundle: Option<bool>,
+
+	/// Only required through `peerDependencies`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub peer: Option<bool>,
+
+	/// Whether the package has `preinstall`, `install` or `postinstall`
+	/// scripts.
+	#[serde(
+		default,
+		rename = "hasInstallScript",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub has_install_script: Option<bool>,
+
+	/// Whether the package ships an `npm-shrinkwrap.json`.
+	#[serde(
+		default,
+		rename = "hasShrinkwrap",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub has_shrinkwrap: Option<bool>,
+
+	/// The dependencies of the package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub dependencies: Option<IndexMap<String, String>>,
+
+	/// The dev dependencies, only recorded for the root and workspaces.
+	#[serde(
+		default,
+		rename = "devDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub dev_dependencies: Option<IndexMap<String, String>>,
+
+	/// The optional dependencies of the package.
+	#[serde(
+		default,
+		rename = "optionalDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub optional_dependencies: Option<IndexMap<String, String>>,
+
+	/// The peer dependencies of the package.
+	#[serde(
+		default,
+		rename = "peerDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub peer_dependencies: Option<IndexMap<String, String>>,
+
+	/// Extra information about the peer dependencies.
+	#[serde(
+		default,
+		rename = "peerDependenciesMeta",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub peer_dependencies_meta: Option<IndexMap<String, PeerDependencyMeta>>,
+
+	/// The dependencies bundled in the tarball.
+	#[serde(
+		default,
+		rename = "bundleDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub bundle_dependencies: Option<BundledDependencies>,
+
+	/// The `engines` field of the package. Old packages may use an array.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub engines: Option<Value>,
+
+	/// The operating systems supported by the package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub os: Option<Vec<String>>,
+
+	/// The CPU architectures supported by the package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub cpu: Option<Vec<String>>,
+
+	/// The license of the package. Old packages may use an object with a
+	/// `type` and a `url`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub license: Option<Value>,
+
+	/// The executables of the package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub bin: Option<Binary>,
+
+	/// The `funding` field of the package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub funding: Option<Value>,
+
+	/// The deprecation message of the installed version.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub deprecated: Option<String>,
+
+	/// The workspaces of the root package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub workspaces: Option<Workspaces>,
+
+	/// All addition field.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+impl LockPackage {
+	/// Whether the package is only installed for development.
+	#[must_use]
+	pub fn is_dev(&self) -> bool {
+		self.dev == Some(true)
+	}
+
+	/// Whether the package is only installed through optional dependencies.
+	#[must_use]
+	pub fn is_optional(&self) -> bool {
+		self.optional == Some(true)
+	}
+
+	/// Whether the package is a symbolic link.
+	#[must_use]
+	pub fn is_link(&self) -> bool {
+		self.link == Some(true)
+	}
+}
+
+/// The legacy `dependencies` tree of a [`PackageLock`], keyed by name.
+pub type LockDependencies = IndexMap<String, LockDependency>;
+
+/// An entry of the legacy `dependencies` tree of a [`PackageLock`].
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct LockDependency {
+	/// The installed version, or the location of linked and local packages.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub version: Option<String>,
+
+	/// Where the package was fetched from.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub resolved: Option<String>,
+
+	/// The subresource integrity of the tarball.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub integrity: Option<String>,
+
+	/// Bundled in the tarball of its parent.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub bundled: Option<bool>,
+
+	/// Only required through `devDependencies`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub dev: Option<bool>,
+
+	/// Only required through `optionalDependencies`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub optional: Option<bool>,
+
+	/// Only required through optional dependencies of `devDependencies`.
+	#[serde(
+		default,
+		rename = "devOptional",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub dev_optional: Option<bool>,
+
+	/// Only required through `peerDependencies`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub peer: Option<bool>,
+
+	/// The dependencies of the package with the range they were required
+	/// with.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub requires: Option<IndexMap<String, String>>,
+
+	/// The dependencies installed below this package because the hoisted
+	/// version doesn't match.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub dependencies: Option<LockDependencies>,
+
+	/// All addition field.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
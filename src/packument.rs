@@ -0,0 +1,339 @@
+use std::cmp::Ordering;
+
+use indexmap::IndexMap;
+use semver::Version;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value;
+use typed_builder::TypedBuilder;
+
+use crate::parse_version;
+use crate::AdditionalFields;
+use crate::PackageJson;
+use crate::Person;
+use crate::VersionRange;
+
+/// The accept header which requests the [`AbbreviatedPackument`] of a
+/// package from the registry.
+pub const ABBREVIATED_PACKUMENT_ACCEPT: &str = "application/vnd.npm.install-v1+json";
+
+/// The document the registry serves for a package, with the full manifest
+/// of every published version.
+///
+/// ```
+/// use package_json_schema::Packument;
+///
+/// let contents = r#"{
+///   "name": "ms",
+///   "dist-tags": { "latest": "2.1.3", "beta": "3.0.0-canary.1" },
+///   "versions": {
+///     "2.1.2": { "name": "ms", "version": "2.1.2" },
+///     "2.1.3": { "name": "ms", "version": "2.1.3" },
+///     "3.0.0-canary.1": { "name": "ms", "version": "3.0.0-canary.1" }
+///   }
+/// }"#;
+/// let packument = Packument::try_from(contents).unwrap();
+/// let version = |spec| packument.resolve(spec).and_then(|p| p.version.clone());
+///
+/// assert_eq!(version("beta").as_deref(), Some("3.0.0-canary.1"));
+/// assert_eq!(version("~2.1.0").as_deref(), Some("2.1.3"));
+/// assert_eq!(version("2.1.2").as_deref(), Some("2.1.2"));
+/// assert_eq!(version("^3.0.0"), None);
+/// ```
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone)]
+pub struct Packument {
+	/// The name of the package, also used as the document id.
+	#[serde(default, rename = "_id", skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub id: Option<String>,
+
+	/// The revision of the registry document.
+	#[serde(default, rename = "_rev", skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub rev: Option<String>,
+
+	/// The name of the package.
+	#[builder(setter(into))]
+	pub name: String,
+
+	/// The description of the latest version.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub description: Option<String>,
+
+	/// The versions of the named tags, e.g. `latest` or `next`.
+	#[serde(default, rename = "dist-tags")]
+	#[builder(default, setter(into))]
+	pub dist_tags: IndexMap<String, String>,
+
+	/// The manifest of every published version keyed by its version. Fields
+	/// of old versions which don't match the schema, like an object
+	/// `license`, are kept in [`PackageJson::other`].
+	#[serde(default, deserialize_with = "deserialize_versions")]
+	#[builder(default, setter(into))]
+	pub versions: IndexMap<String, PackageJson>,
+
+	/// The publish time of every version, along with the `created` and
+	/// `modified` times of the package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub time: Option<IndexMap<String, String>>,
+
+	/// The accounts which can publish the package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub maintainers: Option<Vec<Person>>,
+
+	/// The readme of the latest version.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub readme: Option<String>,
+
+	/// All additional fields, e.g. `repository`, `license` or `users`.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+impl TryFrom<&str> for Packument {
+	type Error = crate::error::Error;
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		serde_json::from_str(value).map_err(crate::Error::ParsePackument)
+	}
+}
+
+impl TryFrom<String> for Packument {
+	type Error = crate::error::Error;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		Self::try_from(value.as_str())
+	}
+}
+
+impl Packument {
+	/// The manifest of the version a dist-tag points to.
+	#[must_use]
+	pub fn dist_tag(&self, tag: &str) -> Option<&PackageJson> {
+		dist_tag(&self.dist_tags, &self.versions, tag)
+	}
+
+	/// The manifest of the highest version which satisfies the range.
+	#[must_use]
+	pub fn max_satisfying(&self, range: &VersionRange) -> Option<&PackageJson> {
+		max_satisfying(&self.versions, range)
+	}
+
+	/// Resolve a dist-tag, a version or a range. See
+	/// [`AbbreviatedPackument::resolve`].
+	#[must_use]
+	pub fn resolve(&self, spec: &str) -> Option<&PackageJson> {
+		pick_manifest(&self.dist_tags, &self.versions, spec, "latest", None)
+	}
+}
+
+/// The document the registry serves for [`ABBREVIATED_PACKUMENT_ACCEPT`].
+///
+/// Every version only has the fields needed to install it, such as the
+/// dependencies, `engines`, `bin`, `dist` and `deprecated`.
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone)]
+pub struct AbbreviatedPackument {
+	/// The name of the package.
+	#[builder(setter(into))]
+	pub name: String,
+
+	/// The last time the package was modified.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub modified: Option<String>,
+
+	/// The versions of the named tags, e.g. `latest` or `next`.
+	#[serde(default, rename = "dist-tags")]
+	#[builder(default, setter(into))]
+	pub dist_tags: IndexMap<String, String>,
+
+	/// The abbreviated manifest of every published version keyed by its
+	/// version, read like [`Packument::versions`].
+	#[serde(default, deserialize_with = "deserialize_versions")]
+	#[builder(default, setter(into))]
+	pub versions: IndexMap<String, PackageJson>,
+
+	/// All additional fields.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+impl TryFrom<&str> for AbbreviatedPackument {
+	type Error = crate::error::Error;
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		serde_json::from_str(value).map_err(crate::Error::ParsePackument)
+	}
+}
+
+impl TryFrom<String> for AbbreviatedPackument {
+	type Error = crate::error::Error;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		Self::try_from(value.as_str())
+	}
+}
+
+impl AbbreviatedPackument {
+	/// The manifest of the version a dist-tag points to.
+	#[must_use]
+	pub fn dist_tag(&self, tag: &str) -> Option<&PackageJson> {
+		dist_tag(&self.dist_tags, &self.versions, tag)
+	}
+
+	/// The manifest of the highest version which satisfies the range.
+	#[must_use]
+	pub fn max_satisfying(&self, range: &VersionRange) -> Option<&PackageJson> {
+		max_satisfying(&self.versions, range)
+	}
+
+	/// Resolve a dist-tag, a version or a range.
+	///
+	/// An empty spec resolves to `latest`. A range resolves to `latest` when
+	/// it satisfies the range and isn't deprecated, otherwise to the highest
+	/// satisfying version, preferring versions which aren't deprecated.
+	/// Prereleases only satisfy ranges which mention a prerelease.
+	#[must_use]
+	pub fn resolve(&self, spec: &str) -> Option<&PackageJson> {
+		pick_manifest(&self.dist_tags, &self.versions, spec, "latest", None)
+	}
+}
+
+/// Read every version on its own, so one legacy manifest doesn't fail the
+/// whole document. Versions which aren't objects are skipped.
+fn deserialize_versions<'de, D>(deserializer: D) -> Result<IndexMap<String, PackageJson>, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	let versions = IndexMap::<String, Value>::deserialize(deserializer)?;
+
+	Ok(versions
+		.into_iter()
+		.filter_map(|(version, manifest)| Some((version, lenient_manifest(manifest)?)))
+		.collect())
+}
+
+/// Parse a manifest, moving the fields which don't match the schema to
+/// [`PackageJson::other`].
+fn lenient_manifest(manifest: Value) -> Option<PackageJson> {
+	let Value::Object(fields) = manifest else {
+		return None;
+	};
+
+	if let Ok(manifest) = serde_json::from_value(Value::Object(fields.clone())) {
+		return Some(manifest);
+	}
+
+	let (valid, invalid): (Map<String, Value>, Map<String, Value>) =
+		fields.into_iter().partition(|(key, value)| {
+			let field = Map::from_iter([(key.clone(), value.clone())]);
+			serde_json::from_value::<PackageJson>(Value::Object(field)).is_ok()
+		});
+	let mut manifest: PackageJson = serde_json::from_value(Value::Object(valid)).ok()?;
+	manifest
+		.other
+		.get_or_insert_with(AdditionalFields::new)
+		.extend(invalid);
+
+	Some(manifest)
+}
+
+fn dist_tag<'a>(
+	dist_tags: &IndexMap<String, String>,
+	versions: &'a IndexMap<String, PackageJson>,
+	tag: &str,
+) -> Option<&'a PackageJson> {
+	versions.get(dist_tags.get(tag)?)
+}
+
+fn max_satisfying<'a>(
+	versions: &'a IndexMap<String, PackageJson>,
+	range: &VersionRange,
+) -> Option<&'a PackageJson> {
+	versions
+		.iter()
+		.filter_map(|(key, manifest)| Some((parse_version(key)?, manifest)))
+		.filter(|(version, _)| range.satisfies(version))
+		.max_by(|(a, _), (b, _)| a.cmp_precedence(b))
+		.map(|(_, manifest)| manifest)
+}
+
+/// Pick the manifest npm installs for a spec. Ranges prefer the default tag,
+/// then versions which aren't deprecated and support the node version.
+pub(crate) fn pick_manifest<'a>(
+	dist_tags: &IndexMap<String, String>,
+	versions: &'a IndexMap<String, PackageJson>,
+	spec: &str,
+	default_tag: &str,
+	node: Option<&Version>,
+) -> Option<&'a PackageJson> {
+	let spec = spec.trim();
+
+	if let Some(manifest) =
+		parse_version(spec).and_then(|version| versions.get(&version.to_string()))
+	{
+		return Some(manifest);
+	}
+
+	let range = if spec.is_empty() { "*" } else { spec };
+	let Ok(range) = VersionRange::parse(range) else {
+		return dist_tag(dist_tags, versions, spec);
+	};
+	let preferred = |manifest: &PackageJson| {
+		let engine = node.map_or(true, |node| supports_node(manifest, node));
+		let current = !is_deprecated(manifest);
+
+		(current && engine, engine, current)
+	};
+
+	let default = dist_tags.get(default_tag).and_then(|version| {
+		let manifest = versions.get(version)?;
+		let satisfies = range.as_str() == "*" || range.satisfies(&parse_version(version)?);
+
+		(satisfies && preferred(manifest).0).then_some(manifest)
+	});
+
+	if default.is_some() {
+		return default;
+	}
+
+	versions
+		.iter()
+		.filter_map(|(version, manifest)| Some((parse_version(version)?, manifest)))
+		.filter(|(version, _)| range.satisfies(version))
+		.max_by(|(a, a_manifest), (b, b_manifest)| {
+			match preferred(a_manifest).cmp(&preferred(b_manifest)) {
+				Ordering::Equal => a.cmp_precedence(b),
+				ordering => ordering,
+			}
+		})
+		.map(|(_, manifest)| manifest)
+}
+
+/// Whether a version is deprecated. The registry un-deprecates a version by
+/// setting an empty message.
+pub(crate) fn is_deprecated(manifest: &PackageJson) -> bool {
+	manifest
+		.deprecated
+		.as_deref()
+		.is_some_and(|message| !message.is_empty())
+}
+
+/// Whether the `engines.node` range of a manifest accepts the node version.
+/// Missing or invalid ranges accept every version.
+fn supports_node(manifest: &PackageJson, node: &Version) -> bool {
+	manifest
+		.engines
+		.as_ref()
+		.and_then(|engines| engines.get("node"))
+		.and_then(|range| VersionRange::parse(range).ok())
+		.map_or(true, |range| range.satisfies_including_prerelease(node))
+}
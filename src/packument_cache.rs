@@ -0,0 +1,206 @@
+use std::path::Path;
+
+use indexmap::IndexMap;
+use typed_builder::TypedBuilder;
+use walkdir::WalkDir;
+
+use crate::packument::pick_manifest;
+use crate::parse_version;
+use crate::workspace::read_file;
+use crate::AbbreviatedPackument;
+use crate::DependencyKind;
+use crate::DependencySpec;
+use crate::PackageJson;
+use crate::Packument;
+
+/// Options for picking the version npm installs for a spec.
+#[derive(TypedBuilder, Debug, Clone, Default)]
+pub struct PickOptions {
+	/// The dist-tag preferred for ranges, `latest` when unset.
+	#[builder(default, setter(into, strip_option))]
+	pub default_tag: Option<String>,
+
+	/// The node version checked against `engines.node`. Versions which don't
+	/// support it are only picked when nothing else satisfies the range.
+	/// Engines are ignored when unset.
+	#[builder(default, setter(into, strip_option))]
+	pub node_version: Option<String>,
+}
+
+impl PickOptions {
+	fn pick<'a>(
+		&self,
+		dist_tags: &IndexMap<String, String>,
+		versions: &'a IndexMap<String, PackageJson>,
+		spec: &str,
+	) -> Option<&'a PackageJson> {
+		let tag = self.default_tag.as_deref().unwrap_or("latest");
+		let node = self.node_version.as_deref().and_then(parse_version);
+
+		pick_manifest(dist_tags, versions, spec, tag, node.as_ref())
+	}
+}
+
+/// The version picked for a dependency of a `package.json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PickedDependency {
+	/// The field which declares the dependency.
+	pub kind: DependencyKind,
+
+	/// The name of the dependency.
+	pub name: String,
+
+	/// The declared spec.
+	pub spec: String,
+
+	/// The picked version. This is `None` when the packument isn't cached or
+	/// no version satisfies the spec.
+	pub version: Option<String>,
+}
+
+/// Packuments loaded from a directory, used to pick versions without a
+/// registry.
+///
+/// Every `*.json` file below the directory is read as an
+/// [`AbbreviatedPackument`], so full packuments can be cached as well.
+/// Scoped packages can be stored as `@scope/name.json`. Packuments are keyed
+/// by the name they contain rather than their file name.
+///
+/// ```
+/// use package_json_schema::PackumentCache;
+/// use package_json_schema::PickOptions;
+///
+/// let cache = PackumentCache::load("./tests/fixtures/packuments").unwrap();
+/// let options = PickOptions::default();
+/// let debug = cache.pick_manifest("debug", "^4.3.0", &options).unwrap();
+///
+/// assert_eq!(debug.version.as_deref(), Some("4.3.4"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PackumentCache {
+	packuments: IndexMap<String, AbbreviatedPackument>,
+}
+
+impl PackumentCache {
+	/// Read every packument below a directory in file name order.
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::ReadFile`] or
+	/// [`crate::Error::ParsePackumentFile`] when a file can't be read or
+	/// parsed.
+	pub fn load(directory: impl AsRef<Path>) -> crate::Result<Self> {
+		let mut cache = Self::default();
+		let walker = WalkDir::new(directory).sort_by_file_name().into_iter();
+
+		for entry in walker.filter_map(Result::ok) {
+			let path = entry.path();
+
+			if !entry.file_type().is_file() || path.extension().map_or(true, |ext| ext != "json") {
+				continue;
+			}
+
+			let packument = serde_json::from_str(&read_file(path)?).map_err(|source| {
+				crate::Error::ParsePackumentFile {
+					path: path.to_path_buf(),
+					source,
+				}
+			})?;
+			cache.insert(packument);
+		}
+
+		Ok(cache)
+	}
+
+	/// Add a packument, replacing the cached packument with the same name.
+	pub fn insert(&mut self, packument: AbbreviatedPackument) {
+		self.packuments.insert(packument.name.clone(), packument);
+	}
+
+	/// The cached packument of a package.
+	#[must_use]
+	pub fn get(&self, name: &str) -> Option<&AbbreviatedPackument> {
+		self.packuments.get(name)
+	}
+
+	/// The names of the cached packages.
+	pub fn names(&self) -> impl Iterator<Item = &str> {
+		self.packuments.keys().map(String::as_str)
+	}
+
+	/// Pick the manifest npm installs for a dependency, following
+	/// `npm:<name>@<range>` aliases. See
+	/// [`AbbreviatedPackument::pick_manifest`].
+	#[must_use]
+	pub fn pick_manifest(
+		&self,
+		name: &str,
+		spec: &str,
+		options: &PickOptions,
+	) -> Option<&PackageJson> {
+		let (name, spec) = registry_spec(name, spec)?;
+
+		self.get(name)?.pick_manifest(spec, options)
+	}
+
+	/// Pick the version of every dependency of a `package.json` which
+	/// installs from the registry. Dependencies using the `workspace:`,
+	/// `catalog:`, path or url protocols are left out.
+	#[must_use]
+	pub fn pick_dependencies(
+		&self,
+		package_json: &PackageJson,
+		options: &PickOptions,
+	) -> Vec<PickedDependency> {
+		package_json
+			.all_dependencies()
+			.filter(|(_, name, spec)| registry_spec(name, spec).is_some())
+			.map(|(kind, name, spec)| {
+				PickedDependency {
+					kind,
+					name: name.to_string(),
+					spec: spec.to_string(),
+					version: self
+						.pick_manifest(name, spec, options)
+						.and_then(|manifest| manifest.version.clone()),
+				}
+			})
+			.collect()
+	}
+}
+
+impl AbbreviatedPackument {
+	/// Pick the manifest npm installs for a dist-tag, a version or a range,
+	/// like `npm-pick-manifest`.
+	///
+	/// A range resolves to the default tag when it satisfies the range and
+	/// the version is neither deprecated nor rejected by `engines`.
+	/// Otherwise the highest satisfying version is picked, preferring
+	/// versions which aren't deprecated and support the node version.
+	/// Prereleases only satisfy ranges which mention a prerelease.
+	#[must_use]
+	pub fn pick_manifest(&self, spec: &str, options: &PickOptions) -> Option<&PackageJson> {
+		options.pick(&self.dist_tags, &self.versions, spec)
+	}
+}
+
+impl Packument {
+	/// Pick the manifest npm installs for a spec. See
+	/// [`AbbreviatedPackument::pick_manifest`].
+	#[must_use]
+	pub fn pick_manifest(&self, spec: &str, options: &PickOptions) -> Option<&PackageJson> {
+		options.pick(&self.dist_tags, &self.versions, spec)
+	}
+}
+
+/// The name of the package a dependency installs from the registry and the
+/// range or tag it is installed with, following `npm:` aliases. Other
+/// protocols return `None`.
+pub(crate) fn registry_spec<'a>(name: &'a str, spec: &'a str) -> Option<(&'a str, &'a str)> {
+	match DependencySpec::parse(spec) {
+		DependencySpec::Range(range) => Some((name, range)),
+		DependencySpec::Tag(tag) => Some((name, tag)),
+		DependencySpec::Alias { name, range } => Some((name, range)),
+		_ => None,
+	}
+}
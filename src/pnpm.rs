@@ -0,0 +1,383 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde::Serialize;
+use typed_builder::TypedBuilder;
+
+use crate::parse_version;
+use crate::AdditionalFields;
+use crate::PackageJson;
+use crate::PeerDependencyMeta;
+use crate::VersionRange;
+
+/// The `pnpm` field of a `package.json`. See <https://pnpm.io/package_json>.
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PnpmConfig {
+	/// Override any dependency in the dependency graph, keyed by a selector
+	/// such as `foo`, `foo@<1.0.0` or `bar>foo`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub overrides: Option<IndexMap<String, String>>,
+
+	/// Extend the manifests of dependencies with missing dependencies or peer
+	/// dependencies, keyed by a selector such as `react-redux` or
+	/// `react-redux@^7.0.0`.
+	#[serde(
+		default,
+		rename = "packageExtensions",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub package_extensions: Option<IndexMap<String, PackageExtension>>,
+
+	/// Rules for how unmet or missing peer dependencies are reported.
+	#[serde(
+		default,
+		rename = "peerDependencyRules",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub peer_dependency_rules: Option<PeerDependencyRules>,
+
+	/// The only dependencies which are allowed to run their install scripts.
+	#[serde(
+		default,
+		rename = "onlyBuiltDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub only_built_dependencies: Option<Vec<String>>,
+
+	/// A JSON file listing the only dependencies which are allowed to run
+	/// their install scripts.
+	#[serde(
+		default,
+		rename = "onlyBuiltDependenciesFile",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub only_built_dependencies_file: Option<String>,
+
+	/// Dependencies which are not allowed to run their install scripts.
+	#[serde(
+		default,
+		rename = "neverBuiltDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub never_built_dependencies: Option<Vec<String>>,
+
+	/// Dependencies whose install scripts are ignored without a warning.
+	#[serde(
+		default,
+		rename = "ignoredBuiltDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub ignored_built_dependencies: Option<Vec<String>>,
+
+	/// Patches applied to dependencies, keyed by `name@version` with the path
+	/// to the patch file as the value.
+	#[serde(
+		default,
+		rename = "patchedDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub patched_dependencies: Option<IndexMap<String, String>>,
+
+	/// Whether patches which don't apply to any dependency are allowed.
+	#[serde(
+		default,
+		rename = "allowNonAppliedPatches",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub allow_non_applied_patches: Option<bool>,
+
+	/// Deprecation warnings to mute, keyed by package name with a range of the
+	/// deprecated versions as the value.
+	#[serde(
+		default,
+		rename = "allowedDeprecatedVersions",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub allowed_deprecated_versions: Option<IndexMap<String, String>>,
+
+	/// Optional dependencies which are never installed.
+	#[serde(
+		default,
+		rename = "ignoredOptionalDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub ignored_optional_dependencies: Option<Vec<String>>,
+
+	/// Scripts which must exist in every project of the workspace.
+	#[serde(
+		default,
+		rename = "requiredScripts",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub required_scripts: Option<Vec<String>>,
+
+	/// Install optional dependencies for architectures other than the current
+	/// one.
+	#[serde(
+		default,
+		rename = "supportedArchitectures",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub supported_architectures: Option<SupportedArchitectures>,
+
+	/// Settings for `pnpm update`.
+	#[serde(
+		default,
+		rename = "updateConfig",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub update_config: Option<UpdateConfig>,
+
+	/// Settings for `pnpm audit`.
+	#[serde(
+		default,
+		rename = "auditConfig",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub audit_config: Option<AuditConfig>,
+
+	/// Settings for the environment which scripts are executed in.
+	#[serde(
+		default,
+		rename = "executionEnv",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub execution_env: Option<ExecutionEnv>,
+
+	/// All additional custom fields.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+/// The fields which a `packageExtensions` entry adds to a dependency's
+/// manifest.
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PackageExtension {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub dependencies: Option<IndexMap<String, String>>,
+
+	#[serde(
+		default,
+		rename = "optionalDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub optional_dependencies: Option<IndexMap<String, String>>,
+
+	#[serde(
+		default,
+		rename = "peerDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub peer_dependencies: Option<IndexMap<String, String>>,
+
+	#[serde(
+		default,
+		rename = "peerDependenciesMeta",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub peer_dependencies_meta: Option<IndexMap<String, PeerDependencyMeta>>,
+}
+
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PeerDependencyRules {
+	/// Peer dependencies which don't produce a warning when they are missing.
+	#[serde(
+		default,
+		rename = "ignoreMissing",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub ignore_missing: Option<Vec<String>>,
+
+	/// Peer dependencies which accept any version.
+	#[serde(default, rename = "allowAny", skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub allow_any: Option<Vec<String>>,
+
+	/// Extra ranges which peer dependencies accept, keyed by a peer name or a
+	/// `parent>peer` selector.
+	#[serde(
+		default,
+		rename = "allowedVersions",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub allowed_versions: Option<IndexMap<String, String>>,
+}
+
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SupportedArchitectures {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub os: Option<Vec<String>>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub cpu: Option<Vec<String>>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub libc: Option<Vec<String>>,
+}
+
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct UpdateConfig {
+	/// Dependencies which `pnpm outdated` and `pnpm update --latest` skip.
+	#[serde(
+		default,
+		rename = "ignoreDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub ignore_dependencies: Option<Vec<String>>,
+}
+
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct AuditConfig {
+	/// CVE identifiers which `pnpm audit` ignores.
+	#[serde(
+		default,
+		rename = "ignoreCves",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub ignore_cves: Option<Vec<String>>,
+
+	/// GitHub advisory identifiers which `pnpm audit` ignores.
+	#[serde(
+		default,
+		rename = "ignoreGhsas",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub ignore_ghsas: Option<Vec<String>>,
+}
+
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ExecutionEnv {
+	/// The version of Node.js used to run scripts.
+	#[serde(
+		default,
+		rename = "nodeVersion",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub node_version: Option<String>,
+}
+
+impl PnpmConfig {
+	/// Apply every matching entry of `packageExtensions` to the provided
+	/// dependency manifest, returning the selectors which matched.
+	///
+	/// A selector without a range matches every version of the package. A
+	/// selector with a range only matches when the manifest has a version which
+	/// satisfies it. Like pnpm, the fields of the manifest take precedence over
+	/// the fields of the extension.
+	///
+	/// ```
+	/// use package_json_schema::PackageJson;
+	///
+	/// let root = PackageJson::try_from(
+	///   r#"{ "pnpm": { "packageExtensions": { "react-redux@7": { "peerDependencies": { "react-dom": "*" } } } } }"#,
+	/// )
+	/// .unwrap();
+	/// let mut dependency =
+	///   PackageJson::try_from(r#"{ "name": "react-redux", "version": "7.2.9" }"#).unwrap();
+	///
+	/// let pnpm = root.pnpm.unwrap();
+	/// let applied = pnpm.apply_package_extensions(&mut dependency);
+	///
+	/// assert_eq!(applied, ["react-redux@7"]);
+	/// assert_eq!(dependency.peer_dependencies.unwrap()["react-dom"], "*");
+	/// ```
+	pub fn apply_package_extensions(&self, package_json: &mut PackageJson) -> Vec<&str> {
+		let Some(name) = package_json.name.as_deref() else {
+			return vec![];
+		};
+		let version = package_json.version.as_deref().and_then(parse_version);
+
+		let matching: Vec<(&str, &PackageExtension)> = self
+			.package_extensions
+			.iter()
+			.flatten()
+			.filter(|(selector, _)| {
+				let (selector_name, range) = split_selector(selector);
+
+				selector_name == name
+					&& range.map_or(true, |range| {
+						let range = VersionRange::parse(range).ok();
+						matches!((range, &version), (Some(range), Some(version)) if range.satisfies(version))
+					})
+			})
+			.map(|(selector, extension)| (selector.as_str(), extension))
+			.collect();
+
+		for (_, extension) in &matching {
+			extend(
+				&mut package_json.dependencies,
+				extension.dependencies.as_ref(),
+			);
+			extend(
+				&mut package_json.optional_dependencies,
+				extension.optional_dependencies.as_ref(),
+			);
+			extend(
+				&mut package_json.peer_dependencies,
+				extension.peer_dependencies.as_ref(),
+			);
+			extend(
+				&mut package_json.peer_dependencies_meta,
+				extension.peer_dependencies_meta.as_ref(),
+			);
+		}
+
+		matching.into_iter().map(|(selector, _)| selector).collect()
+	}
+}
+
+/// Split a `name@range` selector into its name and optional range.
+fn split_selector(selector: &str) -> (&str, Option<&str>) {
+	let start = usize::from(selector.starts_with('@'));
+
+	selector[start..]
+		.find('@')
+		.map_or((selector, None), |index| {
+			let (name, range) = selector.split_at(start + index);
+			(name, Some(&range[1..]))
+		})
+}
+
+/// Merge the extension into the field, the equivalent of
+/// `{ ...extension, ...field }` in JavaScript.
+fn extend<V: Clone>(
+	field: &mut Option<IndexMap<String, V>>,
+	extension: Option<&IndexMap<String, V>>,
+) {
+	let Some(extension) = extension else {
+		return;
+	};
+
+	let mut merged = extension.clone();
+	merged.extend(field.take().into_iter().flatten());
+	*field = Some(merged);
+}
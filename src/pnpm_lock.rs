@@ -0,0 +1,705 @@
+use std::fmt::Display;
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use typed_builder::TypedBuilder;
+
+use crate::AdditionalFields;
+use crate::DependencyKind;
+use crate::PackageJson;
+use crate::PeerDependencyMeta;
+use crate::Workspace;
+
+/// The path of the importer for the root of the workspace.
+pub const ROOT_IMPORTER: &str = ".";
+
+/// The `pnpm-lock.yaml` file written by pnpm.
+///
+/// Lockfile versions `5.4` (pnpm 7), `6.0` (pnpm 8) and `9.0` (pnpm 9 and
+/// later) are supported. Unknown fields are kept so that the file can be
+/// written back without losing information. See
+/// <https://github.com/pnpm/spec/tree/master/lockfile>.
+///
+/// ```
+/// use package_json_schema::PnpmLock;
+///
+/// let contents = r"
+/// lockfileVersion: '9.0'
+/// importers:
+///   .:
+///     dependencies:
+///       ms: { specifier: ^2.1.0, version: 2.1.3 }
+/// ";
+///
+/// let pnpm_lock = PnpmLock::try_from(contents).unwrap();
+/// let importers = pnpm_lock.importers();
+/// let entries = importers["."].entries();
+///
+/// assert_eq!(pnpm_lock.lockfile_version.major(), Some(9));
+/// assert_eq!(entries[0].specifier, Some("^2.1.0"));
+/// assert_eq!(entries[0].version, "2.1.3");
+/// ```
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone)]
+pub struct PnpmLock {
+	/// The version of the lockfile format, a number before `6.0`.
+	#[serde(rename = "lockfileVersion")]
+	#[builder(setter(into))]
+	pub lockfile_version: PnpmLockfileVersion,
+
+	/// The settings used when the lockfile was written.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub settings: Option<PnpmLockSettings>,
+
+	/// The resolved catalogs, keyed by catalog name and then package name.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub catalogs: Option<IndexMap<String, IndexMap<String, PnpmCatalogEntry>>>,
+
+	/// The `overrides` applied to the dependency graph.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub overrides: Option<IndexMap<String, String>>,
+
+	/// The patches applied to dependencies, keyed by `name@version`.
+	#[serde(
+		default,
+		rename = "patchedDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub patched_dependencies: Option<IndexMap<String, PnpmPatchedDependency>>,
+
+	/// The specifiers of a project without `importers` in lockfile `5.4`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub specifiers: Option<IndexMap<String, String>>,
+
+	/// The dependencies of a project without `importers`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub dependencies: Option<IndexMap<String, PnpmImporterDependency>>,
+
+	/// The dev dependencies of a project without `importers`.
+	#[serde(
+		default,
+		rename = "devDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub dev_dependencies: Option<IndexMap<String, PnpmImporterDependency>>,
+
+	/// The optional dependencies of a project without `importers`.
+	#[serde(
+		default,
+		rename = "optionalDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub optional_dependencies: Option<IndexMap<String, PnpmImporterDependency>>,
+
+	/// The projects of the workspace keyed by their path relative to the
+	/// root, which is `.`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub importers: Option<IndexMap<String, PnpmImporter>>,
+
+	/// The resolved packages keyed by `/name/version` in lockfile `5.4`,
+	/// `/name@version` in `6.0` and `name@version` in `9.0`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub packages: Option<IndexMap<String, PnpmPackage>>,
+
+	/// The dependencies of every package in the graph, keyed by
+	/// `name@version` with the peer suffix. Only used by lockfile `9.0`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub snapshots: Option<IndexMap<String, PnpmSnapshot>>,
+
+	/// All addition field.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+impl TryFrom<&str> for PnpmLock {
+	type Error = crate::error::Error;
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		let pnpm_lock: Self = serde_yaml::from_str(value).map_err(crate::Error::ParsePnpmLock)?;
+		Ok(pnpm_lock)
+	}
+}
+
+impl TryFrom<String> for PnpmLock {
+	type Error = crate::error::Error;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		Self::try_from(value.as_str())
+	}
+}
+
+impl PnpmLock {
+	/// Convert the [`PnpmLock`] to a YAML [`String`].
+	///
+	/// # Errors
+	///
+	/// This will return an error when the [`PnpmLock`] cannot be serialized.
+	pub fn try_to_string(&self) -> Result<String, crate::error::Error> {
+		serde_yaml::to_string(self).map_err(crate::Error::SerializePnpmLock)
+	}
+
+	/// Every importer keyed by its path. A lockfile without `importers`
+	/// describes a single project, which is returned as the `.` importer.
+	#[must_use]
+	pub fn importers(&self) -> IndexMap<String, PnpmImporter> {
+		if let Some(importers) = &self.importers {
+			return importers.clone();
+		}
+
+		let root = PnpmImporter {
+			specifiers: self.specifiers.clone(),
+			dependencies: self.dependencies.clone(),
+			dev_dependencies: self.dev_dependencies.clone(),
+			optional_dependencies: self.optional_dependencies.clone(),
+			..PnpmImporter::default()
+		};
+
+		IndexMap::from([(ROOT_IMPORTER.to_string(), root)])
+	}
+
+	/// Pair every importer path with the `package.json` of the workspace
+	/// found at that path. The `.` importer is the root `package.json`.
+	#[must_use]
+	pub fn importer_package_jsons<'a>(
+		&self,
+		workspace: &'a Workspace,
+	) -> Vec<(String, Option<&'a PackageJson>)> {
+		self.importers()
+			.into_keys()
+			.map(|path| {
+				let package_json = workspace.package_json_at(&path);
+				(path, package_json)
+			})
+			.collect()
+	}
+
+	/// Find the resolved package with the provided name and version. The
+	/// version may include the peer suffix, e.g. `1.0.0(react@18.2.0)`.
+	#[must_use]
+	pub fn package(&self, name: &str, version: &str) -> Option<(&str, &PnpmPackage)> {
+		let version = strip_peer_suffix(version);
+
+		self.packages.iter().flatten().find_map(|(key, package)| {
+			let found = parse_pnpm_package_key(key)
+				.is_some_and(|(key_name, key_version)| key_name == name && key_version == version);
+
+			found.then_some((key.as_str(), package))
+		})
+	}
+}
+
+/// Split a key of the `packages` or `snapshots` map into the name and
+/// version of the package, for every lockfile version. The peer and patch
+/// suffixes are dropped.
+///
+/// ```
+/// use package_json_schema::parse_pnpm_package_key;
+///
+/// let v5 = parse_pnpm_package_key("/react-dom/18.2.0_react@18.2.0");
+/// let v6 = parse_pnpm_package_key("/@types/node@20.11.0");
+/// let v9 = parse_pnpm_package_key("ms@2.1.3(patch_hash=abc)");
+///
+/// assert_eq!(v5, Some(("react-dom", "18.2.0")));
+/// assert_eq!(v6, Some(("@types/node", "20.11.0")));
+/// assert_eq!(v9, Some(("ms", "2.1.3")));
+/// ```
+#[must_use]
+pub fn parse_pnpm_package_key(key: &str) -> Option<(&str, &str)> {
+	let key = strip_peer_suffix(key.strip_prefix('/').unwrap_or(key));
+	let valid_name = |name: &str| {
+		let slashes = name.matches('/').count();
+		!name.is_empty() && slashes == usize::from(name.starts_with('@'))
+	};
+
+	if let Some(index) = key.get(1..).and_then(|rest| rest.find('@')) {
+		let (name, version) = (&key[..=index], &key[index + 2..]);
+
+		if valid_name(name) && !version.is_empty() {
+			return Some((name, version));
+		}
+	}
+
+	let segments = if key.starts_with('@') { 2 } else { 1 };
+	let index = key
+		.match_indices('/')
+		.nth(segments - 1)
+		.map(|(index, _)| index)?;
+	let name = &key[..index];
+	let version = key[index + 1..].split('_').next().unwrap_or_default();
+
+	(valid_name(name) && !version.is_empty()).then_some((name, version))
+}
+
+/// Remove the `(peer@version)` suffixes of a key or version.
+fn strip_peer_suffix(value: &str) -> &str {
+	value.find('(').map_or(value, |index| &value[..index])
+}
+
+/// The `lockfileVersion` of a [`PnpmLock`], kept in its original form so that
+/// `5.4` isn't written back as `'5.4'`. Whole numbers display with one
+/// decimal, so an unquoted `6.0` stays `6.0`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum PnpmLockfileVersion {
+	Number(f64),
+	String(String),
+}
+
+impl PnpmLockfileVersion {
+	/// The major version of the lockfile format, e.g. `6` for `'6.0'`.
+	#[must_use]
+	pub fn major(&self) -> Option<u32> {
+		self.to_string()
+			.split('.')
+			.next()
+			.and_then(|major| major.parse().ok())
+	}
+}
+
+impl Display for PnpmLockfileVersion {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Number(number) if number.fract() == 0.0 => write!(f, "{number:.1}"),
+			Self::Number(number) => write!(f, "{number}"),
+			Self::String(string) => write!(f, "{string}"),
+		}
+	}
+}
+
+impl From<&str> for PnpmLockfileVersion {
+	fn from(value: &str) -> Self {
+		Self::String(value.to_string())
+	}
+}
+
+/// The `settings` of a [`PnpmLock`].
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PnpmLockSettings {
+	/// Whether missing peer dependencies are installed automatically.
+	#[serde(
+		default,
+		rename = "autoInstallPeers",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub auto_install_peers: Option<bool>,
+
+	/// Whether `link:` dependencies are left out of the lockfile.
+	#[serde(
+		default,
+		rename = "excludeLinksFromLockfile",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub exclude_links_from_lockfile: Option<bool>,
+
+	/// All addition field.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+/// A resolved entry of the `catalogs` of a [`PnpmLock`].
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PnpmCatalogEntry {
+	/// The range from `pnpm-workspace.yaml`.
+	#[builder(setter(into))]
+	pub specifier: String,
+
+	/// The version the range resolved to.
+	#[builder(setter(into))]
+	pub version: String,
+}
+
+/// An entry of the `patchedDependencies` of a [`PnpmLock`]. pnpm 10 only
+/// records the hash of the patch.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum PnpmPatchedDependency {
+	Hash(String),
+	Object { hash: String, path: String },
+}
+
+impl PnpmPatchedDependency {
+	/// The hash of the patch file.
+	#[must_use]
+	pub fn hash(&self) -> &str {
+		match self {
+			Self::Hash(hash) | Self::Object { hash, .. } => hash,
+		}
+	}
+}
+
+/// A project of the workspace in the `importers` of a [`PnpmLock`].
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PnpmImporter {
+	/// The ranges from the `package.json`, only used by lockfile `5.4`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub specifiers: Option<IndexMap<String, String>>,
+
+	/// The resolved `dependencies`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub dependencies: Option<IndexMap<String, PnpmImporterDependency>>,
+
+	/// The resolved `devDependencies`.
+	#[serde(
+		default,
+		rename = "devDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub dev_dependencies: Option<IndexMap<String, PnpmImporterDependency>>,
+
+	/// The resolved `optionalDependencies`.
+	#[serde(
+		default,
+		rename = "optionalDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub optional_dependencies: Option<IndexMap<String, PnpmImporterDependency>>,
+
+	/// The `dependenciesMeta` of the `package.json`.
+	#[serde(
+		default,
+		rename = "dependenciesMeta",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub dependencies_meta: Option<Value>,
+
+	/// The `publishConfig.directory` of the `package.json`.
+	#[serde(
+		default,
+		rename = "publishDirectory",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub publish_directory: Option<String>,
+
+	/// All addition field.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+impl PnpmImporter {
+	/// The resolved dependencies of a field.
+	#[must_use]
+	pub const fn dependencies_of(
+		&self,
+		kind: DependencyKind,
+	) -> Option<&IndexMap<String, PnpmImporterDependency>> {
+		match kind {
+			DependencyKind::Prod => self.dependencies.as_ref(),
+			DependencyKind::Dev => self.dev_dependencies.as_ref(),
+			DependencyKind::Optional => self.optional_dependencies.as_ref(),
+			DependencyKind::Peer => None,
+		}
+	}
+
+	/// Every resolved dependency with its specifier, for every lockfile
+	/// version.
+	#[must_use]
+	pub fn entries(&self) -> Vec<PnpmImporterEntry<'_>> {
+		DependencyKind::ALL
+			.into_iter()
+			.flat_map(|kind| {
+				self.dependencies_of(kind)
+					.into_iter()
+					.flatten()
+					.map(move |(name, dependency)| {
+						let specifier = dependency.specifier().or_else(|| {
+							self.specifiers
+								.as_ref()
+								.and_then(|specifiers| specifiers.get(name))
+								.map(String::as_str)
+						});
+
+						PnpmImporterEntry {
+							kind,
+							name,
+							specifier,
+							version: dependency.version(),
+						}
+					})
+			})
+			.collect()
+	}
+}
+
+/// A resolved dependency of an [`PnpmImporter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PnpmImporterEntry<'a> {
+	/// The field which declares the dependency.
+	pub kind: DependencyKind,
+
+	/// The name of the dependency.
+	pub name: &'a str,
+
+	/// The range from the `package.json`.
+	pub specifier: Option<&'a str>,
+
+	/// The resolved version, which may be a `link:` or include a peer
+	/// suffix.
+	pub version: &'a str,
+}
+
+/// A dependency of a [`PnpmImporter`]. Lockfile `5.4` only records the
+/// version and keeps the ranges in `specifiers`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum PnpmImporterDependency {
+	Version(String),
+	Resolved { specifier: String, version: String },
+}
+
+impl PnpmImporterDependency {
+	/// The resolved version.
+	#[must_use]
+	pub fn version(&self) -> &str {
+		match self {
+			Self::Version(version) | Self::Resolved { version, .. } => version,
+		}
+	}
+
+	/// The range from the `package.json`, when it is recorded here.
+	#[must_use]
+	pub fn specifier(&self) -> Option<&str> {
+		match self {
+			Self::Version(_) => None,
+			Self::Resolved { specifier, .. } => Some(specifier),
+		}
+	}
+}
+
+/// How a [`PnpmPackage`] is fetched.
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PnpmResolution {
+	/// The subresource integrity of the tarball.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub integrity: Option<String>,
+
+	/// The url of a tarball which isn't in the registry.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub tarball: Option<String>,
+
+	/// The directory of a local package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub directory: Option<String>,
+
+	/// The repository of a git dependency.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub repo: Option<String>,
+
+	/// The commit of a git dependency.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub commit: Option<String>,
+
+	/// The kind of resolution, e.g. `directory` or `git`.
+	#[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub type_: Option<String>,
+
+	/// All addition field.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+/// An entry of the `packages` of a [`PnpmLock`].
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PnpmPackage {
+	/// How the package is fetched.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub resolution: Option<PnpmResolution>,
+
+	/// The id of packages which aren't from the registry.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub id: Option<String>,
+
+	/// The name, when it can't be read from the key.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub name: Option<String>,
+
+	/// The version, when it can't be read from the key.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub version: Option<String>,
+
+	/// The `engines` of the package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub engines: Option<IndexMap<String, String>>,
+
+	/// The CPU architectures supported by the package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub cpu: Option<Vec<String>>,
+
+	/// The operating systems supported by the package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub os: Option<Vec<String>>,
+
+	/// The C standard libraries supported by the package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub libc: Option<Vec<String>>,
+
+	/// The deprecation message of the version.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub deprecated: Option<String>,
+
+	/// Whether the package has executables.
+	#[serde(default, rename = "hasBin", skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub has_bin: Option<bool>,
+
+	/// Whether the package has install scripts, before lockfile `9.0`.
+	#[serde(
+		default,
+		rename = "requiresBuild",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub requires_build: Option<bool>,
+
+	/// The dependencies bundled in the tarball.
+	#[serde(
+		default,
+		rename = "bundledDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub bundled_dependencies: Option<Vec<String>>,
+
+	/// The peer dependencies of the package.
+	#[serde(
+		default,
+		rename = "peerDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub peer_dependencies: Option<IndexMap<String, String>>,
+
+	/// Extra information about the peer dependencies.
+	#[serde(
+		default,
+		rename = "peerDependenciesMeta",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub peer_dependencies_meta: Option<IndexMap<String, PeerDependencyMeta>>,
+
+	/// The resolved dependencies, before lockfile `9.0` moved them to
+	/// `snapshots`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub dependencies: Option<IndexMap<String, String>>,
+
+	/// The resolved optional dependencies, before lockfile `9.0`.
+	#[serde(
+		default,
+		rename = "optionalDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub optional_dependencies: Option<IndexMap<String, String>>,
+
+	/// The peers of dependencies which affect this package, before lockfile
+	/// `9.0`.
+	#[serde(
+		default,
+		rename = "transitivePeerDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub transitive_peer_dependencies: Option<Vec<String>>,
+
+	/// Only required by dev dependencies, before lockfile `9.0`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub dev: Option<bool>,
+
+	/// Only required by optional dependencies, before lockfile `9.0`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub optional: Option<bool>,
+
+	/// Whether a patch is applied, before lockfile `9.0`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub patched: Option<bool>,
+
+	/// All addition field.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+/// An entry of the `snapshots` of a [`PnpmLock`].
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PnpmSnapshot {
+	/// The id of the package, when it differs from the key.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub id: Option<String>,
+
+	/// The resolved dependencies.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub dependencies: Option<IndexMap<String, String>>,
+
+	/// The resolved optional dependencies.
+	#[serde(
+		default,
+		rename = "optionalDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub optional_dependencies: Option<IndexMap<String, String>>,
+
+	/// The peers of dependencies which affect this package.
+	#[serde(
+		default,
+		rename = "transitivePeerDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub transitive_peer_dependencies: Option<Vec<String>>,
+
+	/// Only required by optional dependencies.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub optional: Option<bool>,
+
+	/// All addition field.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
@@ -0,0 +1,176 @@
+use serde_json::Value;
+
+use crate::graph::normalize_path;
+use crate::DependencyKind;
+use crate::DependencySpec;
+use crate::PackageJson;
+use crate::Workspace;
+use crate::WorkspaceMember;
+
+/// The `publishConfig` fields which replace the top level field of the same
+/// name when publishing with pnpm or yarn.
+pub const PUBLISH_CONFIG_OVERRIDES: &[&str] = &[
+	"bin",
+	"browser",
+	"cpu",
+	"es2015",
+	"esnext",
+	"exports",
+	"main",
+	"module",
+	"os",
+	"type",
+	"types",
+	"typesVersions",
+	"typings",
+	"umd:main",
+	"unpkg",
+];
+
+impl PackageJson {
+	/// Create the manifest which is uploaded when the package is published
+	/// from the provided workspace.
+	///
+	/// - Fields from [`PUBLISH_CONFIG_OVERRIDES`] are moved out of
+	///   `publishConfig` and replace the top level fields. `publishConfig` is
+	///   removed when nothing else remains.
+	/// - `workspace:` dependencies are replaced with the version of the
+	///   sibling: `workspace:*` becomes `1.2.3`, `workspace:^` becomes
+	///   `^1.2.3`, `workspace:~` becomes `~1.2.3` and `workspace:<range>`
+	///   becomes `<range>`. Relative paths resolve from the member this
+	///   manifest is borrowed from, or else the member with the same name.
+	/// - `catalog:` dependencies are replaced with their range from the
+	///   catalogs of the workspace.
+	/// - The `pnpm` and `packageManager` fields are removed, like `pnpm
+	///   publish` does.
+	///
+	/// ```
+	/// use package_json_schema::PackageJson;
+	/// use package_json_schema::Workspace;
+	///
+	/// let workspace = Workspace::discover("./tests/fixtures/workspaces/graph").unwrap();
+	/// let app = workspace.member("@graph/app").unwrap();
+	/// let manifest = app.package_json.to_publish_manifest(&workspace).unwrap();
+	/// let dependencies = manifest.dependencies.unwrap();
+	///
+	/// assert_eq!(dependencies["@graph/ui"], "^1.0.0");
+	/// ```
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::UnresolvedWorkspaceDependency`] when a
+	/// `workspace:` dependency doesn't match a versioned member and
+	/// [`crate::Error::UnresolvedCatalogDependency`] for `catalog:`
+	/// dependencies which aren't in a catalog of the workspace. Returns
+	/// [`crate::Error::DuplicateDefaultCatalog`] when the catalogs are
+	/// invalid.
+	pub fn to_publish_manifest(&self, workspace: &Workspace) -> crate::Result<Self> {
+		let mut manifest = apply_publish_config(self)?;
+		manifest.pnpm = None;
+		manifest.package_manager = None;
+
+		let catalogs = workspace.catalogs()?;
+
+		// Prefer the member this manifest was borrowed from, since unnamed
+		// members can't be told apart by name.
+		let directory = workspace
+			.members()
+			.iter()
+			.find(|member| std::ptr::eq(&member.package_json, self))
+			.or_else(|| workspace.member(self.name.as_deref()?))
+			.map(|member| member.path.as_path());
+
+		for kind in DependencyKind::ALL {
+			let Some(dependencies) = manifest.dependencies_of_mut(kind) else {
+				continue;
+			};
+
+			for (name, spec) in dependencies.iter_mut() {
+				let unresolved_workspace = || {
+					crate::Error::UnresolvedWorkspaceDependency {
+						name: name.clone(),
+						spec: spec.clone(),
+					}
+				};
+
+				match DependencySpec::parse(spec) {
+					DependencySpec::Workspace { name: alias, range } => {
+						let target = if range.starts_with('.') {
+							directory.and_then(|directory| {
+								let path = normalize_path(&directory.join(range));
+								workspace
+									.members()
+									.iter()
+									.find(|member| normalize_path(&member.path) == path)
+							})
+						} else {
+							workspace.member(alias.unwrap_or(name))
+						};
+						let version = target
+							.and_then(member_version)
+							.ok_or_else(unresolved_workspace)?;
+						let range = match range {
+							"*" => version.to_string(),
+							"^" | "~" => format!("{range}{version}"),
+							_ if range.starts_with('.') => version.to_string(),
+							_ => range.to_string(),
+						};
+
+						*spec = match alias {
+							Some(alias) if alias != name => format!("npm:{alias}@{range}"),
+							_ => range,
+						};
+					}
+					DependencySpec::Catalog(_) => {
+						let Ok(Some(range)) = catalogs.lookup(name, spec) else {
+							return Err(crate::Error::UnresolvedCatalogDependency {
+								name: name.clone(),
+								spec: spec.clone(),
+							});
+						};
+
+						*spec = range.to_string();
+					}
+					_ => {}
+				}
+			}
+		}
+
+		Ok(manifest)
+	}
+}
+
+fn member_version(member: &WorkspaceMember) -> Option<&str> {
+	member.package_json.version.as_deref()
+}
+
+/// Move the overriding fields of `publishConfig` to the top level.
+fn apply_publish_config(package_json: &PackageJson) -> crate::Result<PackageJson> {
+	let Some(publish_config) = &package_json.publish_config else {
+		return Ok(package_json.clone());
+	};
+
+	let mut manifest =
+		serde_json::to_value(package_json).map_err(crate::Error::SerializePackageJson)?;
+	let mut overrides =
+		serde_json::to_value(publish_config).map_err(crate::Error::SerializePackageJson)?;
+
+	if let (Value::Object(manifest), Value::Object(overrides)) = (&mut manifest, &mut overrides) {
+		for key in PUBLISH_CONFIG_OVERRIDES {
+			if let Some(value) = overrides.shift_remove(*key) {
+				manifest.insert((*key).to_string(), value);
+			}
+		}
+
+		if overrides.is_empty() {
+			manifest.shift_remove("publishConfig");
+		} else {
+			manifest.insert(
+				"publishConfig".to_string(),
+				Value::Object(overrides.clone()),
+			);
+		}
+	}
+
+	serde_json::from_value(manifest).map_err(crate::Error::ParsePackageJson)
+}
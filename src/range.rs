@@ -0,0 +1,472 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use semver::Prerelease;
+use semver::Version;
+
+/// A version range with the semantics of [node-semver](https://github.com/npm/node-semver).
+///
+/// The `semver` crate implements cargo's flavour of requirements which differs
+/// from npm in several ways: space separated comparators, `||` unions, hyphen
+/// ranges, x-ranges and the handling of prerelease versions. This type follows
+/// npm so that ranges in a `package.json` are interpreted the way package
+/// managers interpret them.
+///
+/// ```
+/// use package_json_schema::VersionRange;
+/// use semver::Version;
+///
+/// let range = VersionRange::parse("^1.2.3 || 2.x").unwrap();
+///
+/// assert!(range.satisfies(&Version::parse("1.9.0").unwrap()));
+/// assert!(range.satisfies(&Version::parse("2.4.1").unwrap()));
+/// assert!(!range.satisfies(&Version::parse("3.0.0").unwrap()));
+/// assert!(!range.satisfies(&Version::parse("2.0.0-beta.1").unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRange {
+	raw: String,
+	sets: Vec<Vec<Comparator>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+	Less,
+	LessEqual,
+	Greater,
+	GreaterEqual,
+	Equal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Comparator {
+	operator: Operator,
+	version: Version,
+}
+
+impl Comparator {
+	const fn new(operator: Operator, version: Version) -> Self {
+		Self { operator, version }
+	}
+
+	fn test(&self, version: &Version) -> bool {
+		let ordering = version.cmp_precedence(&self.version);
+
+		match self.operator {
+			Operator::Less => ordering == Ordering::Less,
+			Operator::LessEqual => ordering != Ordering::Greater,
+			Operator::Greater => ordering == Ordering::Greater,
+			Operator::GreaterEqual => ordering != Ordering::Less,
+			Operator::Equal => ordering == Ordering::Equal,
+		}
+	}
+}
+
+/// A version where trailing parts may be missing or wildcards, e.g. `1`,
+/// `1.2`, `1.x` or `*`.
+struct Partial {
+	major: Option<u64>,
+	minor: Option<u64>,
+	patch: Option<u64>,
+	pre: Prerelease,
+}
+
+impl Partial {
+	fn parse(value: &str) -> Result<Self, &'static str> {
+		let value = value.trim_start_matches('=').trim_start_matches('v');
+		let value = value.split_once('+').map_or(value, |(version, _)| version);
+		let (numbers, pre) = match value.split_once('-') {
+			Some((numbers, pre)) => {
+				(
+					numbers,
+					Prerelease::new(pre).map_err(|_| "the prerelease is invalid")?,
+				)
+			}
+			None => (value, Prerelease::EMPTY),
+		};
+
+		let mut parts = numbers.split('.');
+		let mut next = || -> Result<Option<u64>, &'static str> {
+			match parts.next() {
+				None | Some("x" | "X" | "*") => Ok(None),
+				Some(part) => part.parse().map(Some).map_err(|_| "the version is invalid"),
+			}
+		};
+
+		let major = next()?;
+		let minor = next()?.filter(|_| major.is_some());
+		let patch = next()?.filter(|_| minor.is_some());
+
+		if parts.next().is_some() {
+			return Err("the version has too many parts");
+		}
+
+		if !pre.is_empty() && patch.is_none() {
+			return Err("a prerelease requires a full version");
+		}
+
+		Ok(Self {
+			major,
+			minor,
+			patch,
+			pre,
+		})
+	}
+
+	fn version(&self) -> Version {
+		let mut version = Version::new(
+			self.major.unwrap_or(0),
+			self.minor.unwrap_or(0),
+			self.patch.unwrap_or(0),
+		);
+		version.pre = self.pre.clone();
+		version
+	}
+
+	/// The lowest version which is greater than every version matched by this
+	/// partial, as a `-0` prerelease so that it excludes prereleases too.
+	fn upper_bound(&self) -> Option<Version> {
+		match (self.major, self.minor, self.patch) {
+			(Some(major), None, _) => Some(lowest(major + 1, 0, 0)),
+			(Some(major), Some(minor), None) => Some(lowest(major, minor + 1, 0)),
+			_ => None,
+		}
+	}
+}
+
+/// The lowest possible version with the provided numbers, `x.y.z-0`.
+fn lowest(major: u64, minor: u64, patch: u64) -> Version {
+	let mut version = Version::new(major, minor, patch);
+	version.pre = Prerelease::new("0").unwrap();
+	version
+}
+
+impl VersionRange {
+	/// Parse a node-semver range.
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::InvalidVersionRange`] when the range isn't
+	/// valid.
+	pub fn parse(range: &str) -> crate::Result<Self> {
+		let invalid = |reason: &str| {
+			crate::Error::InvalidVersionRange {
+				range: range.to_string(),
+				reason: reason.to_string(),
+			}
+		};
+
+		let sets = range
+			.split("||")
+			.map(|set| parse_comparator_set(set.trim()).map_err(invalid))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		Ok(Self {
+			raw: range.to_string(),
+			sets,
+		})
+	}
+
+	/// The range as it was written.
+	#[must_use]
+	pub fn as_str(&self) -> &str {
+		&self.raw
+	}
+
+	/// Check whether the version satisfies the range. Prerelease versions only
+	/// match when a comparator in the same set refers to a prerelease of the
+	/// same `major.minor.patch`.
+	#[must_use]
+	pub fn satisfies(&self, version: &Version) -> bool {
+		self.test(version, false)
+	}
+
+	/// Check whether the version satisfies the range, treating prerelease
+	/// versions like any other version. This is node-semver's
+	/// `includePrerelease` option.
+	#[must_use]
+	pub fn satisfies_including_prerelease(&self, version: &Version) -> bool {
+		self.test(version, true)
+	}
+
+	/// The highest version which satisfies the range.
+	pub fn max_satisfying<'a, I>(&self, versions: I) -> Option<&'a Version>
+	where
+		I: IntoIterator<Item = &'a Version>,
+	{
+		versions
+			.into_iter()
+			.filter(|version| self.satisfies(version))
+			.max_by(|a, b| a.cmp_precedence(b))
+	}
+
+	/// The lowest version which satisfies the range.
+	pub fn min_satisfying<'a, I>(&self, versions: I) -> Option<&'a Version>
+	where
+		I: IntoIterator<Item = &'a Version>,
+	{
+		versions
+			.into_iter()
+			.filter(|version| self.satisfies(version))
+			.min_by(|a, b| a.cmp_precedence(b))
+	}
+
+	/// Check whether some version satisfies both ranges, like node-semver's
+	/// `intersects`.
+	///
+	/// ```
+	/// use package_json_schema::VersionRange;
+	///
+	/// let range = VersionRange::parse("^1.2.0").unwrap();
+	///
+	/// assert!(range.intersects(&VersionRange::parse("~1.4.1 || 3.x").unwrap()));
+	/// assert!(!range.intersects(&VersionRange::parse(">=2.0.0").unwrap()));
+	/// ```
+	#[must_use]
+	pub fn intersects(&self, other: &Self) -> bool {
+		self.sets.iter().any(|set| {
+			other
+				.sets
+				.iter()
+				.any(|other| is_satisfiable(set.iter().chain(other)))
+		})
+	}
+
+	/// Check whether some version satisfies every range.
+	pub(crate) fn all_intersect(ranges: &[Self]) -> bool {
+		let mut sets: Vec<Vec<&Comparator>> = vec![Vec::new()];
+
+		for range in ranges {
+			sets = sets
+				.iter()
+				.flat_map(|set| {
+					range
+						.sets
+						.iter()
+						.map(move |other| set.iter().copied().chain(other).collect::<Vec<_>>())
+				})
+				.filter(|set| is_satisfiable(set.iter().copied()))
+				.collect();
+		}
+
+		!sets.is_empty()
+	}
+
+	fn test(&self, version: &Version, include_prerelease: bool) -> bool {
+		self.sets.iter().any(|set| {
+			set.iter().all(|comparator| comparator.test(version))
+				&& (include_prerelease
+					|| version.pre.is_empty()
+					|| set.iter().any(|comparator| {
+						!comparator.version.pre.is_empty()
+							&& comparator.version.major == version.major
+							&& comparator.version.minor == version.minor
+							&& comparator.version.patch == version.patch
+					}))
+		})
+	}
+}
+
+impl FromStr for VersionRange {
+	type Err = crate::Error;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		Self::parse(value)
+	}
+}
+
+impl Display for VersionRange {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.raw)
+	}
+}
+
+/// Check whether a version satisfies every comparator by comparing the
+/// tightest lower bound with the tightest upper bound.
+fn is_satisfiable<'a>(comparators: impl IntoIterator<Item = &'a Comparator>) -> bool {
+	let mut lower: Option<(&Version, bool)> = None;
+	let mut upper: Option<(&Version, bool)> = None;
+	let tighter = |bound: Option<(&Version, bool)>, version: &Version, inclusive: bool, wanted| {
+		bound.map_or(true, |(current, current_inclusive): (&Version, bool)| {
+			let ordering = version.cmp_precedence(current);
+			ordering == wanted || (ordering == Ordering::Equal && current_inclusive && !inclusive)
+		})
+	};
+
+	for comparator in comparators {
+		let version = &comparator.version;
+		let (is_lower, is_upper, inclusive) = match comparator.operator {
+			Operator::Greater => (true, false, false),
+			Operator::GreaterEqual => (true, false, true),
+			Operator::Less => (false, true, false),
+			Operator::LessEqual => (false, true, true),
+			Operator::Equal => (true, true, true),
+		};
+
+		if is_lower && tighter(lower, version, inclusive, Ordering::Greater) {
+			lower = Some((version, inclusive));
+		}
+
+		if is_upper && tighter(upper, version, inclusive, Ordering::Less) {
+			upper = Some((version, inclusive));
+		}
+	}
+
+	match (lower, upper) {
+		(_, Some((upper, false))) if (upper.major, upper.minor, upper.patch) == (0, 0, 0) => false,
+		(Some((lower, lower_inclusive)), Some((upper, upper_inclusive))) => {
+			match lower.cmp_precedence(upper) {
+				Ordering::Less => true,
+				Ordering::Equal => lower_inclusive && upper_inclusive,
+				Ordering::Greater => false,
+			}
+		}
+		_ => true,
+	}
+}
+
+/// Parse a version the way node-semver does, ignoring surrounding whitespace
+/// and a leading `=` or `v`.
+///
+/// ```
+/// use package_json_schema::parse_version;
+///
+/// assert_eq!(parse_version("v1.2.3").unwrap().to_string(), "1.2.3");
+/// assert!(parse_version("^1.2.3").is_none());
+/// ```
+#[must_use]
+pub fn parse_version(version: &str) -> Option<Version> {
+	let version = version
+		.trim()
+		.trim_start_matches('=')
+		.trim_start_matches('v');
+	Version::parse(version).ok()
+}
+
+fn parse_comparator_set(set: &str) -> Result<Vec<Comparator>, &'static str> {
+	let tokens: Vec<&str> = set.split_whitespace().collect();
+
+	if let [from, "-", to] = tokens.as_slice() {
+		return parse_hyphen_range(from, to);
+	}
+
+	let mut comparators = Vec::new();
+	let mut index = 0;
+
+	while index < tokens.len() {
+		let mut token = tokens[index].to_string();
+
+		// Operators may be separated from their version by whitespace.
+		if token.chars().all(|char| "<>=~^".contains(char)) {
+			index += 1;
+			token.push_str(
+				tokens
+					.get(index)
+					.ok_or("an operator is missing its version")?,
+			);
+		}
+
+		comparators.extend(parse_comparator(&token)?);
+		index += 1;
+	}
+
+	Ok(comparators)
+}
+
+fn parse_hyphen_range(from: &str, to: &str) -> Result<Vec<Comparator>, &'static str> {
+	let from = Partial::parse(from)?;
+	let to = Partial::parse(to)?;
+	let mut comparators = Vec::new();
+
+	if from.major.is_some() {
+		comparators.push(Comparator::new(Operator::GreaterEqual, from.version()));
+	}
+
+	if to.major.is_some() {
+		comparators.push(to.upper_bound().map_or_else(
+			|| Comparator::new(Operator::LessEqual, to.version()),
+			|bound| Comparator::new(Operator::Less, bound),
+		));
+	}
+
+	Ok(comparators)
+}
+
+fn parse_comparator(token: &str) -> Result<Vec<Comparator>, &'static str> {
+	let (operator, version) = ["<=", ">=", "~>", "<", ">", "=", "~", "^"]
+		.iter()
+		.find_map(|operator| token.strip_prefix(operator).map(|rest| (*operator, rest)))
+		.unwrap_or(("", token));
+	let partial = Partial::parse(version)?;
+
+	let Some(major) = partial.major else {
+		return Ok(match operator {
+			"<" | ">" => vec![Comparator::new(Operator::Less, lowest(0, 0, 0))],
+			_ => vec![],
+		});
+	};
+
+	let lower = Comparator::new(Operator::GreaterEqual, partial.version());
+	let upper_bound = partial.upper_bound();
+
+	let comparators = match operator {
+		"" | "=" => {
+			upper_bound.map_or_else(
+				|| vec![Comparator::new(Operator::Equal, partial.version())],
+				|bound| vec![lower, Comparator::new(Operator::Less, bound)],
+			)
+		}
+		">" => {
+			match (partial.minor, partial.patch) {
+				(None, _) => {
+					vec![Comparator::new(
+						Operator::GreaterEqual,
+						Version::new(major + 1, 0, 0),
+					)]
+				}
+				(Some(minor), None) => {
+					vec![Comparator::new(
+						Operator::GreaterEqual,
+						Version::new(major, minor + 1, 0),
+					)]
+				}
+				(Some(_), Some(_)) => vec![Comparator::new(Operator::Greater, partial.version())],
+			}
+		}
+		">=" => vec![lower],
+		"<" => {
+			let bound = if partial.patch.is_some() {
+				partial.version()
+			} else {
+				lowest(major, partial.minor.unwrap_or(0), 0)
+			};
+			vec![Comparator::new(Operator::Less, bound)]
+		}
+		"<=" => {
+			let comparator = upper_bound.map_or_else(
+				|| Comparator::new(Operator::LessEqual, partial.version()),
+				|bound| Comparator::new(Operator::Less, bound),
+			);
+			vec![comparator]
+		}
+		"~" | "~>" => {
+			let bound = partial.minor.map_or_else(
+				|| lowest(major + 1, 0, 0),
+				|minor| lowest(major, minor + 1, 0),
+			);
+			vec![lower, Comparator::new(Operator::Less, bound)]
+		}
+		"^" => {
+			let bound = match (major, partial.minor, partial.patch) {
+				(0, Some(0), Some(patch)) => lowest(0, 0, patch + 1),
+				(0, Some(minor), _) => lowest(0, minor + 1, 0),
+				_ => lowest(major + 1, 0, 0),
+			};
+			vec![lower, Comparator::new(Operator::Less, bound)]
+		}
+		_ => unreachable!("all operators are handled"),
+	};
+
+	Ok(comparators)
+}
@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::sync::Mutex;
+use std::thread::sleep;
+use std::time::Duration;
+
+use typed_builder::TypedBuilder;
+
+use crate::AbbreviatedPackument;
+use crate::PackageJson;
+use crate::Packument;
+use crate::ABBREVIATED_PACKUMENT_ACCEPT;
+
+/// The url of the public npm registry.
+pub const DEFAULT_REGISTRY: &str = "https://registry.npmjs.org";
+
+/// A response body stored with the `ETag` the registry sent for it.
+#[derive(Debug, Clone)]
+struct CachedResponse {
+	etag: String,
+	body: Vec<u8>,
+}
+
+/// A blocking client for the npm registry, available with the `registry`
+/// feature.
+///
+/// Responses carrying an `ETag` are cached in memory and revalidated with
+/// `If-None-Match`. Failed connections and `429` or `5xx` responses are
+/// retried with an increasing delay.
+///
+/// ```no_run
+/// use package_json_schema::RegistryClient;
+///
+/// let client = RegistryClient::builder().token("npm_secret").build();
+/// let packument = client.abbreviated_packument("@types/node").unwrap();
+/// let latest = packument.dist_tag("latest").unwrap();
+/// let tarball = client.tarball(latest).unwrap();
+/// ```
+#[derive(TypedBuilder, Debug)]
+pub struct RegistryClient {
+	/// The url of the registry.
+	#[builder(default = DEFAULT_REGISTRY.to_string(), setter(into))]
+	registry: String,
+
+	/// The token sent as a bearer token to the registry.
+	#[builder(default, setter(into, strip_option))]
+	token: Option<String>,
+
+	/// How many times a request is retried after a connection failure, a
+	/// `429` or a `5xx` status.
+	#[builder(default = 2)]
+	retries: u32,
+
+	/// The delay before the first retry, which doubles for every retry.
+	#[builder(default = Duration::from_millis(250))]
+	retry_delay: Duration,
+
+	/// The agent which sends the requests.
+	#[builder(default = ureq::AgentBuilder::new().timeout(Duration::from_secs(30)).build())]
+	agent: ureq::Agent,
+
+	#[builder(default, setter(skip))]
+	cache: Mutex<HashMap<(String, &'static str), CachedResponse>>,
+}
+
+impl RegistryClient {
+	/// The url of the packument of a package. The `/` of a scoped name is
+	/// encoded, e.g. `@scope%2fname`.
+	#[must_use]
+	pub fn package_url(&self, name: &str) -> String {
+		format!(
+			"{}/{}",
+			self.registry.trim_end_matches('/'),
+			encode_package_name(name)
+		)
+	}
+
+	/// Fetch the full packument of a package.
+	///
+	/// # Errors
+	///
+	/// Returns an error when the request fails or the response isn't a
+	/// packument.
+	pub fn packument(&self, name: &str) -> crate::Result<Packument> {
+		let body = self.fetch(&self.package_url(name), "application/json")?;
+		serde_json::from_slice(&body).map_err(crate::Error::ParsePackument)
+	}
+
+	/// Fetch the abbreviated packument which only contains the fields needed
+	/// to install a package.
+	///
+	/// # Errors
+	///
+	/// Returns an error when the request fails or the response isn't a
+	/// packument.
+	pub fn abbreviated_packument(&self, name: &str) -> crate::Result<AbbreviatedPackument> {
+		let body = self.fetch(&self.package_url(name), ABBREVIATED_PACKUMENT_ACCEPT)?;
+		serde_json::from_slice(&body).map_err(crate::Error::ParsePackument)
+	}
+
+	/// Download the tarball of a version from its `dist.tarball`. The
+	/// integrity of the tarball isn't checked.
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::MissingTarball`] when the manifest has no
+	/// tarball url, or an error when the request fails.
+	pub fn tarball(&self, manifest: &PackageJson) -> crate::Result<Vec<u8>> {
+		let url = manifest
+			.dist
+			.as_ref()
+			.and_then(|dist| dist.tarball.as_deref())
+			.ok_or_else(|| {
+				crate::Error::MissingTarball {
+					name: manifest.name.clone().unwrap_or_default(),
+					version: manifest.version.clone().unwrap_or_default(),
+				}
+			})?;
+
+		self.fetch(url, "application/octet-stream")
+	}
+
+	/// Send a `GET` request, retrying failures and revalidating cached
+	/// responses.
+	fn fetch(&self, url: &str, accept: &'static str) -> crate::Result<Vec<u8>> {
+		let key = (url.to_string(), accept);
+		let cached = self
+			.cache
+			.lock()
+			.ok()
+			.and_then(|cache| cache.get(&key).cloned());
+		let mut attempt = 0;
+
+		loop {
+			let mut request = self.agent.get(url).set("Accept", accept);
+
+			if let Some(token) = self.token.as_deref().filter(|_| self.is_registry_url(url)) {
+				request = request.set("Authorization", &format!("Bearer {token}"));
+			}
+
+			if let Some(cached) = &cached {
+				request = request.set("If-None-Match", &cached.etag);
+			}
+
+			let (error, retryable) = match request.call() {
+				Ok(response) if response.status() == 304 => {
+					if let Some(cached) = cached {
+						return Ok(cached.body);
+					}
+
+					let error = crate::Error::RegistryStatus {
+						url: url.to_string(),
+						status: 304,
+					};
+
+					(error, false)
+				}
+				Ok(response) => return self.read(key, response),
+				Err(ureq::Error::Status(status, _)) => {
+					let error = crate::Error::RegistryStatus {
+						url: url.to_string(),
+						status,
+					};
+
+					(error, status == 429 || status >= 500)
+				}
+				Err(ureq::Error::Transport(transport)) => {
+					// Invalid urls, unsupported schemes and TLS or DNS failures
+					// won't succeed on a retry.
+					let retryable = matches!(
+						transport.kind(),
+						ureq::ErrorKind::ConnectionFailed | ureq::ErrorKind::Io
+					);
+					let error = crate::Error::RegistryRequest {
+						url: url.to_string(),
+						source: Box::new(transport),
+					};
+
+					(error, retryable)
+				}
+			};
+
+			if !retryable || attempt >= self.retries {
+				return Err(error);
+			}
+
+			sleep(self.retry_delay * 2_u32.saturating_pow(attempt));
+			attempt += 1;
+		}
+	}
+
+	/// Read the body of a response and cache it when it has an `ETag`.
+	fn read(
+		&self,
+		key: (String, &'static str),
+		response: ureq::Response,
+	) -> crate::Result<Vec<u8>> {
+		let etag = response.header("ETag").map(ToString::to_string);
+		let mut body = Vec::new();
+
+		response
+			.into_reader()
+			.read_to_end(&mut body)
+			.map_err(|source| {
+				crate::Error::RegistryResponse {
+					url: key.0.clone(),
+					source,
+				}
+			})?;
+
+		if let (Some(etag), Ok(mut cache)) = (etag, self.cache.lock()) {
+			cache.insert(
+				key,
+				CachedResponse {
+					etag,
+					body: body.clone(),
+				},
+			);
+		}
+
+		Ok(body)
+	}
+
+	/// Whether the url belongs to the registry, so it receives the token. The
+	/// scheme, host and port must match and the path must be inside the path
+	/// of the registry.
+	fn is_registry_url(&self, url: &str) -> bool {
+		let (Some(registry), Some(url)) = (split_url(&self.registry), split_url(url)) else {
+			return false;
+		};
+		let prefix = registry.path.trim_end_matches('/');
+
+		registry.origin == url.origin
+			&& url
+				.path
+				.strip_prefix(prefix)
+				.is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+	}
+}
+
+/// The parts of a url which decide whether it belongs to the registry.
+struct UrlParts<'a> {
+	/// The lowercase scheme and host with the port, which defaults to the
+	/// port of the scheme.
+	origin: (String, String, Option<u16>),
+
+	/// The path without the query or the fragment.
+	path: &'a str,
+}
+
+/// Split a url, returning `None` when it has no scheme or host.
+fn split_url(url: &str) -> Option<UrlParts<'_>> {
+	let (scheme, rest) = url.split_once("://")?;
+	let scheme = scheme.to_ascii_lowercase();
+	let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+	let (authority, path) = rest.split_at(end);
+	let path = &path[..path.find(['?', '#']).unwrap_or(path.len())];
+	let host_port = authority
+		.rsplit_once('@')
+		.map_or(authority, |(_, host)| host);
+	let (host, port) = match host_port.rfind(':') {
+		Some(index) if !host_port[index..].contains(']') => {
+			(
+				&host_port[..index],
+				Some(host_port[index + 1..].parse().ok()?),
+			)
+		}
+		_ => (host_port, None),
+	};
+	let port = port.or(match scheme.as_str() {
+		"http" => Some(80),
+		"https" => Some(443),
+		_ => None,
+	});
+
+	if host.is_empty() {
+		return None;
+	}
+
+	Some(UrlParts {
+		origin: (scheme, host.to_ascii_lowercase(), port),
+		path,
+	})
+}
+
+/// Encode a package name for a registry url, which escapes the `/` of
+/// scoped names.
+///
+/// ```
+/// use package_json_schema::encode_package_name;
+///
+/// assert_eq!(encode_package_name("@scope/name"), "@scope%2fname");
+/// assert_eq!(encode_package_name("name"), "name");
+/// ```
+#[must_use]
+pub fn encode_package_name(name: &str) -> String {
+	name.replace('/', "%2f")
+}
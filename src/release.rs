@@ -0,0 +1,413 @@
+use std::fmt::Display;
+
+use indexmap::IndexMap;
+use semver::Version;
+
+use crate::increment_version;
+use crate::parse_version;
+use crate::DependencyKind;
+use crate::DependencySpec;
+use crate::PackageJson;
+use crate::ReleaseType;
+use crate::VersionRange;
+use crate::Workspace;
+
+/// Why a member is released by a [`ReleasePlan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReleaseReason {
+	/// The release was requested.
+	Intent,
+
+	/// The new version of a dependency no longer satisfies the range declared
+	/// by the member.
+	Dependency { name: String },
+}
+
+/// A new version for a workspace member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedRelease {
+	/// The index of the member in [`Workspace::members`].
+	pub member: usize,
+
+	/// The name of the member.
+	pub name: String,
+
+	/// The type of the release.
+	pub release: ReleaseType,
+
+	/// The current version.
+	pub old_version: Version,
+
+	/// The version after the release.
+	pub new_version: Version,
+
+	/// Why the member is released.
+	pub reason: ReleaseReason,
+}
+
+/// A dependency range of a member which is updated, for example to include
+/// the new version of a sibling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeUpdate {
+	/// The index of the member in [`Workspace::members`].
+	pub member: usize,
+
+	/// The name of the member.
+	pub dependent: String,
+
+	/// The field which declares the dependency.
+	pub kind: DependencyKind,
+
+	/// The name of the dependency.
+	pub name: String,
+
+	/// The current spec.
+	pub from: String,
+
+	/// The updated spec.
+	pub to: String,
+}
+
+impl RangeUpdate {
+	/// Write the updated spec into the `package.json` of the member. Nothing
+	/// changes when the dependency no longer exists.
+	pub fn apply(&self, package_json: &mut PackageJson) {
+		let spec = package_json
+			.dependencies_of_mut(self.kind)
+			.as_mut()
+			.and_then(|dependencies| dependencies.get_mut(&self.name));
+
+		if let Some(spec) = spec {
+			spec.clone_from(&self.to);
+		}
+	}
+}
+
+/// The versions and ranges which change when releasing a workspace. Created by
+/// [`Workspace::plan_release`].
+///
+/// ```
+/// use package_json_schema::ReleaseType;
+/// use package_json_schema::Workspace;
+///
+/// let workspace = Workspace::discover("./tests/fixtures/workspaces/graph").unwrap();
+/// let intents = [("@graph/core", ReleaseType::Major)];
+/// let plan = workspace.plan_release(&intents).unwrap();
+/// let core = plan.release("@graph/core").unwrap();
+///
+/// assert_eq!(core.new_version.to_string(), "2.0.0");
+/// assert!(plan.release("@graph/ui").is_some());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReleasePlan {
+	/// The released members ordered by their relative path.
+	pub releases: Vec<PlannedRelease>,
+
+	/// The ranges which need to change, ordered by member.
+	pub range_updates: Vec<RangeUpdate>,
+}
+
+impl ReleasePlan {
+	/// The planned release of the named member.
+	#[must_use]
+	pub fn release(&self, name: &str) -> Option<&PlannedRelease> {
+		self.releases.iter().find(|release| release.name == name)
+	}
+
+	/// Write the new version and ranges of a member into its `package.json`.
+	pub fn apply(&self, member: usize, package_json: &mut PackageJson) {
+		if let Some(release) = self
+			.releases
+			.iter()
+			.find(|release| release.member == member)
+		{
+			package_json.version = Some(release.new_version.to_string());
+		}
+
+		for update in self
+			.range_updates
+			.iter()
+			.filter(|update| update.member == member)
+		{
+			update.apply(package_json);
+		}
+	}
+
+	/// The members with their updated `package.json`, ready to be written.
+	#[must_use]
+	pub fn apply_to_workspace(&self, workspace: &Workspace) -> Vec<(usize, PackageJson)> {
+		let mut members: Vec<usize> = self
+			.releases
+			.iter()
+			.map(|release| release.member)
+			.chain(self.range_updates.iter().map(|update| update.member))
+			.collect();
+		members.sort_unstable();
+		members.dedup();
+
+		members
+			.into_iter()
+			.map(|member| {
+				let mut package_json = workspace.members()[member].package_json.clone();
+				self.apply(member, &mut package_json);
+				(member, package_json)
+			})
+			.collect()
+	}
+}
+
+impl Display for ReleasePlan {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for release in &self.releases {
+			write!(
+				f,
+				"{}: {} -> {} ({}",
+				release.name, release.old_version, release.new_version, release.release
+			)?;
+
+			match &release.reason {
+				ReleaseReason::Intent => writeln!(f, ")")?,
+				ReleaseReason::Dependency { name } => writeln!(f, ", dependency {name})")?,
+			}
+		}
+
+		for update in &self.range_updates {
+			writeln!(
+				f,
+				"{}: {}.{}: {} -> {}",
+				update.dependent, update.kind, update.name, update.from, update.to
+			)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Workspace {
+	/// Plan the release of the members from the requested release types,
+	/// changesets style.
+	///
+	/// Several intents for the same member are merged into the largest one.
+	/// Whenever the new version of a member falls outside the range declared
+	/// by a dependent, the range is updated and the dependent is released
+	/// too: with a patch for `dependencies` and `optionalDependencies`, with
+	/// a major for `peerDependencies` and not at all for `devDependencies`.
+	/// `workspace:*` counts as the exact current version and `workspace:^` /
+	/// `workspace:~` as the matching range of the current version. Members
+	/// without a version are never released through a dependency.
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::UnknownWorkspaceMember`] when an intent names a
+	/// package which isn't a member and [`crate::Error::MissingVersion`] or
+	/// [`crate::Error::InvalidVersion`] when a released member has no valid
+	/// version.
+	pub fn plan_release(&self, intents: &[(&str, ReleaseType)]) -> crate::Result<ReleasePlan> {
+		let members = self.members();
+		let graph = self.graph();
+		let mut planned: IndexMap<usize, (ReleaseType, ReleaseReason)> = IndexMap::new();
+
+		for (name, release) in intents {
+			let index = members
+				.iter()
+				.position(|member| member.name() == Some(*name))
+				.ok_or_else(|| crate::Error::UnknownWorkspaceMember((*name).to_string()))?;
+
+			merge(&mut planned, index, *release, ReleaseReason::Intent);
+		}
+
+		let versions: Vec<Option<Version>> = members
+			.iter()
+			.map(|member| {
+				member
+					.package_json
+					.version
+					.as_deref()
+					.and_then(parse_version)
+			})
+			.collect();
+
+		let mut new_versions: IndexMap<usize, Version> = IndexMap::new();
+
+		loop {
+			new_versions.clear();
+
+			for (index, (release, _)) in &planned {
+				let version = current_version(members[*index].package_json.version.as_deref())?;
+				new_versions.insert(*index, increment_version(&version, *release, None)?);
+			}
+
+			let mut changed = false;
+
+			for edge in graph.edges() {
+				let (Some(new_version), Some(old_version)) =
+					(new_versions.get(&edge.to), &versions[edge.to])
+				else {
+					continue;
+				};
+
+				if satisfies(&edge.spec, old_version, new_version) || versions[edge.from].is_none()
+				{
+					continue;
+				}
+
+				let release = match edge.kind {
+					DependencyKind::Prod | DependencyKind::Optional => ReleaseType::Patch,
+					DependencyKind::Peer => ReleaseType::Major,
+					DependencyKind::Dev => continue,
+				};
+				let reason = ReleaseReason::Dependency {
+					name: members[edge.to].name().unwrap_or_default().to_string(),
+				};
+
+				changed |= merge(&mut planned, edge.from, release, reason);
+			}
+
+			if !changed {
+				break;
+			}
+		}
+
+		let mut releases: Vec<PlannedRelease> = planned
+			.into_iter()
+			.map(|(index, (release, reason))| {
+				PlannedRelease {
+					member: index,
+					name: members[index].name().unwrap_or_default().to_string(),
+					release,
+					old_version: versions[index]
+						.clone()
+						.unwrap_or_else(|| Version::new(0, 0, 0)),
+					new_version: new_versions[&index].clone(),
+					reason,
+				}
+			})
+			.collect();
+		releases.sort_by_key(|release| release.member);
+
+		let mut range_updates: Vec<RangeUpdate> = graph
+			.edges()
+			.iter()
+			.filter_map(|edge| {
+				let new_version = new_versions.get(&edge.to)?;
+				let old_version = versions[edge.to].as_ref()?;
+
+				if satisfies(&edge.spec, old_version, new_version) {
+					return None;
+				}
+
+				let to = updated_spec(&edge.spec, new_version)?;
+
+				Some(RangeUpdate {
+					member: edge.from,
+					dependent: members[edge.from].name().unwrap_or_default().to_string(),
+					kind: edge.kind,
+					name: edge.name.clone(),
+					from: edge.spec.clone(),
+					to,
+				})
+			})
+			.collect();
+		range_updates.sort_by_key(|update| (update.member, update.kind));
+
+		Ok(ReleasePlan {
+			releases,
+			range_updates,
+		})
+	}
+}
+
+/// The magnitude of a release, used to merge intents.
+const fn rank(release: ReleaseType) -> u8 {
+	match release {
+		ReleaseType::Major | ReleaseType::Premajor => 3,
+		ReleaseType::Minor | ReleaseType::Preminor => 2,
+		ReleaseType::Patch | ReleaseType::Prepatch | ReleaseType::Prerelease => 1,
+	}
+}
+
+/// Record a release unless a larger one is already planned. Returns whether
+/// the plan changed.
+fn merge(
+	planned: &mut IndexMap<usize, (ReleaseType, ReleaseReason)>,
+	index: usize,
+	release: ReleaseType,
+	reason: ReleaseReason,
+) -> bool {
+	match planned.get(&index) {
+		Some((existing, _)) if rank(*existing) >= rank(release) => false,
+		_ => {
+			planned.insert(index, (release, reason));
+			true
+		}
+	}
+}
+
+fn current_version(version: Option<&str>) -> crate::Result<Version> {
+	let version = version.ok_or(crate::Error::MissingVersion)?;
+
+	parse_version(version).ok_or_else(|| {
+		crate::Error::InvalidVersion {
+			version: version.to_string(),
+		}
+	})
+}
+
+/// The range which a spec represents for the current version of the sibling.
+fn effective_range(spec: &str, old_version: &Version) -> Option<String> {
+	match DependencySpec::parse(spec) {
+		DependencySpec::Workspace { range, .. } => {
+			match range {
+				"*" => Some(old_version.to_string()),
+				"^" | "~" => Some(format!("{range}{old_version}")),
+				_ if range.starts_with('.') => Some(old_version.to_string()),
+				_ => Some(range.to_string()),
+			}
+		}
+		DependencySpec::Range(range) | DependencySpec::Alias { range, .. } => {
+			Some(range.to_string())
+		}
+		_ => None,
+	}
+}
+
+fn satisfies(spec: &str, old_version: &Version, new_version: &Version) -> bool {
+	effective_range(spec, old_version)
+		.and_then(|range| VersionRange::parse(&range).ok())
+		.map_or(true, |range| range.satisfies(new_version))
+}
+
+/// Rewrite a spec to include the new version, keeping the `^` or `~` style of
+/// the current range. Returns `None` for specs which don't need to change.
+fn updated_spec(spec: &str, new_version: &Version) -> Option<String> {
+	let with_operator = |range: &str| {
+		let operator = ["^", "~", ">="]
+			.into_iter()
+			.find(|operator| range.trim().starts_with(operator));
+
+		match operator {
+			Some(operator) => format!("{operator}{new_version}"),
+			None if parse_version(range).is_some() => new_version.to_string(),
+			None => format!("^{new_version}"),
+		}
+	};
+
+	match DependencySpec::parse(spec) {
+		DependencySpec::Workspace { name, range } => {
+			if matches!(range, "*" | "^" | "~") || range.starts_with('.') {
+				return None;
+			}
+
+			let range = with_operator(range);
+			Some(name.map_or_else(
+				|| format!("workspace:{range}"),
+				|name| format!("workspace:{name}@{range}"),
+			))
+		}
+		DependencySpec::Alias { name, range } => {
+			Some(format!("npm:{name}@{}", with_operator(range)))
+		}
+		DependencySpec::Range(range) => Some(with_operator(range)),
+		_ => None,
+	}
+}
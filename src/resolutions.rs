@@ -0,0 +1,679 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use indexmap::IndexMap;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::PackageJson;
+
+lazy_static! {
+	static ref RESOLUTION_NAME_REGEX: Regex =
+		Regex::new(r"^(?:@[a-zA-Z0-9-*~][a-zA-Z0-9-*._~]*/)?[a-zA-Z0-9-~][a-zA-Z0-9-._~]*$")
+			.unwrap();
+}
+
+/// The flavour of yarn which interprets the `resolutions` field.
+///
+/// Yarn classic (v1) treats every key as a glob over the path of package names
+/// leading to a dependency. Yarn berry (v2+) only understands a dependency
+/// descriptor, optionally preceded by a single parent descriptor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YarnFlavor {
+	Classic,
+	Berry,
+}
+
+impl YarnFlavor {
+	/// Infer the yarn flavour from a `packageManager` field value such as
+	/// `yarn@1.22.19` or `yarn@4.1.0`. Returns `None` when the package manager
+	/// is not yarn.
+	///
+	/// ```
+	/// use package_json_schema::YarnFlavor;
+	///
+	/// let classic = YarnFlavor::from_package_manager("yarn@1.22.19");
+	/// let berry = YarnFlavor::from_package_manager("yarn@4.1.0");
+	///
+	/// assert_eq!(classic, Some(YarnFlavor::Classic));
+	/// assert_eq!(berry, Some(YarnFlavor::Berry));
+	/// assert_eq!(YarnFlavor::from_package_manager("pnpm@9.0.0"), None);
+	/// ```
+	#[must_use]
+	pub fn from_package_manager(package_manager: &str) -> Option<Self> {
+		let version = package_manager.strip_prefix("yarn@")?;
+		let major = version.split('.').next()?;
+
+		match major.parse::<u64>().ok()? {
+			0 | 1 => Some(Self::Classic),
+			_ => Some(Self::Berry),
+		}
+	}
+}
+
+/// A single segment of a parsed [`ResolutionPattern`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PatternSegment {
+	/// A package name with an optional descriptor range, e.g. `left-pad` or
+	/// `lodash@npm:^4.17.0`.
+	Package { name: String, range: Option<String> },
+
+	/// `*` which matches exactly one package in the dependency chain.
+	Wildcard,
+
+	/// `**` which matches zero or more packages in the dependency chain.
+	Globstar,
+}
+
+impl PatternSegment {
+	const fn range(&self) -> Option<&String> {
+		match self {
+			Self::Package { range, .. } => range.as_ref(),
+			Self::Wildcard | Self::Globstar => None,
+		}
+	}
+}
+
+/// One step in the chain of dependencies leading from the root manifest to an
+/// installed package.
+///
+/// The first link is a direct dependency of the root `package.json` and the
+/// last link is the package which a resolution may apply to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DependencyLink<'a> {
+	/// The name of the dependency.
+	pub name: &'a str,
+
+	/// The range the dependency was requested with by its parent.
+	pub range: &'a str,
+
+	/// The version the dependency resolved to, which yarn berry compares with
+	/// the range of a parent in a pattern.
+	pub version: Option<&'a str>,
+}
+
+impl<'a> DependencyLink<'a> {
+	#[must_use]
+	pub const fn new(name: &'a str, range: &'a str) -> Self {
+		Self {
+			name,
+			range,
+			version: None,
+		}
+	}
+
+	/// Set the version the dependency resolved to.
+	#[must_use]
+	pub const fn with_version(mut self, version: &'a str) -> Self {
+		self.version = Some(version);
+		self
+	}
+}
+
+/// A parsed key of the `resolutions` field.
+///
+/// ```
+/// use package_json_schema::DependencyLink;
+/// use package_json_schema::ResolutionPattern;
+/// use package_json_schema::YarnFlavor;
+///
+/// let pattern: ResolutionPattern = "webpack/**/terser".parse().unwrap();
+/// let webpack = DependencyLink::new("webpack", "^5.0.0");
+/// let plugin = DependencyLink::new("terser-webpack-plugin", "^5.3.0");
+/// let terser = DependencyLink::new("terser", "^5.26.0");
+/// let chain = [webpack, plugin, terser];
+///
+/// assert_eq!(pattern.name(), "terser");
+/// assert!(pattern.matches(&chain, YarnFlavor::Classic));
+/// assert!(!pattern.matches(&chain, YarnFlavor::Berry));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResolutionPattern {
+	raw: String,
+	segments: Vec<PatternSegment>,
+}
+
+impl ResolutionPattern {
+	/// Parse a `resolutions` key.
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::InvalidResolutionPattern`] when the key isn't a
+	/// valid package path.
+	pub fn parse(pattern: &str) -> crate::Result<Self> {
+		let invalid = |reason: &str| {
+			crate::Error::InvalidResolutionPattern {
+				pattern: pattern.to_string(),
+				reason: reason.to_string(),
+			}
+		};
+
+		if pattern.is_empty() {
+			return Err(invalid("the pattern is empty"));
+		}
+
+		let mut segments = Vec::new();
+		let mut rest = pattern;
+
+		loop {
+			let (segment, remaining) = parse_segment(rest).map_err(invalid)?;
+
+			// Consecutive globstars are equivalent to a single one.
+			if !(segment == PatternSegment::Globstar && segments.last() == Some(&segment)) {
+				segments.push(segment);
+			}
+
+			match remaining {
+				Some(remaining) => rest = remaining,
+				None => break,
+			}
+		}
+
+		if !matches!(segments.last(), Some(PatternSegment::Package { .. })) {
+			return Err(invalid("the pattern must end with a package name"));
+		}
+
+		Ok(Self {
+			raw: pattern.to_string(),
+			segments,
+		})
+	}
+
+	/// The original `resolutions` key.
+	#[must_use]
+	pub fn as_str(&self) -> &str {
+		&self.raw
+	}
+
+	/// All the parsed segments, the last of which is always a package.
+	#[must_use]
+	pub fn segments(&self) -> &[PatternSegment] {
+		&self.segments
+	}
+
+	/// The name of the package which this resolution overrides.
+	#[must_use]
+	pub fn name(&self) -> &str {
+		match self.segments.last() {
+			Some(PatternSegment::Package { name, .. }) => name,
+			_ => unreachable!("a parsed pattern always ends with a package"),
+		}
+	}
+
+	/// The descriptor range of the overridden package, e.g. `npm:1.0.0` for
+	/// `foo@npm:1.0.0`.
+	#[must_use]
+	pub fn range(&self) -> Option<&str> {
+		self.segments
+			.last()
+			.and_then(PatternSegment::range)
+			.map(String::as_str)
+	}
+
+	/// The segments preceding the overridden package.
+	#[must_use]
+	pub fn parents(&self) -> &[PatternSegment] {
+		&self.segments[..self.segments.len() - 1]
+	}
+
+	/// Whether any segment carries a descriptor range.
+	#[must_use]
+	pub fn has_ranges(&self) -> bool {
+		self.segments
+			.iter()
+			.any(|segment| segment.range().is_some())
+	}
+
+	/// Whether yarn berry can represent this pattern. Berry supports `dep`,
+	/// `dep@range`, `parent/dep` and `parent@range/dep@range`. A leading `**/`
+	/// is tolerated for compatibility with yarn classic.
+	#[must_use]
+	pub fn is_berry_compatible(&self) -> bool {
+		let parents = self.berry_parents();
+
+		parents.len() <= 1
+			&& parents
+				.iter()
+				.all(|segment| matches!(segment, PatternSegment::Package { .. }))
+	}
+
+	/// Check whether this pattern applies to the last package of the provided
+	/// dependency chain.
+	#[must_use]
+	pub fn matches(&self, chain: &[DependencyLink<'_>], flavor: YarnFlavor) -> bool {
+		let Some((target, ancestors)) = chain.split_last() else {
+			return false;
+		};
+
+		if target.name != self.name() {
+			return false;
+		}
+
+		match flavor {
+			YarnFlavor::Classic => {
+				let names: Vec<&str> = ancestors.iter().map(|link| link.name).collect();
+				glob_matches(&self.classic_parents(), &names)
+			}
+			YarnFlavor::Berry => {
+				if !self.is_berry_compatible() {
+					return false;
+				}
+
+				if let Some(range) = self.range() {
+					if normalize_range(range) != normalize_range(target.range) {
+						return false;
+					}
+				}
+
+				match (self.berry_parents().first(), ancestors.last()) {
+					(None, _) => true,
+					(Some(PatternSegment::Package { name, range }), Some(parent)) => {
+						name == parent.name
+							&& range.as_ref().map_or(true, |range| {
+								parent.version.is_some_and(|version| {
+									normalize_range(range) == normalize_range(version)
+								})
+							})
+					}
+					(Some(_), _) => false,
+				}
+			}
+		}
+	}
+
+	/// The parent segments as yarn classic sees them. A bare package name
+	/// applies at every depth, otherwise the pattern is anchored at the root.
+	fn classic_parents(&self) -> Vec<PatternSegment> {
+		let parents = self.parents();
+
+		if parents.is_empty() {
+			vec![PatternSegment::Globstar]
+		} else {
+			parents.to_vec()
+		}
+	}
+
+	fn berry_parents(&self) -> &[PatternSegment] {
+		match self.parents() {
+			[PatternSegment::Globstar, rest @ ..] => rest,
+			parents => parents,
+		}
+	}
+
+	/// The constraints berry compares when applying this pattern.
+	fn berry_key(&self) -> BerryKey<'_> {
+		let parent = self.berry_parents().first().and_then(|segment| {
+			match segment {
+				PatternSegment::Package { name, range } => {
+					Some((name.as_str(), range.as_deref().map(normalize_range)))
+				}
+				PatternSegment::Wildcard | PatternSegment::Globstar => None,
+			}
+		});
+
+		BerryKey {
+			parent,
+			name: self.name(),
+			range: self.range().map(normalize_range),
+		}
+	}
+}
+
+/// The normalized constraints of a berry compatible pattern.
+struct BerryKey<'a> {
+	parent: Option<(&'a str, Option<String>)>,
+	name: &'a str,
+	range: Option<String>,
+}
+
+impl BerryKey<'_> {
+	/// Whether every dependency matched by `other` is also matched by this key.
+	fn covers(&self, other: &Self) -> bool {
+		let covers =
+			|outer: &Option<String>, inner: &Option<String>| outer.is_none() || outer == inner;
+		let parent = match (&self.parent, &other.parent) {
+			(None, _) => true,
+			(Some((name, range)), Some((other_name, other_range))) => {
+				name == other_name && covers(range, other_range)
+			}
+			(Some(_), None) => false,
+		};
+
+		parent && self.name == other.name && covers(&self.range, &other.range)
+	}
+}
+
+impl FromStr for ResolutionPattern {
+	type Err = crate::Error;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		Self::parse(value)
+	}
+}
+
+impl Display for ResolutionPattern {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.raw)
+	}
+}
+
+/// A valid `resolutions` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolutionEntry {
+	/// The parsed key.
+	pub pattern: ResolutionPattern,
+
+	/// The version range or descriptor that the matched dependency is replaced
+	/// with.
+	pub resolution: String,
+}
+
+/// The reason a `resolutions` entry was reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolutionWarningKind {
+	/// The key couldn't be parsed. The entry is ignored.
+	Invalid(String),
+
+	/// The key is valid but uses syntax the selected yarn flavour doesn't
+	/// support.
+	Unsupported(String),
+
+	/// Every dependency the key matches is already claimed by another entry
+	/// which takes precedence.
+	Unreachable { shadowed_by: String },
+}
+
+/// A problem found with a `resolutions` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolutionWarning {
+	/// The `resolutions` key the warning refers to.
+	pub pattern: String,
+
+	/// What is wrong with the key.
+	pub kind: ResolutionWarningKind,
+}
+
+impl Display for ResolutionWarning {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match &self.kind {
+			ResolutionWarningKind::Invalid(reason) => {
+				write!(f, "invalid resolution `{}`: {reason}", self.pattern)
+			}
+			ResolutionWarningKind::Unsupported(reason) => {
+				write!(f, "unsupported resolution `{}`: {reason}", self.pattern)
+			}
+			ResolutionWarningKind::Unreachable { shadowed_by } => {
+				write!(
+					f,
+					"resolution `{}` is never used because `{shadowed_by}` takes precedence",
+					self.pattern
+				)
+			}
+		}
+	}
+}
+
+/// The `resolutions` field parsed for a specific yarn flavour.
+///
+/// ```
+/// use indexmap::IndexMap;
+/// use package_json_schema::DependencyLink;
+/// use package_json_schema::ResolutionMap;
+/// use package_json_schema::YarnFlavor;
+///
+/// let mut resolutions = IndexMap::new();
+/// resolutions.insert("**/left-pad".to_string(), "1.3.0".to_string());
+/// resolutions.insert("d2/left-pad".to_string(), "1.1.1".to_string());
+///
+/// let map = ResolutionMap::new(&resolutions, YarnFlavor::Classic);
+/// let d2 = DependencyLink::new("d2", "^1.0.0");
+/// let left_pad = DependencyLink::new("left-pad", "^1.0.0");
+/// let chain = [d2, left_pad];
+///
+/// assert_eq!(map.find(&chain).unwrap().resolution, "1.3.0");
+/// assert_eq!(map.warnings().len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResolutionMap {
+	flavor: YarnFlavor,
+	entries: Vec<ResolutionEntry>,
+	warnings: Vec<ResolutionWarning>,
+}
+
+impl ResolutionMap {
+	/// Parse every entry of a `resolutions` field, collecting warnings for the
+	/// ones which are invalid, unsupported or unreachable.
+	#[must_use]
+	pub fn new(resolutions: &IndexMap<String, String>, flavor: YarnFlavor) -> Self {
+		let mut entries = Vec::new();
+		let mut warnings = Vec::new();
+
+		for (key, resolution) in resolutions {
+			match ResolutionPattern::parse(key) {
+				Ok(pattern) => {
+					entries.push(ResolutionEntry {
+						pattern,
+						resolution: resolution.clone(),
+					});
+				}
+				Err(crate::Error::InvalidResolutionPattern { reason, .. }) => {
+					warnings.push(ResolutionWarning {
+						pattern: key.clone(),
+						kind: ResolutionWarningKind::Invalid(reason),
+					});
+				}
+				Err(error) => unreachable!("unexpected error: {error}"),
+			}
+		}
+
+		for (index, entry) in entries.iter().enumerate() {
+			let pattern = &entry.pattern;
+
+			match flavor {
+				YarnFlavor::Classic if pattern.has_ranges() => {
+					warnings.push(ResolutionWarning {
+						pattern: pattern.to_string(),
+						kind: ResolutionWarningKind::Unsupported(
+							"yarn classic ignores descriptor ranges in resolution keys".into(),
+						),
+					});
+				}
+				YarnFlavor::Berry if !pattern.is_berry_compatible() => {
+					warnings.push(ResolutionWarning {
+						pattern: pattern.to_string(),
+						kind: ResolutionWarningKind::Unsupported(
+							"yarn berry only supports a single parent package without globs".into(),
+						),
+					});
+					continue;
+				}
+				_ => {}
+			}
+
+			let shadowed_by = entries[..index].iter().find(|earlier| {
+				match flavor {
+					YarnFlavor::Classic => {
+						earlier.pattern.name() == pattern.name()
+							&& glob_covers(
+								&earlier.pattern.classic_parents(),
+								&pattern.classic_parents(),
+							)
+					}
+					YarnFlavor::Berry => {
+						earlier.pattern.is_berry_compatible()
+							&& earlier.pattern.berry_key().covers(&pattern.berry_key())
+					}
+				}
+			});
+
+			if let Some(earlier) = shadowed_by {
+				warnings.push(ResolutionWarning {
+					pattern: pattern.to_string(),
+					kind: ResolutionWarningKind::Unreachable {
+						shadowed_by: earlier.pattern.to_string(),
+					},
+				});
+			}
+		}
+
+		Self {
+			flavor,
+			entries,
+			warnings,
+		}
+	}
+
+	/// The flavour used to interpret the patterns.
+	#[must_use]
+	pub const fn flavor(&self) -> YarnFlavor {
+		self.flavor
+	}
+
+	/// All the entries with a valid key, in declaration order.
+	#[must_use]
+	pub fn entries(&self) -> &[ResolutionEntry] {
+		&self.entries
+	}
+
+	/// The problems found while parsing the entries.
+	#[must_use]
+	pub fn warnings(&self) -> &[ResolutionWarning] {
+		&self.warnings
+	}
+
+	/// Find the resolution which applies to the last package of the dependency
+	/// chain.
+	///
+	/// Both flavours use the first matching entry in declaration order. Yarn
+	/// berry compares the range of a parent with the version the parent
+	/// resolved to, so parents without a version never match such a pattern.
+	#[must_use]
+	pub fn find(&self, chain: &[DependencyLink<'_>]) -> Option<&ResolutionEntry> {
+		self.entries
+			.iter()
+			.find(|entry| entry.pattern.matches(chain, self.flavor))
+	}
+}
+
+impl PackageJson {
+	/// Parse the `resolutions` field for the provided yarn flavour. An absent
+	/// field produces an empty map.
+	#[must_use]
+	pub fn resolution_map(&self, flavor: YarnFlavor) -> ResolutionMap {
+		self.resolutions.as_ref().map_or_else(
+			|| ResolutionMap::new(&IndexMap::new(), flavor),
+			|resolutions| ResolutionMap::new(resolutions, flavor),
+		)
+	}
+}
+
+/// Parse the next segment of a pattern, returning the remainder after the
+/// separating `/`.
+fn parse_segment(input: &str) -> Result<(PatternSegment, Option<&str>), &'static str> {
+	let (segment, rest) = if let Some(rest) = input.strip_prefix("**") {
+		(PatternSegment::Globstar, rest)
+	} else if let Some(rest) = input.strip_prefix('*') {
+		(PatternSegment::Wildcard, rest)
+	} else {
+		let name_length = package_name_length(input);
+		let name = &input[..name_length];
+		let mut rest = &input[name_length..];
+
+		if name.contains('*') || !RESOLUTION_NAME_REGEX.is_match(name) {
+			return Err("the pattern contains an invalid package name");
+		}
+
+		let range = if let Some(after) = rest.strip_prefix('@') {
+			// Protocol ranges like `npm:@scope/name@1.0.0` may contain slashes so
+			// they consume the rest of the pattern.
+			let end = match (after.find('/'), after.find(':')) {
+				(Some(slash), Some(colon)) if colon < slash => after.len(),
+				(Some(slash), _) => slash,
+				(None, _) => after.len(),
+			};
+
+			if end == 0 {
+				return Err("the pattern contains an empty range");
+			}
+
+			rest = &after[end..];
+			Some(after[..end].to_string())
+		} else {
+			None
+		};
+
+		(
+			PatternSegment::Package {
+				name: name.to_string(),
+				range,
+			},
+			rest,
+		)
+	};
+
+	if rest.is_empty() {
+		Ok((segment, None))
+	} else if let Some(rest) = rest.strip_prefix('/') {
+		if rest.is_empty() {
+			Err("the pattern must end with a package name")
+		} else {
+			Ok((segment, Some(rest)))
+		}
+	} else {
+		Err("partial globs are not supported")
+	}
+}
+
+/// The length of the (possibly scoped) package name at the start of the input.
+fn package_name_length(input: &str) -> usize {
+	let end_of = |value: &str| value.find(['/', '@']).unwrap_or(value.len());
+
+	input.strip_prefix('@').map_or_else(
+		|| end_of(input),
+		|scoped| {
+			scoped.find('/').map_or(input.len(), |slash| {
+				slash + 2 + end_of(&scoped[slash + 1..])
+			})
+		},
+	)
+}
+
+/// Ranges without a protocol are implicitly `npm:` ranges in yarn berry.
+fn normalize_range(range: &str) -> String {
+	if range.contains(':') {
+		range.to_string()
+	} else {
+		format!("npm:{range}")
+	}
+}
+
+/// Check whether the segments match the full list of package names.
+fn glob_matches(segments: &[PatternSegment], names: &[&str]) -> bool {
+	match (segments.split_first(), names.split_first()) {
+		(None, _) => names.is_empty(),
+		(Some((PatternSegment::Globstar, rest)), _) => {
+			glob_matches(rest, names) || (!names.is_empty() && glob_matches(segments, &names[1..]))
+		}
+		(Some(_), None) => false,
+		(Some((PatternSegment::Wildcard, rest)), Some((_, names))) => glob_matches(rest, names),
+		(Some((PatternSegment::Package { name, .. }, rest)), Some((first, names))) => {
+			name == first && glob_matches(rest, names)
+		}
+	}
+}
+
+/// Check whether every path matched by `inner` is also matched by `outer`.
+fn glob_covers(outer: &[PatternSegment], inner: &[PatternSegment]) -> bool {
+	match (outer.split_first(), inner.split_first()) {
+		(None, _) => inner.is_empty(),
+		(Some((PatternSegment::Globstar, rest)), _) => {
+			glob_covers(rest, inner) || (!inner.is_empty() && glob_covers(outer, &inner[1..]))
+		}
+		(Some(_), None | Some((PatternSegment::Globstar, _))) => false,
+		(Some((PatternSegment::Wildcard, rest)), Some((_, inner))) => glob_covers(rest, inner),
+		(Some((PatternSegment::Package { name, .. }, rest)), Some((first, inner))) => {
+			matches!(first, PatternSegment::Package { name: other, .. } if other == name)
+				&& glob_covers(rest, inner)
+		}
+	}
+}
@@ -0,0 +1,122 @@
+use crate::VersionRange;
+
+/// The value of a single dependency in a `package.json`, classified by the
+/// protocol it uses.
+///
+/// ```
+/// use package_json_schema::DependencySpec;
+///
+/// let spec = DependencySpec::parse("workspace:^");
+/// assert!(matches!(spec, DependencySpec::Workspace { range: "^", .. }));
+///
+/// let spec = DependencySpec::parse("npm:lodash@^4.0.0");
+/// assert!(matches!(spec, DependencySpec::Alias { name: "lodash", .. }));
+///
+/// assert_eq!(DependencySpec::parse("next"), DependencySpec::Tag("next"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencySpec<'a> {
+	/// A node-semver range such as `^1.2.3`, `1.x` or an empty string.
+	Range(&'a str),
+
+	/// A dist-tag such as `latest`.
+	Tag(&'a str),
+
+	/// The `workspace:` protocol. The range is `*`, `^`, `~` or a version
+	/// range. The name is set by the `workspace:<name>@<range>` alias form.
+	Workspace {
+		name: Option<&'a str>,
+		range: &'a str,
+	},
+
+	/// The `catalog:` protocol of pnpm, which takes the range from a catalog
+	/// of `pnpm-workspace.yaml`. A bare `catalog:` refers to the `default`
+	/// catalog.
+	Catalog(&'a str),
+
+	/// The `npm:<name>@<range>` alias protocol.
+	Alias { name: &'a str, range: &'a str },
+
+	/// A local directory or tarball referenced with `file:`, `link:` or
+	/// `portal:`.
+	Path { protocol: &'a str, path: &'a str },
+
+	/// A git repository, a remote tarball or any other protocol.
+	Url(&'a str),
+}
+
+impl<'a> DependencySpec<'a> {
+	/// Classify a dependency value. This never fails, values which aren't
+	/// recognized are treated as a [`DependencySpec::Url`].
+	#[must_use]
+	pub fn parse(spec: &'a str) -> Self {
+		let spec = spec.trim();
+
+		if let Some(rest) = spec.strip_prefix("workspace:") {
+			return match split_name_and_range(rest) {
+				Some((name, range)) => {
+					Self::Workspace {
+						name: Some(name),
+						range,
+					}
+				}
+				None => {
+					Self::Workspace {
+						name: None,
+						range: rest,
+					}
+				}
+			};
+		}
+
+		if let Some(name) = spec.strip_prefix("catalog:") {
+			let name = name.trim();
+			return Self::Catalog(if name.is_empty() { "default" } else { name });
+		}
+
+		if let Some(rest) = spec.strip_prefix("npm:") {
+			let (name, range) = split_name_and_range(rest).unwrap_or((rest, "*"));
+			return Self::Alias { name, range };
+		}
+
+		for protocol in ["file", "link", "portal"] {
+			if let Some(path) = spec
+				.strip_prefix(protocol)
+				.and_then(|rest| rest.strip_prefix(':'))
+			{
+				return Self::Path { protocol, path };
+			}
+		}
+
+		if spec.starts_with(['.', '/', '~']) && !spec.starts_with("~>") && spec.contains('/') {
+			return Self::Path {
+				protocol: "file",
+				path: spec,
+			};
+		}
+
+		if spec.contains(':') || spec.contains('/') {
+			return Self::Url(spec);
+		}
+
+		if VersionRange::parse(spec).is_ok() {
+			Self::Range(spec)
+		} else {
+			Self::Tag(spec)
+		}
+	}
+}
+
+/// Split `name@range`, keeping the `@` of a scoped name. Returns `None` when
+/// the value doesn't contain a name.
+fn split_name_and_range(value: &str) -> Option<(&str, &str)> {
+	let offset = usize::from(value.starts_with('@'));
+	let index = value[offset..].find('@')? + offset;
+	let name = &value[..index];
+
+	if name.is_empty() || name == "@" {
+		return None;
+	}
+
+	Some((name, &value[index + 1..]))
+}
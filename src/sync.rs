@@ -0,0 +1,242 @@
+use crate::lockfile::locked_version_satisfies;
+use crate::DependencyKind;
+use crate::LockedDependency;
+use crate::LockfileGraph;
+use crate::PackageJson;
+use crate::VersionRange;
+use crate::Workspace;
+
+/// A difference between a `package.json` and the lockfile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncProblem {
+	/// The dependency is declared but the lockfile doesn't install it.
+	Missing {
+		/// The field which declares the dependency.
+		kind: DependencyKind,
+		/// The name of the dependency.
+		name: String,
+		/// The declared spec.
+		spec: String,
+	},
+
+	/// The lockfile installs a version which the declared range rejects, or
+	/// records a different spec for a dependency which isn't a range.
+	Unsatisfied {
+		/// The field which declares the dependency.
+		kind: DependencyKind,
+		/// The name of the dependency.
+		name: String,
+		/// The declared spec.
+		spec: String,
+		/// The locked version, or the spec recorded by the lockfile.
+		locked: String,
+	},
+
+	/// The lockfile records a dependency which the `package.json` doesn't
+	/// declare.
+	Extraneous {
+		/// The field the lockfile records the dependency in.
+		kind: DependencyKind,
+		/// The name of the dependency.
+		name: String,
+	},
+
+	/// The dependency is declared in a different field than the lockfile
+	/// records, e.g. moved from `devDependencies` to `dependencies`.
+	KindMismatch {
+		/// The name of the dependency.
+		name: String,
+		/// The field of the `package.json`.
+		manifest: DependencyKind,
+		/// The field recorded by the lockfile.
+		lockfile: DependencyKind,
+	},
+}
+
+/// A [`SyncProblem`] of a single project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncIssue {
+	/// The path of the project relative to the root, `.` for the root.
+	pub importer: String,
+
+	/// What is out of sync.
+	pub problem: SyncProblem,
+}
+
+/// The differences between the manifests and the lockfile. Created by
+/// [`LockfileGraph::check_sync`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+	/// The problems ordered by project and then by declaration.
+	pub issues: Vec<SyncIssue>,
+}
+
+impl SyncReport {
+	/// Whether a clean install, like `npm ci`, would succeed.
+	#[must_use]
+	pub fn is_in_sync(&self) -> bool {
+		self.issues.is_empty()
+	}
+
+	/// The problems of a single project.
+	#[must_use]
+	pub fn importer(&self, path: &str) -> Vec<&SyncProblem> {
+		self.issues
+			.iter()
+			.filter(|issue| issue.importer == path)
+			.map(|issue| &issue.problem)
+			.collect()
+	}
+}
+
+impl LockfileGraph {
+	/// Check that the lockfile matches the provided manifests, keyed by their
+	/// path relative to the root, the way `npm ci` refuses to install when
+	/// they disagree.
+	///
+	/// Semver ranges are checked against the locked version, while other
+	/// specs such as `workspace:*` or tags must equal the spec recorded by
+	/// the lockfile. Peer dependencies are skipped since they are installed
+	/// by the dependents.
+	///
+	/// ```
+	/// use package_json_schema::LockfileGraph;
+	/// use package_json_schema::PackageJson;
+	/// use package_json_schema::PackageLock;
+	/// use package_json_schema::SyncProblem;
+	///
+	/// let contents = r#"{
+	///   "lockfileVersion": 3,
+	///   "packages": {
+	///     "": { "dependencies": { "ms": "^2.1.0" } },
+	///     "node_modules/ms": { "version": "2.1.3" }
+	///   }
+	/// }"#;
+	/// let package_lock = PackageLock::try_from(contents).unwrap();
+	/// let package_json = PackageJson::try_from(r#"{ "dependencies": { "ms": "^3.0.0" } }"#).unwrap();
+	/// let graph = LockfileGraph::from_package_lock(&package_lock);
+	/// let report = graph.check_sync(&[(".", &package_json)]);
+	///
+	/// let problem = &report.issues[0].problem;
+	///
+	/// assert!(matches!(problem, SyncProblem::Unsatisfied { locked, .. } if locked == "2.1.3"));
+	/// ```
+	#[must_use]
+	pub fn check_sync(&self, manifests: &[(&str, &PackageJson)]) -> SyncReport {
+		let mut report = SyncReport::default();
+
+		for (path, package_json) in manifests {
+			let importer = self.importer(path);
+			let locked: Vec<&LockedDependency> = importer
+				.map(|importer| {
+					importer
+						.dependencies
+						.iter()
+						.filter(|dependency| dependency.kind != DependencyKind::Peer)
+						.collect()
+				})
+				.unwrap_or_default();
+			let mut problems = Vec::new();
+
+			let declared: Vec<_> = package_json
+				.all_dependencies()
+				.filter(|(kind, ..)| *kind != DependencyKind::Peer)
+				.collect();
+
+			for (kind, name, spec) in &declared {
+				let Some(dependency) = locked.iter().find(|dependency| dependency.name == *name)
+				else {
+					problems.push(SyncProblem::Missing {
+						kind: *kind,
+						name: (*name).to_string(),
+						spec: (*spec).to_string(),
+					});
+					continue;
+				};
+
+				if dependency.kind != *kind {
+					problems.push(SyncProblem::KindMismatch {
+						name: (*name).to_string(),
+						manifest: *kind,
+						lockfile: dependency.kind,
+					});
+				}
+
+				if let Some(problem) = self.check_dependency(*kind, name, spec, dependency) {
+					problems.push(problem);
+				}
+			}
+
+			for dependency in locked {
+				// The legacy npm format lists every hoisted package at the root
+				// without a spec, so only declared dependencies can be extraneous.
+				if dependency.spec.is_some()
+					&& !declared.iter().any(|(_, name, _)| *name == dependency.name)
+				{
+					problems.push(SyncProblem::Extraneous {
+						kind: dependency.kind,
+						name: dependency.name.clone(),
+					});
+				}
+			}
+
+			report.issues.extend(problems.into_iter().map(|problem| {
+				SyncIssue {
+					importer: (*path).to_string(),
+					problem,
+				}
+			}));
+		}
+
+		report
+	}
+
+	fn check_dependency(
+		&self,
+		kind: DependencyKind,
+		name: &str,
+		spec: &str,
+		dependency: &LockedDependency,
+	) -> Option<SyncProblem> {
+		let unsatisfied = |locked: &str| {
+			SyncProblem::Unsatisfied {
+				kind,
+				name: name.to_string(),
+				spec: spec.to_string(),
+				locked: locked.to_string(),
+			}
+		};
+
+		if VersionRange::parse(spec).is_err() {
+			return dependency
+				.spec
+				.as_deref()
+				.filter(|locked| *locked != spec)
+				.map(unsatisfied);
+		}
+
+		let Some(package) = dependency.target.as_deref().and_then(|id| self.package(id)) else {
+			return Some(SyncProblem::Missing {
+				kind,
+				name: name.to_string(),
+				spec: spec.to_string(),
+			});
+		};
+
+		if package.link.is_some() {
+			return None;
+		}
+
+		(locked_version_satisfies(spec, &package.version) == Some(false))
+			.then(|| unsatisfied(&package.version))
+	}
+}
+
+impl Workspace {
+	/// Check that the lockfile matches the root `package.json` and every
+	/// member. See [`LockfileGraph::check_sync`].
+	#[must_use]
+	pub fn check_lockfile_sync(&self, graph: &LockfileGraph) -> SyncReport {
+		graph.check_sync(&self.manifests())
+	}
+}
@@ -5,6 +5,8 @@ use validator::ValidateEmail;
 use validator::ValidateUrl;
 use validator::ValidationError;
 
+use crate::PackageJson;
+
 lazy_static! {
 	pub static ref PACKAGE_NAME_REGEX: Regex =
 		Regex::new(r"^(?:@[a-z0-9-*~][a-z0-9-*._~]*/)?[a-z0-9-~][a-z0-9-._~]*$").unwrap();
@@ -34,3 +36,21 @@ pub fn validate_email_or_url(email_or_url: &str) -> Result<(), ValidationError>
 		Err(ValidationError::new("invalid email or url"))
 	}
 }
+
+pub fn validate_peer_dependencies_meta(package_json: &PackageJson) -> Result<(), ValidationError> {
+	let undeclared = package_json.undeclared_peer_dependencies_meta();
+
+	if undeclared.is_empty() {
+		Ok(())
+	} else {
+		let message = format!(
+			"peerDependenciesMeta references names missing from peerDependencies: `{}`",
+			undeclared.join("`, `")
+		);
+
+		Err(
+			ValidationError::new("peerDependenciesMeta must only reference peerDependencies")
+				.with_message(message.into()),
+		)
+	}
+}
@@ -0,0 +1,289 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use semver::BuildMetadata;
+use semver::Prerelease;
+use semver::Version;
+
+use crate::parse_version;
+use crate::PackageJson;
+
+/// The part of a version to increment, matching the release types of
+/// `npm version`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ReleaseType {
+	/// `1.2.3` becomes `2.0.0`. A prerelease of a major version like
+	/// `2.0.0-0` becomes `2.0.0`.
+	Major,
+	/// `1.2.3` becomes `1.3.0`. A prerelease of a minor version like
+	/// `1.3.0-0` becomes `1.3.0`.
+	Minor,
+	/// `1.2.3` becomes `1.2.4`. A prerelease like `1.2.4-0` becomes `1.2.4`.
+	Patch,
+	/// `1.2.3` becomes `2.0.0-0`.
+	Premajor,
+	/// `1.2.3` becomes `1.3.0-0`.
+	Preminor,
+	/// `1.2.3` becomes `1.2.4-0`.
+	Prepatch,
+	/// `1.2.3` becomes `1.2.4-0` and `1.2.4-0` becomes `1.2.4-1`.
+	Prerelease,
+}
+
+impl ReleaseType {
+	/// Every release type, from the largest to the smallest change.
+	pub const ALL: [Self; 7] = [
+		Self::Major,
+		Self::Minor,
+		Self::Patch,
+		Self::Premajor,
+		Self::Preminor,
+		Self::Prepatch,
+		Self::Prerelease,
+	];
+
+	/// The name used by `npm version`.
+	#[must_use]
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Major => "major",
+			Self::Minor => "minor",
+			Self::Patch => "patch",
+			Self::Premajor => "premajor",
+			Self::Preminor => "preminor",
+			Self::Prepatch => "prepatch",
+			Self::Prerelease => "prerelease",
+		}
+	}
+}
+
+impl Display for ReleaseType {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.as_str())
+	}
+}
+
+impl FromStr for ReleaseType {
+	type Err = crate::Error;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		Self::ALL
+			.into_iter()
+			.find(|release| release.as_str() == value)
+			.ok_or_else(|| crate::Error::InvalidReleaseType(value.to_string()))
+	}
+}
+
+/// Increment a version the way node-semver's `inc` does. The prerelease
+/// identifier is used by the `pre*` release types, e.g. `beta` turns
+/// `1.2.3` into `1.2.4-beta.0` for a [`ReleaseType::Prerelease`].
+///
+/// ```
+/// use package_json_schema::increment_version;
+/// use package_json_schema::ReleaseType;
+/// use semver::Version;
+///
+/// let version = Version::parse("1.2.3-beta.1").unwrap();
+/// let release = ReleaseType::Prerelease;
+/// let next = increment_version(&version, release, Some("beta")).unwrap();
+///
+/// assert_eq!(next.to_string(), "1.2.3-beta.2");
+/// ```
+///
+/// # Errors
+///
+/// Returns [`crate::Error::InvalidPrereleaseIdentifier`] when the identifier
+/// isn't a valid prerelease and [`crate::Error::PrereleaseOverflow`] when the
+/// prerelease number of the version is too large to increment.
+pub fn increment_version(
+	version: &Version,
+	release: ReleaseType,
+	preid: Option<&str>,
+) -> crate::Result<Version> {
+	let overflow = || {
+		crate::Error::PrereleaseOverflow {
+			version: version.to_string(),
+		}
+	};
+
+	if preid.is_some_and(|preid| preid.is_empty() || Prerelease::new(preid).is_err()) {
+		return Err(crate::Error::InvalidPrereleaseIdentifier(
+			preid.unwrap_or_default().to_string(),
+		));
+	}
+
+	let mut next = version.clone();
+	next.build = BuildMetadata::EMPTY;
+
+	match release {
+		ReleaseType::Major => {
+			if next.minor != 0 || next.patch != 0 || next.pre.is_empty() {
+				next.major += 1;
+			}
+
+			next.minor = 0;
+			next.patch = 0;
+			next.pre = Prerelease::EMPTY;
+		}
+		ReleaseType::Minor => {
+			if next.patch != 0 || next.pre.is_empty() {
+				next.minor += 1;
+			}
+
+			next.patch = 0;
+			next.pre = Prerelease::EMPTY;
+		}
+		ReleaseType::Patch => {
+			if next.pre.is_empty() {
+				next.patch += 1;
+			}
+
+			next.pre = Prerelease::EMPTY;
+		}
+		ReleaseType::Premajor => {
+			next = Version::new(next.major + 1, 0, 0);
+			next.pre = increment_prerelease(&Prerelease::EMPTY, preid).ok_or_else(overflow)?;
+		}
+		ReleaseType::Preminor => {
+			next = Version::new(next.major, next.minor + 1, 0);
+			next.pre = increment_prerelease(&Prerelease::EMPTY, preid).ok_or_else(overflow)?;
+		}
+		ReleaseType::Prepatch => {
+			next = Version::new(next.major, next.minor, next.patch + 1);
+			next.pre = increment_prerelease(&Prerelease::EMPTY, preid).ok_or_else(overflow)?;
+		}
+		ReleaseType::Prerelease => {
+			if next.pre.is_empty() {
+				next.patch += 1;
+			}
+
+			next.pre = increment_prerelease(&next.pre, preid).ok_or_else(overflow)?;
+		}
+	}
+
+	Ok(next)
+}
+
+/// node-semver's `inc('pre', identifier)`. Returns `None` when the number to
+/// increment doesn't fit in a `u64`.
+fn increment_prerelease(current: &Prerelease, preid: Option<&str>) -> Option<Prerelease> {
+	let mut parts: Vec<String> = if current.is_empty() {
+		vec!["0".to_string()]
+	} else {
+		let mut parts: Vec<String> = current.split('.').map(ToString::to_string).collect();
+		let numeric = parts
+			.iter_mut()
+			.rev()
+			.find(|part| part.bytes().all(|byte| byte.is_ascii_digit()));
+
+		if let Some(part) = numeric {
+			*part = part.parse::<u64>().ok()?.checked_add(1)?.to_string();
+		} else {
+			parts.push("0".to_string());
+		}
+
+		parts
+	};
+
+	if let Some(preid) = preid {
+		let keeps_number = parts.first().is_some_and(|first| first == preid)
+			&& parts
+				.get(1)
+				.is_some_and(|second| second.bytes().all(|byte| byte.is_ascii_digit()));
+
+		if !keeps_number {
+			parts = vec![preid.to_string(), "0".to_string()];
+		}
+	}
+
+	Prerelease::new(&parts.join(".")).ok()
+}
+
+impl PackageJson {
+	/// Increment the `version` field, returning the new version.
+	///
+	/// ```
+	/// use package_json_schema::PackageJson;
+	/// use package_json_schema::ReleaseType;
+	///
+	/// let mut package_json = PackageJson::try_from(r#"{ "version": "1.2.3" }"#).unwrap();
+	/// package_json.bump(ReleaseType::Minor).unwrap();
+	///
+	/// assert_eq!(package_json.version.as_deref(), Some("1.3.0"));
+	/// ```
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::MissingVersion`],
+	/// [`crate::Error::InvalidVersion`] or [`crate::Error::PrereleaseOverflow`]
+	/// when the current version can't be incremented.
+	pub fn bump(&mut self, release: ReleaseType) -> crate::Result<Version> {
+		self.bump_version(release, None)
+	}
+
+	/// Increment the `version` field using a prerelease identifier such as
+	/// `beta`, returning the new version.
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::MissingVersion`],
+	/// [`crate::Error::InvalidVersion`] or [`crate::Error::PrereleaseOverflow`]
+	/// when the current version can't be incremented and
+	/// [`crate::Error::InvalidPrereleaseIdentifier`] for an invalid identifier.
+	pub fn bump_with_preid(&mut self, release: ReleaseType, preid: &str) -> crate::Result<Version> {
+		self.bump_version(release, Some(preid))
+	}
+
+	/// Set the `version` field to an explicit version, which must be greater
+	/// than the current version when there is one.
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::InvalidVersion`] when either version isn't
+	/// valid and [`crate::Error::VersionNotIncreased`] when the new version
+	/// doesn't come after the current one.
+	pub fn set_version(&mut self, version: &str) -> crate::Result<Version> {
+		let next = parse_version(version).ok_or_else(|| {
+			crate::Error::InvalidVersion {
+				version: version.to_string(),
+			}
+		})?;
+
+		if self.version.is_some() {
+			let current = self.current_version()?;
+
+			if next.cmp_precedence(&current).is_le() {
+				return Err(crate::Error::VersionNotIncreased {
+					current: current.to_string(),
+					next: next.to_string(),
+				});
+			}
+		}
+
+		self.version = Some(next.to_string());
+		Ok(next)
+	}
+
+	fn bump_version(
+		&mut self,
+		release: ReleaseType,
+		preid: Option<&str>,
+	) -> crate::Result<Version> {
+		let next = increment_version(&self.current_version()?, release, preid)?;
+		self.version = Some(next.to_string());
+		Ok(next)
+	}
+
+	fn current_version(&self) -> crate::Result<Version> {
+		let version = self
+			.version
+			.as_deref()
+			.ok_or(crate::Error::MissingVersion)?;
+
+		parse_version(version).ok_or_else(|| {
+			crate::Error::InvalidVersion {
+				version: version.to_string(),
+			}
+		})
+	}
+}
@@ -0,0 +1,460 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs::read_to_string;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use globset::Glob;
+use globset::GlobBuilder;
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde::Serialize;
+use typed_builder::TypedBuilder;
+use walkdir::WalkDir;
+
+use crate::AdditionalFields;
+use crate::PackageJson;
+use crate::Workspaces;
+
+/// The name of the file which configures a pnpm workspace.
+pub const PNPM_WORKSPACE_FILE: &str = "pnpm-workspace.yaml";
+
+/// The globs pnpm uses when `pnpm-workspace.yaml` has no `packages`, which
+/// make every package in the tree a member.
+pub const PNPM_DEFAULT_PATTERNS: [&str; 2] = [".", "**"];
+
+/// The contents of a `pnpm-workspace.yaml` file. See
+/// <https://pnpm.io/pnpm-workspace_yaml>.
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PnpmWorkspace {
+	/// Globs of the directories which contain workspace packages. Globs
+	/// starting with `!` exclude directories.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub packages: Option<Vec<String>>,
+
+	/// The `default` catalog, mapping package names to the ranges used by
+	/// `catalog:` dependencies.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub catalog: Option<IndexMap<String, String>>,
+
+	/// Named catalogs used by `catalog:<name>` dependencies.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub catalogs: Option<IndexMap<String, IndexMap<String, String>>>,
+
+	/// All additional settings.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+impl TryFrom<&str> for PnpmWorkspace {
+	type Error = crate::error::Error;
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		let pnpm_workspace: Self =
+			serde_yaml::from_str(value).map_err(crate::Error::ParsePnpmWorkspace)?;
+		Ok(pnpm_workspace)
+	}
+}
+
+impl Workspaces {
+	/// The globs of the directories which contain workspace packages.
+	#[must_use]
+	pub fn packages(&self) -> &[String] {
+		match self {
+			Self::List(packages) => packages,
+			Self::Object { packages, .. } => packages.as_deref().unwrap_or_default(),
+		}
+	}
+}
+
+/// A package which belongs to a [`Workspace`].
+#[derive(Debug, Clone)]
+pub struct WorkspaceMember {
+	/// The directory of the package.
+	pub path: PathBuf,
+
+	/// The directory of the package relative to the workspace root, using `/`
+	/// as the separator.
+	pub relative_path: String,
+
+	/// The parsed `package.json` of the package.
+	pub package_json: PackageJson,
+}
+
+impl WorkspaceMember {
+	/// The name of the package, when it has one.
+	#[must_use]
+	pub fn name(&self) -> Option<&str> {
+		self.package_json.name.as_deref()
+	}
+}
+
+/// A problem found while discovering the members of a [`Workspace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkspaceDiagnostic {
+	/// Several members share the same package name.
+	DuplicateName {
+		name: String,
+		relative_paths: Vec<String>,
+	},
+
+	/// A glob didn't match any package. For a negated glob this means that it
+	/// didn't exclude any package.
+	UnmatchedPattern { pattern: String },
+}
+
+impl Display for WorkspaceDiagnostic {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::DuplicateName {
+				name,
+				relative_paths,
+			} => {
+				write!(
+					f,
+					"the package name `{name}` is used by multiple workspace members: {}",
+					relative_paths.join(", ")
+				)
+			}
+			Self::UnmatchedPattern { pattern } => {
+				write!(
+					f,
+					"the workspace pattern `{pattern}` doesn't match any package"
+				)
+			}
+		}
+	}
+}
+
+/// A monorepo discovered from its root directory.
+///
+/// The member globs are read from `pnpm-workspace.yaml` when it exists and
+/// from the `workspaces` field of the root `package.json` otherwise.
+/// Directories named `node_modules` and hidden directories are never
+/// searched.
+///
+/// ```
+/// use package_json_schema::Workspace;
+///
+/// let workspace = Workspace::discover("./tests/fixtures/workspaces/npm").unwrap();
+/// let members = workspace.members();
+/// let names: Vec<_> = members.iter().filter_map(|member| member.name()).collect();
+/// let expected = ["@fixture/a", "@fixture/b", "@fixture/c", "@fixture/b"];
+///
+/// assert_eq!(names, expected);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Workspace {
+	root: PathBuf,
+	package_json: PackageJson,
+	pnpm: Option<PnpmWorkspace>,
+	patterns: Vec<String>,
+	members: Vec<WorkspaceMember>,
+	diagnostics: Vec<WorkspaceDiagnostic>,
+}
+
+impl Workspace {
+	/// Discover the workspace with the provided root directory and load the
+	/// `package.json` of every member.
+	///
+	/// # Errors
+	///
+	/// Returns an error when the root has neither a `package.json` nor a
+	/// `pnpm-workspace.yaml`, when a manifest can't be read or parsed, or when
+	/// a glob is invalid.
+	pub fn discover(root: impl AsRef<Path>) -> crate::Result<Self> {
+		let root = root.as_ref().to_path_buf();
+		let package_json_path = root.join("package.json");
+		let pnpm_workspace_path = root.join(PNPM_WORKSPACE_FILE);
+
+		let pnpm_workspace = if pnpm_workspace_path.is_file() {
+			let contents = read_file(&pnpm_workspace_path)?;
+			Some(PnpmWorkspace::try_from(contents.as_str())?)
+		} else {
+			None
+		};
+
+		let package_json = if package_json_path.is_file() || pnpm_workspace.is_none() {
+			read_package_json(&package_json_path)?
+		} else {
+			PackageJson::builder().build()
+		};
+
+		let patterns = match (&pnpm_workspace, &package_json.workspaces) {
+			(Some(pnpm_workspace), _) => {
+				pnpm_workspace
+					.packages
+					.clone()
+					.unwrap_or_else(|| PNPM_DEFAULT_PATTERNS.map(ToString::to_string).to_vec())
+			}
+			(None, Some(workspaces)) => workspaces.packages().to_vec(),
+			(None, None) => vec![],
+		};
+
+		let (members, unmatched) = find_members(&root, &patterns)?;
+		let mut diagnostics: Vec<WorkspaceDiagnostic> = unmatched
+			.into_iter()
+			.map(|pattern| WorkspaceDiagnostic::UnmatchedPattern { pattern })
+			.collect();
+
+		let mut names: IndexMap<&str, Vec<String>> = IndexMap::new();
+
+		for member in &members {
+			if let Some(name) = member.name() {
+				names
+					.entry(name)
+					.or_default()
+					.push(member.relative_path.clone());
+			}
+		}
+
+		diagnostics.extend(
+			names
+				.into_iter()
+				.filter(|(_, relative_paths)| relative_paths.len() > 1)
+				.map(|(name, relative_paths)| {
+					WorkspaceDiagnostic::DuplicateName {
+						name: name.to_string(),
+						relative_paths,
+					}
+				}),
+		);
+
+		Ok(Self {
+			root,
+			package_json,
+			pnpm: pnpm_workspace,
+			patterns,
+			members,
+			diagnostics,
+		})
+	}
+
+	/// The root directory of the workspace.
+	#[must_use]
+	pub fn root(&self) -> &Path {
+		&self.root
+	}
+
+	/// The root `package.json`. This is empty for a pnpm workspace without a
+	/// root manifest.
+	#[must_use]
+	pub const fn package_json(&self) -> &PackageJson {
+		&self.package_json
+	}
+
+	/// The parsed `pnpm-workspace.yaml`, when the workspace has one.
+	#[must_use]
+	pub const fn pnpm_workspace(&self) -> Option<&PnpmWorkspace> {
+		self.pnpm.as_ref()
+	}
+
+	/// The globs used to find the members.
+	#[must_use]
+	pub fn patterns(&self) -> &[String] {
+		&self.patterns
+	}
+
+	/// Every member ordered by its relative path. The root package is not a
+	/// member.
+	#[must_use]
+	pub fn members(&self) -> &[WorkspaceMember] {
+		&self.members
+	}
+
+	/// Find the first member with the provided package name.
+	#[must_use]
+	pub fn member(&self, name: &str) -> Option<&WorkspaceMember> {
+		self.members
+			.iter()
+			.find(|member| member.name() == Some(name))
+	}
+
+	/// The `package.json` at a directory relative to the root, using `/` as
+	/// the separator. `.` and the empty path are the root `package.json`.
+	#[must_use]
+	pub fn package_json_at(&self, relative_path: &str) -> Option<&PackageJson> {
+		let relative_path = relative_path.trim_start_matches("./").trim_end_matches('/');
+
+		if matches!(relative_path, "" | ".") {
+			return Some(&self.package_json);
+		}
+
+		self.members
+			.iter()
+			.find(|member| member.relative_path == relative_path)
+			.map(|member| &member.package_json)
+	}
+
+	/// The root `package.json` followed by the manifest of every member, each
+	/// with its path relative to the root. The root path is `.`.
+	#[must_use]
+	pub fn manifests(&self) -> Vec<(&str, &PackageJson)> {
+		std::iter::once((".", &self.package_json))
+			.chain(
+				self.members
+					.iter()
+					.map(|member| (member.relative_path.as_str(), &member.package_json)),
+			)
+			.collect()
+	}
+
+	/// The problems found while discovering the members.
+	#[must_use]
+	pub fn diagnostics(&self) -> &[WorkspaceDiagnostic] {
+		&self.diagnostics
+	}
+}
+
+/// Expand the globs, returning the members along with the globs which matched
+/// nothing.
+fn find_members(
+	root: &Path,
+	patterns: &[String],
+) -> crate::Result<(Vec<WorkspaceMember>, Vec<String>)> {
+	let mut includes = Vec::new();
+	let mut excludes = Vec::new();
+
+	// The root matches `.` but is never a member.
+	for pattern in patterns
+		.iter()
+		.filter(|pattern| normalize_pattern(pattern) != ".")
+	{
+		match pattern.strip_prefix('!') {
+			Some(negated) => excludes.push((pattern, build_glob(negated)?)),
+			None => includes.push((pattern, build_glob(pattern)?)),
+		}
+	}
+
+	let mut candidates: BTreeMap<String, PathBuf> = BTreeMap::new();
+	let mut unmatched = Vec::new();
+
+	for (pattern, glob) in &includes {
+		let normalized = normalize_pattern(pattern);
+		let segments: Vec<&str> = normalized.split('/').collect();
+		let literal = segments
+			.iter()
+			.take_while(|segment| !segment.contains(['*', '?', '[', '{']))
+			.count();
+		let base: PathBuf = segments[..literal].iter().collect();
+		let max_depth = if segments[literal..].contains(&"**") {
+			usize::MAX
+		} else {
+			segments.len() - literal
+		};
+
+		let mut matched = false;
+		let walker = WalkDir::new(root.join(&base))
+			.max_depth(max_depth)
+			.sort_by_file_name()
+			.into_iter()
+			.filter_entry(|entry| {
+				let name = entry.file_name().to_string_lossy();
+				entry.depth() == 0 || !(name == "node_modules" || name.starts_with('.'))
+			});
+
+		for entry in walker.filter_map(Result::ok) {
+			if !entry.file_type().is_dir() {
+				continue;
+			}
+
+			let Some(relative_path) = relative_path(root, entry.path()) else {
+				continue;
+			};
+
+			if relative_path.is_empty()
+				|| !glob.is_match(&relative_path)
+				|| !entry.path().join("package.json").is_file()
+			{
+				continue;
+			}
+
+			matched = true;
+			candidates.insert(relative_path, entry.into_path());
+		}
+
+		if !matched {
+			unmatched.push((*pattern).clone());
+		}
+	}
+
+	for (pattern, glob) in &excludes {
+		let before = candidates.len();
+		candidates.retain(|relative_path, _| !glob.is_match(relative_path));
+
+		if candidates.len() == before {
+			unmatched.push((*pattern).clone());
+		}
+	}
+
+	let members = candidates
+		.into_iter()
+		.map(|(relative_path, path)| {
+			let package_json = read_package_json(&path.join("package.json"))?;
+
+			Ok(WorkspaceMember {
+				path,
+				relative_path,
+				package_json,
+			})
+		})
+		.collect::<crate::Result<Vec<_>>>()?;
+
+	Ok((members, unmatched))
+}
+
+/// Remove a leading `./` and trailing slashes from a glob.
+fn normalize_pattern(pattern: &str) -> &str {
+	pattern.trim_start_matches("./").trim_end_matches('/')
+}
+
+pub(crate) fn build_glob(pattern: &str) -> crate::Result<globset::GlobMatcher> {
+	let glob: Glob = GlobBuilder::new(normalize_pattern(pattern))
+		.literal_separator(true)
+		.build()
+		.map_err(|source| {
+			crate::Error::InvalidGlob {
+				pattern: pattern.to_string(),
+				source,
+			}
+		})?;
+
+	Ok(glob.compile_matcher())
+}
+
+/// The path relative to the root with `/` separators.
+pub(crate) fn relative_path(root: &Path, path: &Path) -> Option<String> {
+	let relative = path.strip_prefix(root).ok()?;
+	let segments: Vec<_> = relative
+		.components()
+		.filter(|component| component != &Component::CurDir)
+		.map(|component| component.as_os_str().to_string_lossy())
+		.collect();
+
+	Some(segments.join("/"))
+}
+
+pub(crate) fn read_file(path: &Path) -> crate::Result<String> {
+	read_to_string(path).map_err(|source| {
+		crate::Error::ReadFile {
+			path: path.to_path_buf(),
+			source,
+		}
+	})
+}
+
+pub(crate) fn read_package_json(path: &Path) -> crate::Result<PackageJson> {
+	let contents = read_file(path)?;
+
+	serde_json::from_str(&contents).map_err(|source| {
+		crate::Error::ParsePackageJsonFile {
+			path: path.to_path_buf(),
+			source,
+		}
+	})
+}
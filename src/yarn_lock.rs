@@ -0,0 +1,609 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use typed_builder::TypedBuilder;
+
+use crate::AdditionalFields;
+use crate::DependencyKind;
+use crate::PackageJson;
+use crate::PeerDependencyMeta;
+
+/// The key of the metadata in a yarn berry lockfile.
+const BERRY_METADATA: &str = "__metadata";
+
+/// The comment written at the start of a yarn classic lockfile.
+const CLASSIC_HEADER: &str = concat!(
+	"# THIS IS AN AUTOGENERATED FILE. DO NOT EDIT THIS FILE DIRECTLY.\n",
+	"# yarn lockfile v1\n\n",
+);
+
+/// The comment written at the start of a yarn berry lockfile.
+const BERRY_HEADER: &str = concat!(
+	"# This file is generated by running \"yarn install\" inside your project.\n",
+	"# Manual changes might be lost - proceed with caution!\n",
+);
+
+/// The format of a [`YarnLock`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YarnLockFlavor {
+	/// The custom syntax of yarn 1.
+	Classic,
+	/// The YAML lockfile of yarn 2 and later.
+	Berry,
+}
+
+/// The `__metadata` of a yarn berry lockfile.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct YarnLockMetadata {
+	/// The version of the lockfile format.
+	pub version: Option<u32>,
+
+	/// The key of the cache the checksums were computed for.
+	pub cache_key: Option<String>,
+}
+
+/// A `yarn.lock` written by yarn classic or yarn berry.
+///
+/// Both formats are read into the same list of entries, each resolving one
+/// or more descriptors such as `lodash@^4.17.0` (classic) or
+/// `lodash@npm:^4.17.0` (berry).
+///
+/// ```
+/// use package_json_schema::YarnLock;
+///
+/// let contents = r#"
+/// ms@^2.1.1, ms@^2.1.2:
+///   version "2.1.3"
+///   integrity sha512-abc
+/// "#;
+///
+/// let yarn_lock = YarnLock::try_from(contents).unwrap();
+/// let entry = yarn_lock.find("ms", "^2.1.2").unwrap();
+///
+/// assert_eq!(entry.version.as_deref(), Some("2.1.3"));
+/// assert_eq!(entry.descriptors.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct YarnLock {
+	flavor: YarnLockFlavor,
+	metadata: Option<YarnLockMetadata>,
+	entries: Vec<YarnLockEntry>,
+	descriptors: IndexMap<String, usize>,
+}
+
+impl TryFrom<&str> for YarnLock {
+	type Error = crate::error::Error;
+
+	fn try_from(value: &str) -> Result<Self, Self::Error> {
+		let is_berry = value
+			.lines()
+			.any(|line| line.trim_end() == format!("{BERRY_METADATA}:"));
+
+		if is_berry {
+			parse_berry(value)
+		} else {
+			parse_classic(value)
+		}
+	}
+}
+
+impl TryFrom<String> for YarnLock {
+	type Error = crate::error::Error;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		Self::try_from(value.as_str())
+	}
+}
+
+impl YarnLock {
+	/// Create a lockfile from its entries, e.g. to write a converted
+	/// lockfile with [`YarnLock::try_to_string`].
+	#[must_use]
+	pub fn new(
+		flavor: YarnLockFlavor,
+		metadata: Option<YarnLockMetadata>,
+		entries: Vec<YarnLockEntry>,
+	) -> Self {
+		let descriptors = entries
+			.iter()
+			.enumerate()
+			.flat_map(|(index, entry)| {
+				entry
+					.descriptors
+					.iter()
+					.map(move |descriptor| (descriptor.clone(), index))
+			})
+			.collect();
+
+		Self {
+			flavor,
+			metadata,
+			entries,
+			descriptors,
+		}
+	}
+
+	/// Write the lockfile in the syntax of its flavor. Unknown fields of the
+	/// entries are kept, except lists in the classic syntax.
+	///
+	/// # Errors
+	///
+	/// Returns [`crate::Error::SerializeYarnLock`] when an entry can't be
+	/// converted to YAML.
+	pub fn try_to_string(&self) -> crate::Result<String> {
+		match self.flavor {
+			YarnLockFlavor::Classic => write_classic(&self.entries),
+			YarnLockFlavor::Berry => write_berry(self.metadata.as_ref(), &self.entries),
+		}
+	}
+
+	/// The format the lockfile was written in.
+	#[must_use]
+	pub const fn flavor(&self) -> YarnLockFlavor {
+		self.flavor
+	}
+
+	/// The `__metadata` of a berry lockfile.
+	#[must_use]
+	pub const fn metadata(&self) -> Option<&YarnLockMetadata> {
+		self.metadata.as_ref()
+	}
+
+	/// Every entry in the order of the lockfile.
+	#[must_use]
+	pub fn entries(&self) -> &[YarnLockEntry] {
+		&self.entries
+	}
+
+	/// Find the entry which resolves a dependency declared in a
+	/// `package.json`. Berry lockfiles store plain ranges with the `npm:`
+	/// protocol, which is added when needed.
+	#[must_use]
+	pub fn find(&self, name: &str, spec: &str) -> Option<&YarnLockEntry> {
+		let index = self
+			.descriptors
+			.get(&format!("{name}@{spec}"))
+			.or_else(|| {
+				(self.flavor == YarnLockFlavor::Berry)
+					.then(|| self.descriptors.get(&format!("{name}@npm:{spec}")))
+					.flatten()
+			})?;
+
+		self.entries.get(*index)
+	}
+
+	/// The entry of every dependency of a `package.json`, except the
+	/// `peerDependencies` which yarn doesn't install for the project itself.
+	#[must_use]
+	pub fn locked_dependencies<'a>(
+		&'a self,
+		package_json: &'a PackageJson,
+	) -> Vec<YarnLockedDependency<'a>> {
+		package_json
+			.all_dependencies()
+			.filter(|(kind, ..)| *kind != DependencyKind::Peer)
+			.map(|(kind, name, spec)| {
+				YarnLockedDependency {
+					kind,
+					name,
+					spec,
+					entry: self.find(name, spec),
+				}
+			})
+			.collect()
+	}
+}
+
+/// A dependency of a `package.json` with its [`YarnLock`] entry, when the
+/// lockfile has one.
+#[derive(Debug, Clone, Copy)]
+pub struct YarnLockedDependency<'a> {
+	/// The field which declares the dependency.
+	pub kind: DependencyKind,
+
+	/// The name of the dependency.
+	pub name: &'a str,
+
+	/// The declared spec.
+	pub spec: &'a str,
+
+	/// The entry which resolves the spec.
+	pub entry: Option<&'a YarnLockEntry>,
+}
+
+/// An entry of a [`YarnLock`], shared by every descriptor it resolves.
+#[derive(TypedBuilder, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct YarnLockEntry {
+	/// The descriptors resolved by this entry, e.g. `lodash@^4.17.0` or
+	/// `lodash@npm:^4.17.0`.
+	#[serde(skip)]
+	#[builder(default, setter(into))]
+	pub descriptors: Vec<String>,
+
+	/// The resolved version.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub version: Option<String>,
+
+	/// The url of the tarball, only used by yarn classic.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub resolved: Option<String>,
+
+	/// The subresource integrity of the tarball, only used by yarn classic.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub integrity: Option<String>,
+
+	/// The locator of the package, e.g. `lodash@npm:4.17.21`, only used by
+	/// yarn berry.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub resolution: Option<String>,
+
+	/// The checksum of the package archive in the cache, only used by yarn
+	/// berry.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub checksum: Option<String>,
+
+	/// Either `node` for packages or `unknown` for workspaces.
+	#[serde(
+		default,
+		rename = "languageName",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub language_name: Option<String>,
+
+	/// Either `hard` for fetched packages or `soft` for links and
+	/// workspaces.
+	#[serde(default, rename = "linkType", skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub link_type: Option<String>,
+
+	/// The dependencies with the ranges they were required with.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub dependencies: Option<IndexMap<String, String>>,
+
+	/// The optional dependencies, only used by yarn classic.
+	#[serde(
+		default,
+		rename = "optionalDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub optional_dependencies: Option<IndexMap<String, String>>,
+
+	/// The peer dependencies, only used by yarn berry.
+	#[serde(
+		default,
+		rename = "peerDependencies",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub peer_dependencies: Option<IndexMap<String, String>>,
+
+	/// Extra information about the peer dependencies.
+	#[serde(
+		default,
+		rename = "peerDependenciesMeta",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub peer_dependencies_meta: Option<IndexMap<String, PeerDependencyMeta>>,
+
+	/// Installation settings of the dependencies, such as `optional`.
+	#[serde(
+		default,
+		rename = "dependenciesMeta",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[builder(default, setter(into, strip_option))]
+	pub dependencies_meta: Option<Value>,
+
+	/// The executables of the package.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub bin: Option<IndexMap<String, String>>,
+
+	/// The platforms supported by the package, e.g. `os=darwin`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub conditions: Option<String>,
+
+	/// All addition field.
+	#[serde(flatten, skip_serializing_if = "Option::is_none")]
+	#[builder(default, setter(into, strip_option))]
+	pub other: Option<AdditionalFields>,
+}
+
+impl YarnLockEntry {
+	/// The name of the package, read from the first descriptor.
+	#[must_use]
+	pub fn name(&self) -> Option<&str> {
+		self.descriptors
+			.first()
+			.and_then(|descriptor| parse_yarn_descriptor(descriptor))
+			.map(|(name, _)| name)
+	}
+}
+
+/// Split a descriptor into the package name and the range, e.g.
+/// `@babel/core@npm:^7.0.0` into `@babel/core` and `npm:^7.0.0`.
+///
+/// ```
+/// use package_json_schema::parse_yarn_descriptor;
+///
+/// let descriptor = parse_yarn_descriptor("@babel/core@npm:^7.0.0");
+///
+/// assert_eq!(descriptor, Some(("@babel/core", "npm:^7.0.0")));
+/// ```
+#[must_use]
+pub fn parse_yarn_descriptor(descriptor: &str) -> Option<(&str, &str)> {
+	let index = descriptor.get(1..)?.find('@')? + 1;
+
+	Some((&descriptor[..index], &descriptor[index + 1..]))
+}
+
+fn write_berry(
+	metadata: Option<&YarnLockMetadata>,
+	entries: &[YarnLockEntry],
+) -> crate::Result<String> {
+	let metadata = metadata.cloned().unwrap_or_default();
+	let mut lines = vec![
+		BERRY_HEADER.to_string(),
+		format!("{BERRY_METADATA}:"),
+		format!("  version: {}", metadata.version.unwrap_or(8)),
+	];
+
+	if let Some(cache_key) = metadata.cache_key {
+		lines.push(format!("  cacheKey: {cache_key}"));
+	}
+
+	for entry in entries {
+		let yaml = serde_yaml::to_string(entry).map_err(crate::Error::SerializeYarnLock)?;
+
+		lines.push(String::new());
+		lines.push(format!("\"{}\":", entry.descriptors.join(", ")));
+		lines.extend(yaml.lines().map(|line| format!("  {line}")));
+	}
+
+	Ok(lines.join("\n") + "\n")
+}
+
+/// Write every field of the entries in the classic syntax. Lists, which
+/// the syntax can't express, are dropped.
+fn write_classic(entries: &[YarnLockEntry]) -> crate::Result<String> {
+	let mut lines = vec![CLASSIC_HEADER.to_string()];
+
+	for entry in entries {
+		let descriptors: Vec<String> = entry
+			.descriptors
+			.iter()
+			.map(|descriptor| quote(descriptor))
+			.collect();
+		let value = serde_yaml::to_value(entry).map_err(crate::Error::SerializeYarnLock)?;
+
+		lines.push(String::new());
+		lines.push(format!("{}:", descriptors.join(", ")));
+
+		if let serde_yaml::Value::Mapping(fields) = &value {
+			write_classic_fields(&mut lines, fields, 1);
+		}
+	}
+
+	Ok(lines.join("\n") + "\n")
+}
+
+fn write_classic_fields(lines: &mut Vec<String>, fields: &serde_yaml::Mapping, depth: usize) {
+	let indent = "  ".repeat(depth);
+
+	for (key, value) in fields {
+		let Some(key) = key.as_str() else {
+			continue;
+		};
+		let key = quote(key);
+
+		match value {
+			serde_yaml::Value::String(value) => {
+				lines.push(format!("{indent}{key} {}", quote(value)));
+			}
+			serde_yaml::Value::Bool(value) => lines.push(format!("{indent}{key} {value}")),
+			serde_yaml::Value::Number(value) => lines.push(format!("{indent}{key} {value}")),
+			serde_yaml::Value::Mapping(map) if !map.is_empty() => {
+				lines.push(format!("{indent}{key}:"));
+				write_classic_fields(lines, map, depth + 1);
+			}
+			_ => {}
+		}
+	}
+}
+
+/// Quote a classic value the way yarn does, unless it is a plain word.
+fn quote(value: &str) -> String {
+	let plain = value.starts_with(|char: char| char.is_ascii_alphabetic())
+		&& !value.starts_with("true")
+		&& !value.starts_with("false")
+		&& !value.contains(|char: char| char.is_whitespace() || ":\\\",[]".contains(char));
+
+	if plain {
+		value.to_string()
+	} else {
+		format!("\"{}\"", value.replace('"', "\\\""))
+	}
+}
+
+fn parse_berry(contents: &str) -> crate::Result<YarnLock> {
+	let document: IndexMap<String, serde_yaml::Value> =
+		serde_yaml::from_str(contents).map_err(crate::Error::ParseYarnLock)?;
+	let mut metadata = None;
+	let mut entries = Vec::new();
+
+	for (key, value) in document {
+		if key == BERRY_METADATA {
+			let scalar = |field: &str| {
+				match value.get(field) {
+					Some(serde_yaml::Value::String(value)) => Some(value.clone()),
+					Some(serde_yaml::Value::Number(value)) => Some(value.to_string()),
+					_ => None,
+				}
+			};
+
+			metadata = Some(YarnLockMetadata {
+				version: scalar("version").and_then(|version| version.parse().ok()),
+				cache_key: scalar("cacheKey"),
+			});
+			continue;
+		}
+
+		let mut entry: YarnLockEntry =
+			serde_yaml::from_value(value).map_err(crate::Error::ParseYarnLock)?;
+		entry.descriptors = split_descriptors(&key);
+		entries.push(entry);
+	}
+
+	Ok(YarnLock::new(YarnLockFlavor::Berry, metadata, entries))
+}
+
+/// Parse the custom syntax of yarn classic. Entries start with their
+/// descriptors at the top level, followed by their fields and the entries of
+/// nested maps such as `dependencies`, each indented further than the line
+/// above. Yarn writes two spaces per level but any consistent indentation,
+/// including tabs, is accepted.
+fn parse_classic(contents: &str) -> crate::Result<YarnLock> {
+	let mut entries = Vec::new();
+	let mut values: AdditionalFields = IndexMap::new();
+	let mut descriptors = Vec::new();
+	let mut entry_line = 0;
+	let mut nested: Option<String> = None;
+	let mut field_indent: Option<usize> = None;
+	let mut nested_indent: Option<usize> = None;
+
+	let invalid = |line: usize, reason: &str| {
+		crate::Error::InvalidYarnLock {
+			line,
+			reason: reason.to_string(),
+		}
+	};
+
+	for (index, line) in contents.lines().enumerate() {
+		let number = index + 1;
+		let trimmed = line.trim_start();
+
+		if trimmed.is_empty() || trimmed.starts_with('#') {
+			continue;
+		}
+
+		let indent = line.len() - trimmed.len();
+		let trimmed = trimmed.trim_end();
+
+		if indent == 0 {
+			let Some(keys) = trimmed.strip_suffix(':') else {
+				return Err(invalid(number, "expected the descriptors of an entry"));
+			};
+
+			if !descriptors.is_empty() {
+				entries.push(classic_entry(descriptors, values, entry_line)?);
+			}
+
+			descriptors = split_descriptors(keys);
+			entry_line = number;
+			values = IndexMap::new();
+			nested = None;
+			field_indent = None;
+			continue;
+		}
+
+		if descriptors.is_empty() {
+			return Err(invalid(number, "unexpected indentation"));
+		}
+
+		let field_indent = *field_indent.get_or_insert(indent);
+
+		if indent == field_indent {
+			if let Some(key) = trimmed.strip_suffix(':') {
+				let key = unquote(key);
+				values.insert(key.clone(), Value::Object(serde_json::Map::new()));
+				nested = Some(key);
+				nested_indent = None;
+			} else {
+				let (key, value) = split_classic_field(trimmed)
+					.ok_or_else(|| invalid(number, "expected a key and a value"))?;
+				values.insert(key, value);
+				nested = None;
+			}
+
+			continue;
+		}
+
+		let map = nested
+			.as_ref()
+			.filter(|_| indent > field_indent && *nested_indent.get_or_insert(indent) == indent)
+			.and_then(|key| values.get_mut(key))
+			.and_then(Value::as_object_mut)
+			.ok_or_else(|| invalid(number, "unexpected indentation"))?;
+		let (key, value) = split_classic_field(trimmed)
+			.ok_or_else(|| invalid(number, "expected a key and a value"))?;
+		map.insert(key, value);
+	}
+
+	if !descriptors.is_empty() {
+		entries.push(classic_entry(descriptors, values, entry_line)?);
+	}
+
+	Ok(YarnLock::new(YarnLockFlavor::Classic, None, entries))
+}
+
+/// Build an entry from its fields, reporting errors at the line of its
+/// descriptors.
+fn classic_entry(
+	descriptors: Vec<String>,
+	values: AdditionalFields,
+	line: usize,
+) -> crate::Result<YarnLockEntry> {
+	let value = Value::Object(values.into_iter().collect());
+	let mut entry: YarnLockEntry = serde_json::from_value(value).map_err(|error| {
+		crate::Error::InvalidYarnLock {
+			line,
+			reason: error.to_string(),
+		}
+	})?;
+	entry.descriptors = descriptors;
+
+	Ok(entry)
+}
+
+/// Split a line like `version "1.0.0"` or `"@scope/a" "^1.0.0"`.
+fn split_classic_field(line: &str) -> Option<(String, Value)> {
+	let (key, value) = if let Some(rest) = line.strip_prefix('"') {
+		let end = rest.find('"')?;
+		(&rest[..end], rest[end + 1..].trim_start())
+	} else {
+		line.split_once(' ')?
+	};
+
+	let value = match value {
+		"true" => Value::Bool(true),
+		"false" => Value::Bool(false),
+		value => Value::String(unquote(value)),
+	};
+
+	Some((key.to_string(), value))
+}
+
+fn split_descriptors(keys: &str) -> Vec<String> {
+	keys.split(", ").map(unquote).collect()
+}
+
+fn unquote(value: &str) -> String {
+	let value = value.trim();
+
+	value
+		.strip_prefix('"')
+		.and_then(|value| value.strip_suffix('"'))
+		.unwrap_or(value)
+		.replace("\\\"", "\"")
+}
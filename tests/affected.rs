@@ -0,0 +1,78 @@
+use package_json_schema::AffectedOptions;
+use package_json_schema::Workspace;
+
+#[test]
+fn separate_published_and_test_only_changes() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/graph").unwrap();
+	let files = [
+		"packages/core/test/index.test.js",
+		"./packages/core/dist/index.js.map",
+		"readme.md",
+		"packages/core/package.json",
+	];
+
+	let affected = workspace
+		.affected(&files[..3], AffectedOptions::default())
+		.unwrap();
+	assert_eq!(affected.names(), ["@graph/core"]);
+	assert!(affected.members[0].is_test_only());
+	assert_eq!(
+		affected.members[0].unpublished_files,
+		[
+			"packages/core/test/index.test.js",
+			"packages/core/dist/index.js.map"
+		]
+	);
+	assert_eq!(affected.unowned_files, ["readme.md"]);
+
+	let affected = workspace
+		.affected(&files, AffectedOptions::default())
+		.unwrap();
+	assert_eq!(
+		affected.names(),
+		["@graph/app", "@graph/core", "@graph/legacy", "@graph/ui"]
+	);
+	assert_eq!(
+		affected.members[1].published_files,
+		["packages/core/package.json"]
+	);
+	assert!(!affected.members[1].via_dependency);
+}
+
+#[test]
+fn only_follow_published_changes_to_dependents() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/graph").unwrap();
+	let affected = workspace
+		.affected(&["packages/config/index.js"], AffectedOptions::default())
+		.unwrap();
+	let dependents: Vec<_> = affected
+		.members
+		.iter()
+		.filter(|member| member.via_dependency)
+		.filter_map(|member| member.member.name())
+		.collect();
+
+	assert_eq!(
+		affected.names(),
+		["@graph/app", "@graph/config", "@graph/ui"]
+	);
+	assert_eq!(dependents, ["@graph/app", "@graph/ui"]);
+}
+
+#[test]
+fn optionally_follow_dev_dependencies() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/cycle").unwrap();
+	let files = [workspace.root().join("packages/a/index.js")];
+
+	let affected = workspace
+		.affected(&files, AffectedOptions::default())
+		.unwrap();
+	assert_eq!(affected.names(), ["a"]);
+
+	let options = AffectedOptions::builder()
+		.include_dev_dependents(true)
+		.build();
+	let affected = workspace.affected(&files, options).unwrap();
+	assert_eq!(affected.names(), ["a", "b", "c"]);
+	assert!(affected.members[0].via_dependency);
+}
@@ -0,0 +1,97 @@
+use std::fs::read_to_string;
+
+use package_json_schema::BunLock;
+use package_json_schema::BunPackage;
+use package_json_schema::Workspace;
+
+fn load() -> BunLock {
+	let contents = read_to_string("./tests/fixtures/lockfiles/bun/bun.lock").unwrap();
+	BunLock::try_from(contents).unwrap()
+}
+
+#[test]
+fn parse_packages() {
+	let bun_lock = load();
+	let debug = &bun_lock.packages["debug"];
+	let info = debug.info().unwrap();
+	let lib = &bun_lock.packages["lib"];
+
+	assert_eq!(bun_lock.lockfile_version, Some(1));
+	assert_eq!(
+		bun_lock.trusted_dependencies.as_deref(),
+		Some(&["fsevents".to_string()][..])
+	);
+	assert_eq!(debug.name(), Some("debug"));
+	assert_eq!(debug.version(), Some("4.3.4"));
+	assert!(debug.integrity().unwrap().starts_with("sha512-PRWF"));
+	assert_eq!(info.dependencies.unwrap()["ms"], "2.1.2");
+	assert_eq!(lib.resolution(), Some("lib@workspace:packages/lib"));
+	assert_eq!(lib.version(), None);
+	assert_eq!(lib.integrity(), None);
+	assert_eq!(
+		bun_lock.packages["fsevents"].info().unwrap().os,
+		Some(serde_json::json!("darwin"))
+	);
+
+	let github: BunPackage = serde_json::from_value(serde_json::json!([
+		"ui@github:shadcn-ui/ui#abc1234",
+		{},
+		"shadcn-ui-ui-abc1234"
+	]))
+	.unwrap();
+	assert_eq!(github.integrity(), None);
+}
+
+#[test]
+fn locked_dependencies_of_members() {
+	let bun_lock = load();
+	let workspace = Workspace::discover("./tests/fixtures/lockfiles/bun").unwrap();
+	let linked = bun_lock.workspace_package_jsons(&workspace);
+
+	assert_eq!(linked.len(), 2);
+	assert_eq!(linked[0].1.unwrap().name.as_deref(), Some("bun-fixture"));
+	assert_eq!(linked[1].0, "packages/lib");
+	assert_eq!(linked[1].1.unwrap().name.as_deref(), Some("lib"));
+
+	let root: Vec<_> = bun_lock
+		.locked_dependencies(workspace.package_json())
+		.into_iter()
+		.map(|locked| {
+			(
+				locked.name,
+				locked.key.unwrap(),
+				locked.package.unwrap().version(),
+			)
+		})
+		.collect();
+	assert_eq!(
+		root,
+		[
+			("debug", "debug", Some("4.3.4")),
+			("lib", "lib", None),
+			("typescript", "typescript", Some("5.4.5")),
+			("fsevents", "fsevents", Some("2.3.3")),
+		]
+	);
+
+	let lib = bun_lock.locked_dependencies(linked[1].1.unwrap());
+	assert_eq!(lib[0].key, Some("lib/ms"));
+	assert_eq!(lib[0].package.unwrap().version(), Some("2.0.0"));
+}
+
+#[test]
+fn jsonc_syntax() {
+	let contents = r#"{
+		/* block comment with "quotes", and commas, */
+		"workspaces": { "": { "name": "a // not a comment", }, },
+		// line comment
+		"packages": {},
+	}"#;
+	let bun_lock = BunLock::try_from(contents).unwrap();
+
+	assert_eq!(
+		bun_lock.workspaces[""].name.as_deref(),
+		Some("a // not a comment")
+	);
+	assert!(BunLock::try_from("{ \"packages\": [ }").is_err());
+}
@@ -0,0 +1,132 @@
+use package_json_schema::CatalogEdit;
+use package_json_schema::CatalogProblem;
+use package_json_schema::DependencyKind;
+use package_json_schema::Error;
+use package_json_schema::PnpmWorkspace;
+use package_json_schema::Workspace;
+
+#[test]
+fn resolve_catalog_dependencies() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/catalog").unwrap();
+	let catalogs = workspace.catalogs().unwrap();
+	assert_eq!(catalogs.get("default", "react"), Some("^18.2.0"));
+	assert_eq!(catalogs.get("legacy", "react"), Some("^16.14.0"));
+
+	let mut app = workspace.member("app").unwrap().package_json.clone();
+	assert!(catalogs.resolve(&mut app).is_empty());
+	assert_eq!(app.dependencies.unwrap()["react"], "^18.2.0");
+
+	let mut lib = workspace.member("lib").unwrap().package_json.clone();
+	let diagnostics = catalogs.resolve(&mut lib);
+	let problems: Vec<_> = diagnostics
+		.iter()
+		.map(|diagnostic| (diagnostic.name.as_str(), &diagnostic.problem))
+		.collect();
+
+	assert_eq!(
+		problems,
+		[
+			(
+				"vitest",
+				&CatalogProblem::UnknownCatalog {
+					catalog: "testing".into()
+				}
+			),
+			(
+				"left-pad",
+				&CatalogProblem::UnknownEntry {
+					catalog: "default".into()
+				}
+			),
+		]
+	);
+	assert_eq!(
+		diagnostics[0].to_string(),
+		"`vitest@catalog:testing` in devDependencies refers to the unknown catalog `testing`"
+	);
+	assert_eq!(lib.peer_dependencies.unwrap()["react"], "^16.14.0");
+	assert_eq!(lib.dev_dependencies.unwrap()["vitest"], "catalog:testing");
+}
+
+#[test]
+fn publish_with_catalog_ranges() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/catalog").unwrap();
+
+	let app = &workspace.member("app").unwrap().package_json;
+	let manifest = app.to_publish_manifest(&workspace).unwrap();
+	let dependencies = manifest.dependencies.unwrap();
+	assert_eq!(dependencies["react"], "^18.2.0");
+	assert_eq!(dependencies["lib"], "1.0.0");
+
+	let lib = &workspace.member("lib").unwrap().package_json;
+	let error = lib.to_publish_manifest(&workspace).unwrap_err();
+	assert!(matches!(
+		error,
+		Error::UnresolvedCatalogDependency { ref name, .. } if name == "vitest"
+	));
+}
+
+#[test]
+fn reject_duplicate_default_catalogs() {
+	let yaml = "catalog:\n  react: ^18.0.0\ncatalogs:\n  default:\n    react: ^17.0.0\n";
+	let pnpm_workspace = PnpmWorkspace::try_from(yaml).unwrap();
+	let error = package_json_schema::Catalogs::new(&pnpm_workspace).unwrap_err();
+
+	assert!(matches!(error, Error::DuplicateDefaultCatalog));
+}
+
+#[test]
+fn migrate_repeated_ranges_into_the_catalog() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/catalog").unwrap();
+	let migration = workspace.catalog_migration(2).unwrap();
+
+	let catalog: Vec<_> = migration
+		.catalog
+		.iter()
+		.map(|(name, range)| (name.as_str(), range.as_str()))
+		.collect();
+	assert_eq!(
+		catalog,
+		[
+			("lodash", "^4.17.21"),
+			("zod", "^3.22.0"),
+			("typescript", "^5.4.0")
+		]
+	);
+
+	let edits: Vec<_> = migration
+		.edits
+		.iter()
+		.map(|edit| (edit.member, edit.kind, edit.name.as_str()))
+		.collect();
+	assert_eq!(
+		edits,
+		[
+			(0, DependencyKind::Prod, "lodash"),
+			(0, DependencyKind::Prod, "zod"),
+			(0, DependencyKind::Dev, "typescript"),
+			(1, DependencyKind::Prod, "lodash"),
+			(1, DependencyKind::Dev, "typescript"),
+			(2, DependencyKind::Prod, "zod"),
+		]
+	);
+
+	let mut web = workspace.members()[2].package_json.clone();
+	migration.apply(2, &mut web);
+	let dependencies = web.dependencies.unwrap();
+	assert_eq!(dependencies["zod"], "catalog:");
+	assert_eq!(dependencies["lodash"], "4.17.20");
+
+	let mut pnpm_workspace = workspace.pnpm_workspace().unwrap().clone();
+	migration.extend_catalog(&mut pnpm_workspace);
+	assert_eq!(pnpm_workspace.catalog.unwrap().len(), 4);
+
+	let everything = workspace.catalog_migration(1).unwrap();
+	assert!(everything.catalog.get("react").is_none());
+	assert!(everything.edits.contains(&CatalogEdit {
+		member: 2,
+		kind: DependencyKind::Prod,
+		name: "react".into(),
+		range: "^18.2.0".into(),
+	}));
+}
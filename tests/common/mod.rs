@@ -0,0 +1,6 @@
+use std::fs::read_to_string;
+
+/// Read a file below `tests/fixtures/lockfiles`.
+pub fn read(path: &str) -> String {
+	read_to_string(format!("./tests/fixtures/lockfiles/{path}")).unwrap()
+}
@@ -0,0 +1,94 @@
+use indexmap::IndexMap;
+use package_json_schema::ConsistencyOptions;
+use package_json_schema::DependencyKind;
+use package_json_schema::Workspace;
+
+fn fixes(workspace: &Workspace, options: &ConsistencyOptions) -> Vec<(String, String, String)> {
+	workspace
+		.check_consistency(options)
+		.unwrap()
+		.fixes()
+		.into_iter()
+		.map(|fix| (fix.dependent.clone(), fix.name.clone(), fix.to.clone()))
+		.collect()
+}
+
+fn fix(dependent: &str, name: &str, to: &str) -> (String, String, String) {
+	(dependent.into(), name.into(), to.into())
+}
+
+#[test]
+fn use_the_highest_range() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/consistency").unwrap();
+	let report = workspace
+		.check_consistency(&ConsistencyOptions::default())
+		.unwrap();
+	let mismatches: Vec<_> = report
+		.mismatches
+		.iter()
+		.map(|mismatch| (mismatch.name.as_str(), mismatch.expected.as_str()))
+		.collect();
+
+	assert!(!report.is_consistent());
+	assert_eq!(
+		mismatches,
+		[
+			("react", "^18.2.0"),
+			("@types/node", "^20.0.0"),
+			("typescript", "5.4.2"),
+			("eslint", "^8.57.0")
+		]
+	);
+	assert_eq!(report.mismatches[0].usages.len(), 3);
+	assert_eq!(
+		fixes(&workspace, &ConsistencyOptions::default()),
+		[
+			fix("b", "react", "^18.2.0"),
+			fix("b", "@types/node", "^20.0.0"),
+			fix("b", "typescript", "5.4.2"),
+			fix("c", "typescript", "5.4.2"),
+			fix("c", "eslint", "^8.57.0"),
+			fix("c", "react", "^18.2.0"),
+		]
+	);
+
+	let mut b = workspace.members()[1].package_json.clone();
+	report.apply(1, &mut b);
+	assert_eq!(b.dependencies.as_ref().unwrap()["react"], "^18.2.0");
+	assert_eq!(b.dependencies.as_ref().unwrap()["a"], "^1.0.0");
+	assert_eq!(b.dev_dependencies.unwrap()["typescript"], "5.4.2");
+}
+
+#[test]
+fn apply_consistency_policies() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/consistency").unwrap();
+	let options = ConsistencyOptions::builder()
+		.pins(IndexMap::from([(
+			"typescript".to_string(),
+			"5.4.5".to_string(),
+		)]))
+		.ignore(vec!["@types/*".to_string()])
+		.allow_wider_peer_ranges(true)
+		.build();
+
+	assert_eq!(
+		fixes(&workspace, &options),
+		[
+			fix("a", "typescript", "5.4.5"),
+			fix("b", "react", "^18.2.0"),
+			fix("b", "typescript", "5.4.5"),
+			fix("c", "typescript", "5.4.5"),
+			fix("c", "eslint", "^8.57.0"),
+		]
+	);
+
+	let report = workspace.check_consistency(&options).unwrap();
+	let react = &report.mismatches[0];
+	assert_eq!(react.fixes.len(), 1);
+	assert_eq!(react.usages[2].kind, DependencyKind::Peer);
+
+	let invalid = ConsistencyOptions::builder()
+		.ignore(vec!["[".to_string()])
+		.build();
+	assert!(workspace.check_consistency(&invalid).is_err());
+}
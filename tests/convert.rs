@@ -0,0 +1,213 @@
+use common::read;
+use package_json_schema::ConversionIssue;
+use package_json_schema::LockfileGraph;
+use package_json_schema::PackageJson;
+use package_json_schema::PackageLock;
+use package_json_schema::PnpmImporterDependency;
+use package_json_schema::PnpmLock;
+use package_json_schema::Workspace;
+use package_json_schema::YarnLock;
+use package_json_schema::YarnLockFlavor;
+
+mod common;
+
+fn npm_graph() -> (LockfileGraph, PackageJson) {
+	let package_lock = PackageLock::try_from(read("npm-v3/package-lock.json")).unwrap();
+	let package_json = PackageJson::try_from(read("npm-v3/package.json")).unwrap();
+
+	(
+		LockfileGraph::from_package_lock(&package_lock),
+		package_json,
+	)
+}
+
+#[test]
+fn npm_to_pnpm() {
+	let (graph, package_json) = npm_graph();
+	let conversion = graph.to_pnpm_lock(&[(".", &package_json)]);
+	let contents = conversion.lockfile.try_to_string().unwrap();
+	let pnpm_lock = PnpmLock::try_from(contents).unwrap();
+	let importer = &pnpm_lock.importers.as_ref().unwrap()["."];
+	let debug = &importer.dependencies.as_ref().unwrap()["debug"];
+
+	assert!(matches!(
+		debug,
+		PnpmImporterDependency::Resolved { specifier, version }
+			if specifier == "^4.3.4" && version == "4.3.4"
+	));
+	assert_eq!(
+		pnpm_lock.packages.as_ref().unwrap()["debug@4.3.4"]
+			.resolution
+			.as_ref()
+			.unwrap()
+			.integrity,
+		graph.package("node_modules/debug").unwrap().integrity
+	);
+	assert_eq!(
+		pnpm_lock.snapshots.as_ref().unwrap()["debug@4.3.4"]
+			.dependencies
+			.as_ref()
+			.unwrap()["ms"],
+		"2.1.2"
+	);
+
+	let reparsed = LockfileGraph::from_pnpm_lock(&pnpm_lock);
+	assert!(reparsed.check_sync(&[(".", &package_json)]).is_in_sync());
+}
+
+#[test]
+fn pnpm_workspace_to_npm() {
+	let pnpm_lock = PnpmLock::try_from(read("pnpm/pnpm-lock.yaml")).unwrap();
+	let workspace = Workspace::discover("./tests/fixtures/lockfiles/pnpm").unwrap();
+	let manifests = workspace.manifests();
+	let graph = LockfileGraph::from_pnpm_lock(&pnpm_lock);
+	let conversion = graph.to_package_lock(&manifests);
+	let package_lock = &conversion.lockfile;
+	let lib = package_lock.package("node_modules/lib").unwrap();
+	let app = package_lock.package("packages/app").unwrap();
+	let nested = package_lock
+		.package("node_modules/debug/node_modules/ms")
+		.unwrap();
+
+	assert_eq!(lib.resolved.as_deref(), Some("packages/lib"));
+	assert_eq!(lib.link, Some(true));
+	assert_eq!(app.dependencies.as_ref().unwrap()["lib"], "workspace:*");
+	assert_eq!(
+		package_lock
+			.package("node_modules/ms")
+			.unwrap()
+			.version
+			.as_deref(),
+		Some("2.1.3")
+	);
+	assert_eq!(nested.version.as_deref(), Some("2.1.2"));
+	assert!(nested.integrity.is_some());
+	assert_eq!(
+		package_lock.package("node_modules/typescript").unwrap().dev,
+		Some(true)
+	);
+	assert!(conversion.issues.iter().all(|issue| {
+		matches!(issue, ConversionIssue::MissingField { field, .. } if *field == "resolved")
+	}));
+
+	let reparsed = LockfileGraph::from_package_lock(package_lock);
+	assert!(reparsed.check_sync(&manifests).is_in_sync());
+}
+
+#[test]
+fn npm_to_yarn() {
+	let (graph, package_json) = npm_graph();
+
+	for flavor in [YarnLockFlavor::Classic, YarnLockFlavor::Berry] {
+		let conversion = graph.to_yarn_lock(&[(".", &package_json)], flavor);
+		let contents = conversion.lockfile.try_to_string().unwrap();
+		let yarn_lock = YarnLock::try_from(contents).unwrap();
+		let debug = yarn_lock.find("debug", "^4.3.4").unwrap();
+		let reparsed = LockfileGraph::from_yarn_lock(&yarn_lock, &[(".", &package_json)]);
+
+		assert_eq!(yarn_lock.flavor(), flavor);
+		assert_eq!(debug.version.as_deref(), Some("4.3.4"), "{flavor:?}");
+		assert!(
+			reparsed.check_sync(&[(".", &package_json)]).is_in_sync(),
+			"{flavor:?}"
+		);
+
+		if flavor == YarnLockFlavor::Classic {
+			assert_eq!(
+				debug.integrity,
+				graph.package("node_modules/debug").unwrap().integrity
+			);
+			assert!(conversion.issues.contains(&ConversionIssue::DroppedField {
+				package: "fsevents@2.3.3".into(),
+				field: "os",
+			}));
+		} else {
+			assert_eq!(debug.resolution.as_deref(), Some("debug@npm:4.3.4"));
+			assert!(conversion.issues.contains(&ConversionIssue::DroppedField {
+				package: "debug@4.3.4".into(),
+				field: "integrity",
+			}));
+		}
+	}
+}
+
+#[test]
+fn keep_or_report_other_sources() {
+	let contents = r#"{
+		"lockfileVersion": 3,
+		"packages": {
+			"": {
+				"dependencies": {
+					"a": "github:owner/a",
+					"b": "https://example.com/b.tgz",
+					"c": "file:vendor/c.tgz"
+				}
+			},
+			"node_modules/a": {
+				"version": "1.0.0",
+				"resolved": "git+ssh://git@github.com/owner/a.git#0123abc"
+			},
+			"node_modules/b": {
+				"version": "2.0.0",
+				"resolved": "https://example.com/b.tgz",
+				"integrity": "sha512-b"
+			},
+			"node_modules/c": {
+				"version": "3.0.0",
+				"resolved": "file:vendor/c.tgz"
+			}
+		}
+	}"#;
+	let manifest = r#"{
+		"dependencies": {
+			"a": "github:owner/a",
+			"b": "https://example.com/b.tgz",
+			"c": "file:vendor/c.tgz"
+		}
+	}"#;
+	let package_json = PackageJson::try_from(manifest).unwrap();
+	let graph = LockfileGraph::from_package_lock(&PackageLock::try_from(contents).unwrap());
+	let unsupported = ConversionIssue::UnsupportedSource {
+		package: "c@3.0.0".into(),
+		source: "file:vendor/c.tgz".into(),
+	};
+
+	let pnpm = graph.to_pnpm_lock(&[(".", &package_json)]);
+	let packages = pnpm.lockfile.packages.as_ref().unwrap();
+	let git = packages["a@1.0.0"].resolution.as_ref().unwrap();
+
+	assert_eq!(git.type_.as_deref(), Some("git"));
+	assert_eq!(
+		git.repo.as_deref(),
+		Some("ssh://git@github.com/owner/a.git")
+	);
+	assert_eq!(git.commit.as_deref(), Some("0123abc"));
+	assert_eq!(
+		packages["b@2.0.0"]
+			.resolution
+			.as_ref()
+			.unwrap()
+			.tarball
+			.as_deref(),
+		Some("https://example.com/b.tgz")
+	);
+	assert!(pnpm.issues.contains(&unsupported));
+
+	let berry = graph.to_yarn_lock(&[(".", &package_json)], YarnLockFlavor::Berry);
+	let resolution = |name, spec| {
+		berry
+			.lockfile
+			.find(name, spec)
+			.and_then(|entry| entry.resolution.clone())
+	};
+
+	assert_eq!(
+		resolution("a", "github:owner/a").as_deref(),
+		Some("a@ssh://git@github.com/owner/a.git#commit=0123abc")
+	);
+	assert_eq!(
+		resolution("b", "https://example.com/b.tgz").as_deref(),
+		Some("b@https://example.com/b.tgz")
+	);
+	assert!(berry.issues.contains(&unsupported));
+}
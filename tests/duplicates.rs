@@ -0,0 +1,83 @@
+use common::read;
+use package_json_schema::DuplicateDependent;
+use package_json_schema::LockfileGraph;
+use package_json_schema::PackageLock;
+use package_json_schema::PnpmLock;
+
+mod common;
+
+#[test]
+fn duplicates_in_package_lock() {
+	let package_lock = PackageLock::try_from(read("npm-v3/package-lock.json")).unwrap();
+	let graph = LockfileGraph::from_package_lock(&package_lock);
+	let duplicates = graph.duplicates();
+
+	assert_eq!(duplicates.len(), 1);
+
+	let ms = &duplicates[0];
+	let versions: Vec<_> = ms
+		.versions
+		.iter()
+		.map(|version| (version.version.as_str(), version.ids.clone()))
+		.collect();
+
+	assert_eq!(ms.name, "ms");
+	assert_eq!(
+		versions,
+		[
+			("2.0.0", vec!["node_modules/ms".to_string()]),
+			(
+				"2.1.2",
+				vec!["node_modules/debug/node_modules/ms".to_string()]
+			),
+		]
+	);
+	assert_eq!(
+		ms.versions[1].dependents,
+		[DuplicateDependent {
+			dependent: "debug@4.3.4".into(),
+			range: Some("2.1.2".into()),
+		}]
+	);
+	assert_eq!(ms.dedupable, Some(false));
+	assert_eq!(ms.dedupe_to, None);
+}
+
+#[test]
+fn dedupable_duplicates() {
+	let mut package_lock = PackageLock::try_from(read("npm-v3/package-lock.json")).unwrap();
+	let packages = package_lock.packages.as_mut().unwrap();
+	packages[""]
+		.dependencies
+		.as_mut()
+		.unwrap()
+		.insert("ms".into(), "^2.0.0".into());
+	packages["node_modules/debug"]
+		.dependencies
+		.as_mut()
+		.unwrap()
+		.insert("ms".into(), "^2.1.1".into());
+
+	let graph = LockfileGraph::from_package_lock(&package_lock);
+	let duplicates = graph.duplicates();
+
+	assert_eq!(duplicates[0].dedupable, Some(true));
+	assert_eq!(duplicates[0].dedupe_to.as_deref(), Some("2.1.2"));
+}
+
+#[test]
+fn duplicates_without_ranges() {
+	let pnpm_lock = PnpmLock::try_from(read("pnpm/pnpm-lock.yaml")).unwrap();
+	let graph = LockfileGraph::from_pnpm_lock(&pnpm_lock);
+	let duplicates = graph.duplicates();
+	let dependents: Vec<_> = duplicates[0]
+		.versions
+		.iter()
+		.flat_map(|version| &version.dependents)
+		.map(|dependent| dependent.dependent.as_str())
+		.collect();
+
+	assert_eq!(duplicates[0].name, "ms");
+	assert_eq!(dependents, ["debug@4.3.4", "packages/lib"]);
+	assert_eq!(duplicates[0].dedupable, None);
+}
@@ -0,0 +1,103 @@
+use common::read;
+use package_json_schema::BunLock;
+use package_json_schema::DependencyKind;
+use package_json_schema::LockfileGraph;
+use package_json_schema::PackageJson;
+use package_json_schema::PackageLock;
+
+mod common;
+
+fn describe(graph: &LockfileGraph, package_json: &PackageJson, query: &str) -> Vec<String> {
+	graph
+		.explain(package_json, query)
+		.iter()
+		.map(ToString::to_string)
+		.collect()
+}
+
+#[test]
+fn explain_package_lock() {
+	let package_lock = PackageLock::try_from(read("npm-v3/package-lock.json")).unwrap();
+	let package_json = PackageJson::try_from(read("npm-v3/package.json")).unwrap();
+	let graph = LockfileGraph::from_package_lock(&package_lock);
+
+	assert_eq!(
+		describe(&graph, &package_json, "ms"),
+		[
+			"dependencies: debug@4.3.4 > ms@2.1.2",
+			"dependencies: ms@2.0.0",
+		]
+	);
+	assert_eq!(
+		describe(&graph, &package_json, "ms@^2.1.0"),
+		["dependencies: debug@4.3.4 > ms@2.1.2"]
+	);
+	assert_eq!(
+		describe(&graph, &package_json, "typescript@5.4.5"),
+		["devDependencies: typescript@5.4.5"]
+	);
+	assert!(describe(&graph, &package_json, "ms@3.0.0").is_empty());
+
+	let paths = graph.explain(&package_json, "fsevents");
+
+	assert_eq!(paths[0].kind, DependencyKind::Optional);
+	assert_eq!(paths[0].target().id, "node_modules/fsevents");
+}
+
+#[test]
+fn explain_through_workspace_links() {
+	let bun_lock = BunLock::try_from(read("bun/bun.lock")).unwrap();
+	let package_json = PackageJson::try_from(read("bun/package.json")).unwrap();
+	let graph = LockfileGraph::from_bun_lock(&bun_lock);
+
+	assert_eq!(
+		describe(&graph, &package_json, "ms"),
+		[
+			"dependencies: debug@4.3.4 > ms@2.1.2",
+			"dependencies: lib@1.0.0 > ms@2.0.0",
+		]
+	);
+}
+
+#[test]
+fn explain_shared_dependencies_once_per_dependent() {
+	let layers = 40;
+	let mut packages = serde_json::Map::new();
+	let dependencies = |layer: usize| {
+		if layer == layers {
+			serde_json::json!({ "ms": "2.1.2" })
+		} else {
+			serde_json::json!({ format!("a{layer}"): "1.0.0", format!("b{layer}"): "1.0.0" })
+		}
+	};
+
+	packages.insert(
+		String::new(),
+		serde_json::json!({ "dependencies": dependencies(0) }),
+	);
+	packages.insert(
+		"node_modules/ms".into(),
+		serde_json::json!({ "version": "2.1.2" }),
+	);
+
+	for layer in 0..layers {
+		for prefix in ["a", "b"] {
+			packages.insert(
+				format!("node_modules/{prefix}{layer}"),
+				serde_json::json!({ "version": "1.0.0", "dependencies": dependencies(layer + 1) }),
+			);
+		}
+	}
+
+	let contents = serde_json::json!({ "lockfileVersion": 3, "packages": packages }).to_string();
+	let package_lock = PackageLock::try_from(contents).unwrap();
+	let package_json =
+		PackageJson::try_from(r#"{ "dependencies": { "a0": "1.0.0", "b0": "1.0.0" } }"#).unwrap();
+	let graph = LockfileGraph::from_package_lock(&package_lock);
+	let paths = graph.explain(&package_json, "ms");
+
+	assert_eq!(paths.len(), 2);
+	assert_eq!(paths[0].steps.len(), layers + 1);
+	assert_eq!(paths[0].steps[layers - 1].name, format!("a{}", layers - 1));
+	assert_eq!(paths[1].steps[layers - 1].name, format!("b{}", layers - 1));
+}
@@ -0,0 +1,167 @@
+use package_json_schema::DependencyKind;
+use package_json_schema::DependencySpec;
+use package_json_schema::Error;
+use package_json_schema::Workspace;
+use package_json_schema::WorkspaceGraph;
+use package_json_schema::WorkspaceMember;
+
+fn names<'a>(members: &[&'a WorkspaceMember]) -> Vec<&'a str> {
+	members.iter().filter_map(|member| member.name()).collect()
+}
+
+#[test]
+fn classify_dependency_specs() {
+	assert_eq!(
+		DependencySpec::parse("^1.0.0"),
+		DependencySpec::Range("^1.0.0")
+	);
+	assert_eq!(DependencySpec::parse(""), DependencySpec::Range(""));
+	assert_eq!(
+		DependencySpec::parse("latest"),
+		DependencySpec::Tag("latest")
+	);
+	assert_eq!(
+		DependencySpec::parse("workspace:@scope/core@*"),
+		DependencySpec::Workspace {
+			name: Some("@scope/core"),
+			range: "*"
+		}
+	);
+	assert_eq!(
+		DependencySpec::parse("workspace:^1.2.0"),
+		DependencySpec::Workspace {
+			name: None,
+			range: "^1.2.0"
+		}
+	);
+	assert_eq!(
+		DependencySpec::parse("npm:@scope/core"),
+		DependencySpec::Alias {
+			name: "@scope/core",
+			range: "*"
+		}
+	);
+	assert_eq!(
+		DependencySpec::parse("link:../core"),
+		DependencySpec::Path {
+			protocol: "link",
+			path: "../core"
+		}
+	);
+	assert_eq!(
+		DependencySpec::parse("./vendor/core"),
+		DependencySpec::Path {
+			protocol: "file",
+			path: "./vendor/core"
+		}
+	);
+	assert_eq!(
+		DependencySpec::parse("github:user/repo#main"),
+		DependencySpec::Url("github:user/repo#main")
+	);
+	assert_eq!(
+		DependencySpec::parse("user/repo"),
+		DependencySpec::Url("user/repo")
+	);
+}
+
+#[test]
+fn build_workspace_graph() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/graph").unwrap();
+	let graph = WorkspaceGraph::new(&workspace);
+
+	let edges: Vec<_> = graph
+		.edges()
+		.iter()
+		.map(|edge| {
+			(
+				graph.member(edge.from).name().unwrap(),
+				graph.member(edge.to).name().unwrap(),
+				edge.kind,
+			)
+		})
+		.collect();
+
+	assert_eq!(
+		edges,
+		[
+			("@graph/app", "@graph/core", DependencyKind::Prod),
+			("@graph/app", "@graph/ui", DependencyKind::Prod),
+			("@graph/app", "@graph/config", DependencyKind::Dev),
+			("@graph/legacy", "@graph/core", DependencyKind::Prod),
+			("@graph/ui", "@graph/core", DependencyKind::Prod),
+			("@graph/ui", "@graph/config", DependencyKind::Optional),
+		]
+	);
+
+	let legacy = graph.dependencies("@graph/legacy");
+	assert_eq!(legacy.len(), 1);
+	assert_eq!(legacy[0].name, "core-alias");
+
+	let dependents: Vec<_> = graph
+		.dependents("@graph/core")
+		.iter()
+		.filter_map(|edge| graph.member(edge.from).name())
+		.collect();
+	assert_eq!(dependents, ["@graph/app", "@graph/legacy", "@graph/ui"]);
+
+	let core = workspace
+		.members()
+		.iter()
+		.position(|member| member.name() == Some("@graph/ui"))
+		.unwrap();
+	let transitive: Vec<_> = graph
+		.transitive_dependents(&[core])
+		.into_iter()
+		.filter_map(|index| graph.member(index).name())
+		.collect();
+	assert_eq!(transitive, ["@graph/app"]);
+}
+
+#[test]
+fn order_workspace_members_in_batches() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/graph").unwrap();
+	let graph = workspace.graph();
+	let batches: Vec<_> = graph
+		.topological_batches()
+		.unwrap()
+		.iter()
+		.map(|batch| names(batch))
+		.collect();
+
+	assert!(graph.cycles().is_empty());
+	assert_eq!(
+		batches,
+		[
+			vec!["@graph/config", "@graph/core"],
+			vec!["@graph/legacy", "@graph/ui"],
+			vec!["@graph/app"],
+		]
+	);
+}
+
+#[test]
+fn report_dependency_cycles() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/cycle").unwrap();
+	let graph = workspace.graph();
+	let cycles: Vec<_> = graph.cycles().iter().map(|cycle| names(cycle)).collect();
+
+	assert_eq!(cycles, [["a", "b", "c", "a"]]);
+
+	let error = graph.topological_batches().unwrap_err();
+	assert!(matches!(&error, Error::DependencyCycle { cycle } if cycle == &["a", "b", "c", "a"]));
+	assert_eq!(
+		error.to_string(),
+		"dependency cycle detected: a -> b -> c -> a"
+	);
+
+	let without_dev = graph.with_kinds(&[DependencyKind::Prod, DependencyKind::Peer]);
+	let batches: Vec<_> = without_dev
+		.topological_batches()
+		.unwrap()
+		.iter()
+		.map(|batch| names(batch))
+		.collect();
+
+	assert_eq!(batches, [vec!["c", "d"], vec!["b"], vec!["a"]]);
+}
@@ -0,0 +1,208 @@
+use common::read;
+use package_json_schema::BunLock;
+use package_json_schema::DependencyKind;
+use package_json_schema::LockfileGraph;
+use package_json_schema::PackageJson;
+use package_json_schema::PackageLock;
+use package_json_schema::PnpmLock;
+use package_json_schema::YarnLock;
+
+mod common;
+
+/// The locked version of every direct dependency of an importer.
+fn direct(graph: &LockfileGraph, importer: &str) -> Vec<(DependencyKind, String, String)> {
+	graph
+		.importer(importer)
+		.unwrap()
+		.dependencies
+		.iter()
+		.map(|dependency| {
+			let package = &graph.packages[dependency.target.as_deref().unwrap()];
+			let version = package
+				.link
+				.clone()
+				.unwrap_or_else(|| package.version.clone());
+			(dependency.kind, dependency.name.clone(), version)
+		})
+		.collect()
+}
+
+fn sorted(
+	mut entries: Vec<(DependencyKind, String, String)>,
+) -> Vec<(DependencyKind, String, String)> {
+	entries.sort();
+	entries
+}
+
+#[test]
+fn npm_versions_share_the_graph() {
+	let expected = vec![
+		(
+			DependencyKind::Prod,
+			"debug".to_string(),
+			"4.3.4".to_string(),
+		),
+		(DependencyKind::Prod, "ms".to_string(), "2.0.0".to_string()),
+		(
+			DependencyKind::Dev,
+			"typescript".to_string(),
+			"5.4.5".to_string(),
+		),
+		(
+			DependencyKind::Optional,
+			"fsevents".to_string(),
+			"2.3.3".to_string(),
+		),
+	];
+
+	for version in ["npm-v1", "npm-v2", "npm-v3"] {
+		let package_lock =
+			PackageLock::try_from(read(&format!("{version}/package-lock.json"))).unwrap();
+		let graph = LockfileGraph::from_package_lock(&package_lock);
+		let debug = graph.package("node_modules/debug").unwrap();
+
+		assert_eq!(
+			sorted(direct(&graph, "")),
+			sorted(expected.clone()),
+			"{version}"
+		);
+		assert_eq!(
+			debug.dependencies[0].target.as_deref(),
+			Some("node_modules/debug/node_modules/ms"),
+			"{version}"
+		);
+	}
+
+	let package_lock = PackageLock::try_from(read("npm-v3/package-lock.json")).unwrap();
+	let graph = LockfileGraph::from_package_lock(&package_lock);
+
+	assert_eq!(
+		graph.package("node_modules/fsevents").unwrap().os,
+		["darwin"]
+	);
+}
+
+#[test]
+fn pnpm_versions_share_the_graph() {
+	for version in ["pnpm", "pnpm-v6"] {
+		let pnpm_lock = PnpmLock::try_from(read(&format!("{version}/pnpm-lock.yaml"))).unwrap();
+		let graph = LockfileGraph::from_pnpm_lock(&pnpm_lock);
+
+		assert_eq!(
+			direct(&graph, "packages/app"),
+			[
+				(
+					DependencyKind::Prod,
+					"debug".to_string(),
+					"4.3.4".to_string()
+				),
+				(
+					DependencyKind::Prod,
+					"lib".to_string(),
+					"packages/lib".to_string()
+				),
+				(
+					DependencyKind::Prod,
+					"react".to_string(),
+					"18.2.0".to_string()
+				),
+				(
+					DependencyKind::Dev,
+					"typescript".to_string(),
+					"5.4.5".to_string()
+				),
+			],
+			"{version}"
+		);
+		assert_eq!(direct(&graph, "packages/lib")[0].2, "2.1.3", "{version}");
+
+		let debug = graph.package("debug@4.3.4").unwrap();
+		let ms = &graph.packages[debug.dependencies[0].target.as_deref().unwrap()];
+
+		assert_eq!(ms.version, "2.1.2", "{version}");
+		assert!(ms.integrity.is_some(), "{version}");
+	}
+
+	let pnpm_lock = PnpmLock::try_from(read("pnpm-v5/pnpm-lock.yaml")).unwrap();
+	let graph = LockfileGraph::from_pnpm_lock(&pnpm_lock);
+
+	assert_eq!(
+		direct(&graph, "."),
+		[
+			(
+				DependencyKind::Prod,
+				"debug".to_string(),
+				"4.3.4".to_string()
+			),
+			(
+				DependencyKind::Dev,
+				"typescript".to_string(),
+				"5.4.5".to_string()
+			),
+		]
+	);
+}
+
+#[test]
+fn yarn_and_bun_graphs() {
+	let package_json = PackageJson::try_from(read("yarn-classic/package.json")).unwrap();
+	let expected = sorted(vec![
+		(
+			DependencyKind::Prod,
+			"debug".to_string(),
+			"4.3.4".to_string(),
+		),
+		(DependencyKind::Prod, "ms".to_string(), "2.0.0".to_string()),
+		(
+			DependencyKind::Dev,
+			"typescript".to_string(),
+			"5.4.5".to_string(),
+		),
+		(
+			DependencyKind::Optional,
+			"fsevents".to_string(),
+			"2.3.3".to_string(),
+		),
+	]);
+
+	for flavor in ["yarn-classic", "yarn-berry"] {
+		let yarn_lock = YarnLock::try_from(read(&format!("{flavor}/yarn.lock"))).unwrap();
+		let graph = LockfileGraph::from_yarn_lock(&yarn_lock, &[(".", &package_json)]);
+
+		assert_eq!(sorted(direct(&graph, ".")), expected, "{flavor}");
+	}
+
+	let bun_lock = BunLock::try_from(read("bun/bun.lock")).unwrap();
+	let graph = LockfileGraph::from_bun_lock(&bun_lock);
+
+	assert_eq!(
+		sorted(direct(&graph, ".")),
+		sorted(vec![
+			(
+				DependencyKind::Prod,
+				"debug".to_string(),
+				"4.3.4".to_string()
+			),
+			(
+				DependencyKind::Prod,
+				"lib".to_string(),
+				"packages/lib".to_string()
+			),
+			(
+				DependencyKind::Dev,
+				"typescript".to_string(),
+				"5.4.5".to_string()
+			),
+			(
+				DependencyKind::Optional,
+				"fsevents".to_string(),
+				"2.3.3".to_string()
+			),
+		])
+	);
+	assert_eq!(direct(&graph, "packages/lib")[0].2, "2.0.0");
+	assert_eq!(
+		graph.packages["debug"].dependencies[0].target.as_deref(),
+		Some("ms")
+	);
+}
@@ -0,0 +1,149 @@
+use common::read;
+use package_json_schema::LockPackage;
+use package_json_schema::LockfileChangeKind;
+use package_json_schema::LockfileGraph;
+use package_json_schema::PackageJson;
+use package_json_schema::PackageLock;
+
+mod common;
+
+fn load() -> (PackageLock, PackageJson) {
+	(
+		PackageLock::try_from(read("npm-v3/package-lock.json")).unwrap(),
+		PackageJson::try_from(read("npm-v3/package.json")).unwrap(),
+	)
+}
+
+/// Upgrade debug and its nested ms, drop typescript, add semver and change
+/// the integrity of fsevents.
+fn update(package_lock: &mut PackageLock, package_json: &mut PackageJson) {
+	let packages = package_lock.packages.as_mut().unwrap();
+	let debug = packages.get_mut("node_modules/debug").unwrap();
+	debug.version = Some("4.3.5".into());
+	debug.integrity = Some("sha512-debug".into());
+	packages
+		.get_mut("node_modules/debug/node_modules/ms")
+		.unwrap()
+		.version = Some("2.1.3".into());
+	packages.get_mut("node_modules/fsevents").unwrap().integrity = Some("sha512-fsevents".into());
+	packages.shift_remove("node_modules/typescript");
+	packages.insert(
+		"node_modules/semver".into(),
+		LockPackage::builder().version("7.6.0").build(),
+	);
+	packages[""]
+		.dependencies
+		.as_mut()
+		.unwrap()
+		.insert("semver".into(), "^7.6.0".into());
+	package_json
+		.dependencies
+		.as_mut()
+		.unwrap()
+		.insert("semver".into(), "^7.6.0".into());
+}
+
+#[test]
+fn diff_package_lock() {
+	let (package_lock, mut package_json) = load();
+	let mut updated = package_lock.clone();
+	update(&mut updated, &mut package_json);
+
+	let old = LockfileGraph::from_package_lock(&package_lock);
+	let new = LockfileGraph::from_package_lock(&updated);
+	let diff = old.diff(&new, &[(".", &package_json)]);
+	let summary: Vec<_> = diff
+		.changes
+		.iter()
+		.map(|change| {
+			(
+				change.name.as_str(),
+				change.kind,
+				change.from.as_deref(),
+				change.to.as_deref(),
+				change.direct.join(","),
+			)
+		})
+		.collect();
+
+	assert_eq!(
+		summary,
+		[
+			(
+				"debug",
+				LockfileChangeKind::Upgraded,
+				Some("4.3.4"),
+				Some("4.3.5"),
+				"debug".into()
+			),
+			(
+				"fsevents",
+				LockfileChangeKind::Integrity,
+				Some("2.3.3"),
+				Some("2.3.3"),
+				"fsevents".into()
+			),
+			(
+				"ms",
+				LockfileChangeKind::Upgraded,
+				Some("2.1.2"),
+				Some("2.1.3"),
+				"debug".into()
+			),
+			(
+				"semver",
+				LockfileChangeKind::Added,
+				None,
+				Some("7.6.0"),
+				"semver".into()
+			),
+			(
+				"typescript",
+				LockfileChangeKind::Removed,
+				Some("5.4.5"),
+				None,
+				"typescript".into()
+			),
+		]
+	);
+	assert_eq!(
+		diff.of_kind(LockfileChangeKind::Integrity)[0]
+			.to_integrity
+			.as_deref(),
+		Some("sha512-fsevents")
+	);
+	assert!(old.diff(&old, &[(".", &package_json)]).is_empty());
+
+	let downgrade = new.diff(&old, &[(".", &package_json)]);
+
+	assert_eq!(downgrade.changes[0].kind, LockfileChangeKind::Downgraded);
+}
+
+#[test]
+fn render_table_and_json() {
+	let (package_lock, mut package_json) = load();
+	let mut updated = package_lock.clone();
+	update(&mut updated, &mut package_json);
+
+	let old = LockfileGraph::from_package_lock(&package_lock);
+	let new = LockfileGraph::from_package_lock(&updated);
+	let diff = old.diff(&new, &[(".", &package_json)]);
+
+	assert_eq!(
+		diff.to_table(),
+		"package     change     from   to     direct
+debug       upgraded   4.3.4  4.3.5  debug
+fsevents    integrity  2.3.3  2.3.3  fsevents
+ms          upgraded   2.1.2  2.1.3  debug
+semver      added      -      7.6.0  semver
+typescript  removed    5.4.5  -      typescript
+"
+	);
+
+	let json: serde_json::Value = serde_json::from_str(&diff.to_json().unwrap()).unwrap();
+
+	assert_eq!(
+		json["changes"][3],
+		serde_json::json!({ "name": "semver", "kind": "added", "to": "7.6.0", "direct": ["semver"] })
+	);
+}
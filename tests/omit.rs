@@ -0,0 +1,144 @@
+use common::read;
+use package_json_schema::BunLock;
+use package_json_schema::DependencyKind;
+use package_json_schema::LockfileGraph;
+use package_json_schema::OmitOptions;
+use package_json_schema::PackageJson;
+use package_json_schema::PackageLock;
+use package_json_schema::UnsupportedPackage;
+
+mod common;
+
+fn load() -> (PackageLock, PackageJson) {
+	(
+		PackageLock::try_from(read("npm-v3/package-lock.json")).unwrap(),
+		PackageJson::try_from(read("npm-v3/package.json")).unwrap(),
+	)
+}
+
+#[test]
+fn omit_dependency_kinds() {
+	let (package_lock, package_json) = load();
+	let graph = LockfileGraph::from_package_lock(&package_lock);
+	let everything = graph.install_subset(&package_json, &OmitOptions::default());
+	let production = graph.install_subset(
+		&package_json,
+		&OmitOptions::builder()
+			.omit(vec![DependencyKind::Dev])
+			.build(),
+	);
+	let minimal = graph.install_subset(
+		&package_json,
+		&OmitOptions::builder()
+			.omit(vec![DependencyKind::Dev, DependencyKind::Optional])
+			.build(),
+	);
+
+	assert_eq!(everything.packages.len(), graph.packages.len());
+	assert_eq!(
+		production.packages,
+		[
+			"node_modules/debug",
+			"node_modules/debug/node_modules/ms",
+			"node_modules/fsevents",
+			"node_modules/ms",
+		]
+	);
+	assert!(!minimal.contains("node_modules/fsevents"));
+	assert!(minimal.contains("node_modules/debug"));
+}
+
+#[test]
+fn keep_packages_reached_through_prod_and_dev() {
+	let (mut package_lock, package_json) = load();
+	package_lock.packages.as_mut().unwrap()["node_modules/debug"]
+		.dependencies
+		.as_mut()
+		.unwrap()
+		.insert("typescript".into(), "^5.4.0".into());
+
+	let graph = LockfileGraph::from_package_lock(&package_lock);
+	let production = graph.install_subset(
+		&package_json,
+		&OmitOptions::builder()
+			.omit(vec![DependencyKind::Dev])
+			.build(),
+	);
+
+	assert!(production.contains("node_modules/typescript"));
+}
+
+#[test]
+fn skip_unsupported_platforms() {
+	let (package_lock, package_json) = load();
+	let graph = LockfileGraph::from_package_lock(&package_lock);
+	let linux = graph.install_subset(
+		&package_json,
+		&OmitOptions::builder().os("linux").cpu("x64").build(),
+	);
+	let darwin = graph.install_subset(&package_json, &OmitOptions::builder().os("darwin").build());
+
+	assert!(!linux.contains("node_modules/fsevents"));
+	assert_eq!(
+		linux.unsupported,
+		[UnsupportedPackage {
+			id: "node_modules/fsevents".into(),
+			optional: true,
+		}]
+	);
+	assert!(darwin.contains("node_modules/fsevents"));
+	assert!(darwin.unsupported.is_empty());
+}
+
+#[test]
+fn require_packages_reached_through_optional_and_required_edges() {
+	let package_json = PackageJson::try_from(
+		r#"{ "dependencies": { "b": "1.0.0" }, "optionalDependencies": { "a": "1.0.0" } }"#,
+	)
+	.unwrap();
+	let package_lock = PackageLock::try_from(
+		r#"{
+			"lockfileVersion": 3,
+			"packages": {
+				"": {
+					"dependencies": { "b": "1.0.0" },
+					"optionalDependencies": { "a": "1.0.0" }
+				},
+				"node_modules/a": { "version": "1.0.0", "dependencies": { "c": "1.0.0" } },
+				"node_modules/b": { "version": "1.0.0", "dependencies": { "c": "1.0.0" } },
+				"node_modules/c": { "version": "1.0.0", "dependencies": { "d": "1.0.0" } },
+				"node_modules/d": { "version": "1.0.0", "os": ["darwin"] }
+			}
+		}"#,
+	)
+	.unwrap();
+	let graph = LockfileGraph::from_package_lock(&package_lock);
+	let linux = graph.install_subset(&package_json, &OmitOptions::builder().os("linux").build());
+
+	assert_eq!(
+		linux.packages,
+		["node_modules/a", "node_modules/b", "node_modules/c"]
+	);
+	assert_eq!(
+		linux.unsupported,
+		[UnsupportedPackage {
+			id: "node_modules/d".into(),
+			optional: false,
+		}]
+	);
+}
+
+#[test]
+fn follow_workspace_links() {
+	let bun_lock = BunLock::try_from(read("bun/bun.lock")).unwrap();
+	let package_json = PackageJson::try_from(read("bun/package.json")).unwrap();
+	let graph = LockfileGraph::from_bun_lock(&bun_lock);
+	let production = graph.install_subset(
+		&package_json,
+		&OmitOptions::builder()
+			.omit(vec![DependencyKind::Dev, DependencyKind::Optional])
+			.build(),
+	);
+
+	assert_eq!(production.packages, ["debug", "lib", "ms", "lib/ms"]);
+}
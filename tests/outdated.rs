@@ -0,0 +1,169 @@
+use package_json_schema::upgrade_range;
+use package_json_schema::AbbreviatedPackument;
+use package_json_schema::LockfileGraph;
+use package_json_schema::PackageJson;
+use package_json_schema::PackageLock;
+use package_json_schema::PackumentCache;
+use package_json_schema::PickOptions;
+use package_json_schema::UpgradeTarget;
+use semver::Version;
+
+fn load() -> PackumentCache {
+	PackumentCache::load("./tests/fixtures/packuments").unwrap()
+}
+
+#[test]
+fn outdated_report() {
+	let package_lock = PackageLock::try_from(
+		r#"{
+			"lockfileVersion": 3,
+			"packages": {
+				"": { "dependencies": { "debug": "^4.0.0", "ms": "^2.0.0" } },
+				"node_modules/debug": { "version": "4.3.3" },
+				"node_modules/ms": { "version": "2.0.0" }
+			}
+		}"#,
+	)
+	.unwrap();
+	let package_json = PackageJson::try_from(
+		r#"{
+			"dependencies": { "debug": "^4.0.0", "ms": "^2.0.0", "lib": "workspace:*" },
+			"devDependencies": { "left-pad": "^1.0.0" }
+		}"#,
+	)
+	.unwrap();
+	let graph = LockfileGraph::from_package_lock(&package_lock);
+	let report = graph.outdated(&[(".", &package_json)], &load(), &PickOptions::default());
+	let versions: Vec<_> = report
+		.dependencies
+		.iter()
+		.map(|dependency| {
+			(
+				dependency.name.as_str(),
+				dependency.current.as_deref(),
+				dependency.wanted.as_deref(),
+				dependency.latest.as_deref(),
+			)
+		})
+		.collect();
+
+	assert_eq!(
+		versions,
+		[
+			("debug", Some("4.3.3"), Some("4.3.4"), Some("4.3.4")),
+			("ms", Some("2.0.0"), Some("2.1.3"), Some("2.1.3")),
+			("left-pad", None, None, None),
+		]
+	);
+	assert_eq!(
+		report.to_table(),
+		"package   current  wanted  latest  location\ndebug     4.3.3    4.3.4   4.3.4   \
+		 .\nms        2.0.0    2.1.3   2.1.3   .\nleft-pad  -        -       -       .\n"
+	);
+}
+
+#[test]
+fn upgrade_plan_targets() {
+	let cache = load();
+	let mut package_json = PackageJson::try_from(
+		r#"{
+			"dependencies": {
+				"debug": "~2.6.0",
+				"ms": ">=2.0.0",
+				"ms-pinned": "npm:ms@2.0.0"
+			},
+			"devDependencies": { "debug": "2.x" }
+		}"#,
+	)
+	.unwrap();
+	let upgrades = |target| {
+		cache
+			.upgrade_plan(&package_json, target)
+			.upgrades
+			.into_iter()
+			.map(|upgrade| (upgrade.name, upgrade.to))
+			.collect::<Vec<_>>()
+	};
+	let pairs = |pairs: &[(&str, &str)]| {
+		pairs
+			.iter()
+			.map(|(name, to)| ((*name).to_string(), (*to).to_string()))
+			.collect::<Vec<_>>()
+	};
+
+	assert_eq!(
+		upgrades(UpgradeTarget::Major),
+		pairs(&[
+			("debug", "~4.3.4"),
+			("ms", ">=2.1.3"),
+			("ms-pinned", "npm:ms@2.1.3"),
+			("debug", "4.x"),
+		])
+	);
+	assert_eq!(
+		upgrades(UpgradeTarget::Minor),
+		pairs(&[
+			("debug", "~2.6.9"),
+			("ms", ">=2.1.3"),
+			("ms-pinned", "npm:ms@2.1.3"),
+		])
+	);
+	assert_eq!(
+		upgrades(UpgradeTarget::Patch),
+		pairs(&[("debug", "~2.6.9")])
+	);
+
+	let plan = cache.upgrade_plan(&package_json, UpgradeTarget::Major);
+	plan.apply(&mut package_json);
+
+	assert_eq!(
+		package_json.dependencies.as_ref().unwrap()["debug"],
+		"~4.3.4"
+	);
+	assert_eq!(
+		package_json.dev_dependencies.as_ref().unwrap()["debug"],
+		"4.x"
+	);
+}
+
+#[test]
+fn upgrade_to_undeprecated_versions() {
+	let mut cache = PackumentCache::default();
+	cache.insert(
+		AbbreviatedPackument::try_from(
+			r#"{
+				"name": "revived",
+				"dist-tags": { "latest": "1.1.0" },
+				"versions": {
+					"1.0.0": { "name": "revived", "version": "1.0.0" },
+					"1.1.0": { "name": "revived", "version": "1.1.0", "deprecated": "" }
+				}
+			}"#,
+		)
+		.unwrap(),
+	);
+	let package_json =
+		PackageJson::try_from(r#"{ "dependencies": { "revived": "^1.0.0" } }"#).unwrap();
+	let plan = cache.upgrade_plan(&package_json, UpgradeTarget::Minor);
+
+	assert_eq!(plan.upgrades[0].to, "^1.1.0");
+}
+
+#[test]
+fn upgrade_range_styles() {
+	let version = Version::parse("2.4.1").unwrap();
+	let upgrade = |range| upgrade_range(range, &version);
+
+	assert_eq!(upgrade("1.2.3").as_deref(), Some("2.4.1"));
+	assert_eq!(upgrade("=1.2.3").as_deref(), Some("=2.4.1"));
+	assert_eq!(upgrade("v1.2.3").as_deref(), Some("v2.4.1"));
+	assert_eq!(upgrade("1.2.3-beta.1").as_deref(), Some("2.4.1"));
+	assert_eq!(upgrade("^0.1").as_deref(), Some("^2.4"));
+	assert_eq!(upgrade("~1").as_deref(), Some("~2"));
+	assert_eq!(upgrade("1.x.x").as_deref(), Some("2.x.x"));
+	assert_eq!(upgrade("*"), None);
+	assert_eq!(upgrade("<2.0.0"), None);
+	assert_eq!(upgrade(">=1 <2"), None);
+	assert_eq!(upgrade("^1 || ^2"), None);
+	assert_eq!(upgrade("latest"), None);
+}
@@ -98,3 +98,46 @@ fn invalid_name_field() {
 	let package_json = PackageJson::try_from(contents).unwrap();
 	assert!(package_json.validate().is_err());
 }
+
+#[test]
+fn parse_package_json_file_with_dependencies_meta() {
+	let contents = read_to_string("./tests/fixtures/6/package.json").unwrap();
+	let package_json = PackageJson::try_from(contents).unwrap();
+
+	let dependencies_meta = package_json.dependencies_meta.as_ref().unwrap();
+	assert_eq!(dependencies_meta["shared"].injected, Some(true));
+	assert_eq!(dependencies_meta["esbuild@0.19.0"].unplugged, Some(true));
+	assert_eq!(
+		package_json.undeclared_peer_dependencies_meta(),
+		["typescript"]
+	);
+
+	let peers: Vec<_> = package_json
+		.peer_requirements()
+		.into_iter()
+		.map(|peer| (peer.name, peer.range, peer.optional))
+		.collect();
+
+	assert_eq!(
+		peers,
+		[
+			("react".into(), "^18.0.0".into(), false),
+			("react-dom".into(), "^18.0.0".into(), true),
+			("typescript".into(), "*".into(), true),
+		]
+	);
+}
+
+#[cfg(feature = "validate")]
+#[test]
+fn invalid_peer_dependencies_meta() {
+	use package_json_schema::validator::Validate;
+
+	let contents = read_to_string("./tests/fixtures/6/package.json").unwrap();
+	let package_json = PackageJson::try_from(contents).unwrap();
+	let errors = package_json.validate().unwrap_err();
+
+	assert!(errors
+		.to_string()
+		.contains("missing from peerDependencies: `typescript`"));
+}
@@ -0,0 +1,90 @@
+use std::fs::read_to_string;
+
+use package_json_schema::PackageLock;
+use serde_json::Value;
+
+fn fixture(version: u32) -> String {
+	read_to_string(format!(
+		"./tests/fixtures/lockfiles/npm-v{version}/package-lock.json"
+	))
+	.unwrap()
+}
+
+#[test]
+fn parse_packages() {
+	let package_lock = PackageLock::try_from(fixture(3)).unwrap();
+	let root = package_lock.root().unwrap();
+
+	assert_eq!(package_lock.lockfile_version, Some(3));
+	assert!(package_lock.dependencies.is_none());
+	assert_eq!(root.dependencies.as_ref().unwrap()["debug"], "^4.3.4");
+	assert_eq!(
+		root.optional_dependencies.as_ref().unwrap()["fsevents"],
+		"~2.3.3"
+	);
+
+	let installed: Vec<_> = package_lock
+		.installed("ms")
+		.into_iter()
+		.map(|(path, package)| (path, package.version.as_deref().unwrap()))
+		.collect();
+	assert_eq!(
+		installed,
+		[
+			("node_modules/debug/node_modules/ms", "2.1.2"),
+			("node_modules/ms", "2.0.0")
+		]
+	);
+
+	let typescript = package_lock.package("node_modules/typescript").unwrap();
+	assert!(typescript.is_dev());
+	assert!(!typescript.is_optional());
+
+	let fsevents = package_lock.package("node_modules/fsevents").unwrap();
+	assert!(fsevents.is_optional());
+	assert_eq!(fsevents.has_install_script, Some(true));
+	assert_eq!(fsevents.os.as_deref(), Some(&["darwin".to_string()][..]));
+	assert!(fsevents.integrity.as_ref().unwrap().starts_with("sha512-"));
+
+	let ms = package_lock.package("node_modules/ms").unwrap();
+	assert_eq!(ms.license.as_ref().unwrap()["type"], "MIT");
+}
+
+#[test]
+fn legacy_dependencies() {
+	for version in [1, 2] {
+		let package_lock = PackageLock::try_from(fixture(version)).unwrap();
+		let legacy = package_lock.legacy_packages();
+		let paths: Vec<_> = legacy.iter().map(|(path, _)| path.as_str()).collect();
+
+		assert_eq!(package_lock.packages.is_some(), version == 2);
+		assert_eq!(
+			paths,
+			[
+				"node_modules/debug",
+				"node_modules/debug/node_modules/ms",
+				"node_modules/fsevents",
+				"node_modules/ms",
+				"node_modules/typescript"
+			]
+		);
+		assert_eq!(legacy[0].1.requires.as_ref().unwrap()["ms"], "2.1.2");
+		assert_eq!(legacy[1].1.version.as_deref(), Some("2.1.2"));
+		assert_eq!(legacy[4].1.dev, Some(true));
+	}
+}
+
+#[test]
+fn round_trip_without_loss() {
+	for version in [1, 2, 3] {
+		let contents = fixture(version);
+		let package_lock = PackageLock::try_from(contents.as_str()).unwrap();
+		let output = package_lock.try_to_string().unwrap();
+
+		assert!(output.ends_with("}\n"));
+		assert_eq!(
+			serde_json::from_str::<Value>(&output).unwrap(),
+			serde_json::from_str::<Value>(&contents).unwrap()
+		);
+	}
+}
@@ -0,0 +1,130 @@
+use std::fs::read_to_string;
+
+use package_json_schema::AbbreviatedPackument;
+use package_json_schema::Packument;
+use package_json_schema::Person;
+use package_json_schema::VersionRange;
+
+fn read(name: &str) -> String {
+	read_to_string(format!("./tests/fixtures/packuments/{name}.json")).unwrap()
+}
+
+#[test]
+fn parse_packument() {
+	let packument = Packument::try_from(read("ms")).unwrap();
+	let latest = packument.dist_tag("latest").unwrap();
+	let dist = latest.dist.as_ref().unwrap();
+
+	assert_eq!(packument.id.as_deref(), Some("ms"));
+	assert_eq!(packument.versions.len(), 4);
+	assert_eq!(
+		packument.time.as_ref().unwrap()["2.1.3"],
+		"2020-12-08T13:54:35.223Z"
+	);
+	assert!(dist.integrity.as_ref().unwrap().starts_with("sha512-6Flz"));
+	assert_eq!(dist.file_count, Some(4));
+	assert_eq!(dist.unpacked_size, Some(6721));
+	assert_eq!(
+		dist.signatures.as_ref().unwrap()[0].keyid,
+		"SHA256:jl3bwswu80PjjokCgh0o2w5c2U4LhQAE57gj9cz1kzA"
+	);
+	assert!(dist
+		.attestations
+		.as_ref()
+		.unwrap()
+		.url
+		.ends_with("ms@2.1.3"));
+	assert_eq!(latest.has_shrinkwrap, Some(false));
+	assert!(matches!(
+		&latest.npm_user,
+		Some(Person::Object(user)) if user.name == "leo"
+	));
+	assert!(packument.versions["2.1.2"].deprecated.is_some());
+	assert!(packument.other.as_ref().unwrap().contains_key("repository"));
+
+	let contents = serde_json::to_string(&packument).unwrap();
+	let reparsed = Packument::try_from(contents).unwrap();
+	assert_eq!(
+		reparsed.versions["2.1.3"].dist.as_ref().unwrap().signatures,
+		dist.signatures
+	);
+}
+
+#[test]
+fn resolve_specs() {
+	let packument = AbbreviatedPackument::try_from(read("debug")).unwrap();
+	let version = |spec: &str| {
+		packument
+			.resolve(spec)
+			.and_then(|manifest| manifest.version.clone())
+	};
+
+	assert_eq!(
+		packument.modified.as_deref(),
+		Some("2023-10-19T10:55:11.000Z")
+	);
+	assert_eq!(version("").as_deref(), Some("4.3.4"));
+	assert_eq!(version("beta").as_deref(), Some("5.0.0-beta.1"));
+	assert_eq!(version("4.3.3").as_deref(), Some("4.3.3"));
+	assert_eq!(version("^2.0.0").as_deref(), Some("2.6.9"));
+	assert_eq!(version(">=4").as_deref(), Some("4.3.4"));
+	assert_eq!(version(">=5.0.0-beta.0").as_deref(), Some("5.0.0-beta.1"));
+	assert_eq!(version("^6.0.0"), None);
+	assert_eq!(version("missing"), None);
+	assert_eq!(
+		packument
+			.max_satisfying(&VersionRange::parse("<4.3.4").unwrap())
+			.and_then(|manifest| manifest.version.as_deref()),
+		Some("4.3.3")
+	);
+}
+
+#[test]
+fn resolve_versions_which_arent_deprecated() {
+	let contents = r#"{
+		"name": "revived",
+		"dist-tags": { "latest": "1.2.0" },
+		"versions": {
+			"1.0.0": { "name": "revived", "version": "1.0.0" },
+			"1.1.0": { "name": "revived", "version": "1.1.0", "deprecated": "" },
+			"1.2.0": { "name": "revived", "version": "1.2.0", "deprecated": "Broken" }
+		}
+	}"#;
+	let packument = AbbreviatedPackument::try_from(contents).unwrap();
+	let version = |spec: &str| {
+		packument
+			.resolve(spec)
+			.and_then(|manifest| manifest.version.clone())
+	};
+
+	// An empty message un-deprecates 1.1.0.
+	assert_eq!(version("^1.0.0").as_deref(), Some("1.1.0"));
+	assert_eq!(version("latest").as_deref(), Some("1.2.0"));
+	assert_eq!(version("~1.2.0").as_deref(), Some("1.2.0"));
+}
+
+#[test]
+fn keep_legacy_versions() {
+	let contents = read("legacy");
+	let packument = Packument::try_from(contents.as_str()).unwrap();
+	let abbreviated = AbbreviatedPackument::try_from(contents).unwrap();
+
+	for versions in [&packument.versions, &abbreviated.versions] {
+		let legacy = &versions["0.0.1"];
+		let other = legacy.other.as_ref().unwrap();
+
+		assert_eq!(versions.len(), 2);
+		assert_eq!(legacy.version.as_deref(), Some("0.0.1"));
+		assert!(legacy.dist.as_ref().unwrap().tarball.is_some());
+		assert!(legacy.license.is_none());
+		assert_eq!(other["license"]["type"], "MIT");
+		assert_eq!(other["engines"][0], "node >= 0.4.0");
+		assert_eq!(other["dependencies"], serde_json::json!([]));
+		assert_eq!(other["keywords"], "legacy, old");
+		assert_eq!(other["deprecated"], false);
+		assert_eq!(versions["1.0.0"].license.as_deref(), Some("MIT"));
+	}
+
+	let resolved = packument.resolve("^0.0.1").unwrap();
+	assert_eq!(resolved.version.as_deref(), Some("0.0.1"));
+}
@@ -0,0 +1,111 @@
+use package_json_schema::DependencyKind;
+use package_json_schema::PackageJson;
+use package_json_schema::PackumentCache;
+use package_json_schema::PickOptions;
+use package_json_schema::PickedDependency;
+
+fn load() -> PackumentCache {
+	PackumentCache::load("./tests/fixtures/packuments").unwrap()
+}
+
+fn pick(cache: &PackumentCache, name: &str, spec: &str, options: &PickOptions) -> Option<String> {
+	cache
+		.pick_manifest(name, spec, options)
+		.and_then(|manifest| manifest.version.clone())
+}
+
+#[test]
+fn pick_latest_or_max_satisfying() {
+	let cache = load();
+	let options = PickOptions::default();
+	let ms = |spec| pick(&cache, "ms", spec, &options);
+
+	assert_eq!(cache.names().collect::<Vec<_>>(), ["debug", "legacy", "ms"]);
+	assert_eq!(ms("").as_deref(), Some("2.1.3"));
+	assert_eq!(ms("*").as_deref(), Some("2.1.3"));
+	assert_eq!(ms("^2.0.0").as_deref(), Some("2.1.3"));
+	assert_eq!(ms("beta").as_deref(), Some("3.0.0-canary.1"));
+	assert_eq!(ms("^3.0.0"), None);
+	assert_eq!(ms("^3.0.0-0").as_deref(), Some("3.0.0-canary.1"));
+	assert_eq!(pick(&cache, "left-pad", "^1.0.0", &options), None);
+}
+
+#[test]
+fn avoid_deprecated_versions() {
+	let cache = load();
+	let options = PickOptions::default();
+	let ms = |spec| pick(&cache, "ms", spec, &options);
+
+	// 2.1.2 is deprecated, so an older version is preferred.
+	assert_eq!(ms("<2.1.3").as_deref(), Some("2.0.0"));
+	// Deprecated versions are still picked when nothing else satisfies.
+	assert_eq!(ms("~2.1.0 <2.1.3").as_deref(), Some("2.1.2"));
+	assert_eq!(ms("2.1.2").as_deref(), Some("2.1.2"));
+	assert_eq!(
+		pick(&cache, "debug", ">=4 <4.3.4", &options).as_deref(),
+		Some("4.3.3")
+	);
+}
+
+#[test]
+fn respect_engines() {
+	let cache = load();
+	let range = "^4.3.0 || >=5.0.0-beta.0";
+	let beta = |node: Option<&str>| {
+		let options = match node {
+			Some(node) => {
+				PickOptions::builder()
+					.default_tag("beta")
+					.node_version(node)
+					.build()
+			}
+			None => PickOptions::builder().default_tag("beta").build(),
+		};
+		pick(&cache, "debug", range, &options)
+	};
+
+	assert_eq!(beta(None).as_deref(), Some("5.0.0-beta.1"));
+	assert_eq!(beta(Some("22.1.0")).as_deref(), Some("5.0.0-beta.1"));
+	assert_eq!(beta(Some("18.19.0")).as_deref(), Some("4.3.4"));
+}
+
+#[test]
+fn pick_package_json_dependencies() {
+	let cache = load();
+	let package_json = PackageJson::try_from(
+		r#"{
+			"dependencies": {
+				"debug": "^4.0.0",
+				"ms-next": "npm:ms@beta",
+				"lib": "workspace:*",
+				"left-pad": "^1.0.0"
+			},
+			"devDependencies": { "ms": "<2.1.3" }
+		}"#,
+	)
+	.unwrap();
+	let picked = cache.pick_dependencies(&package_json, &PickOptions::default());
+	let expected = |kind, name: &str, spec: &str, version: Option<&str>| {
+		PickedDependency {
+			kind,
+			name: name.into(),
+			spec: spec.into(),
+			version: version.map(Into::into),
+		}
+	};
+
+	assert_eq!(
+		picked,
+		[
+			expected(DependencyKind::Prod, "debug", "^4.0.0", Some("4.3.4")),
+			expected(
+				DependencyKind::Prod,
+				"ms-next",
+				"npm:ms@beta",
+				Some("3.0.0-canary.1")
+			),
+			expected(DependencyKind::Prod, "left-pad", "^1.0.0", None),
+			expected(DependencyKind::Dev, "ms", "<2.1.3", Some("2.0.0")),
+		]
+	);
+}
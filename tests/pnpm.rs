@@ -0,0 +1,96 @@
+use std::fs::read_to_string;
+
+use indexmap::IndexMap;
+use package_json_schema::PackageJson;
+
+fn load_config() -> package_json_schema::PnpmConfig {
+	let contents = read_to_string("./tests/fixtures/7/package.json").unwrap();
+	let package_json = PackageJson::try_from(contents).unwrap();
+
+	assert!(package_json.other.as_ref().is_some_and(IndexMap::is_empty));
+	package_json.pnpm.unwrap()
+}
+
+#[test]
+fn parse_pnpm_config() {
+	let config = load_config();
+
+	assert_eq!(config.overrides.unwrap()["bar@^2.1.0"], "3.0.0");
+	assert_eq!(config.package_extensions.unwrap().len(), 3);
+	assert_eq!(
+		config
+			.peer_dependency_rules
+			.unwrap()
+			.ignore_missing
+			.unwrap(),
+		["@babel/*"]
+	);
+	assert_eq!(config.only_built_dependencies.unwrap(), ["esbuild"]);
+	assert_eq!(config.never_built_dependencies.unwrap(), ["fsevents"]);
+	assert_eq!(
+		config.patched_dependencies.unwrap()["express@4.18.1"],
+		"patches/express@4.18.1.patch"
+	);
+	assert_eq!(config.allowed_deprecated_versions.unwrap()["request"], "*");
+	insta::assert_json_snapshot!(config.other, @r###"
+ {
+   "customSetting": true
+ }
+ "###);
+}
+
+#[test]
+fn apply_package_extensions_without_overwriting_the_manifest() {
+	let config = load_config();
+	let mut dependency = PackageJson::try_from(
+		r#"{
+			"name": "react-redux",
+			"version": "7.2.9",
+			"peerDependencies": { "react": "^16.8.3 || ^17 || ^18", "react-dom": "^18.0.0" }
+		}"#,
+	)
+	.unwrap();
+
+	let applied = config.apply_package_extensions(&mut dependency);
+	assert_eq!(applied, ["react-redux"]);
+
+	insta::assert_json_snapshot!(dependency, @r###"
+ {
+   "name": "react-redux",
+   "version": "7.2.9",
+   "peerDependencies": {
+     "react-dom": "^18.0.0",
+     "react": "^16.8.3 || ^17 || ^18"
+   },
+   "peerDependenciesMeta": {
+     "react-dom": {
+       "optional": true
+     }
+   }
+ }
+ "###);
+}
+
+#[test]
+fn apply_package_extensions_with_selector_ranges() {
+	let config = load_config();
+	let mut old =
+		PackageJson::try_from(r#"{ "name": "@scope/legacy", "version": "1.4.0" }"#).unwrap();
+	let mut new =
+		PackageJson::try_from(r#"{ "name": "@scope/legacy", "version": "2.0.0" }"#).unwrap();
+	let mut unversioned = PackageJson::try_from(r#"{ "name": "@scope/legacy" }"#).unwrap();
+
+	assert_eq!(
+		config.apply_package_extensions(&mut old),
+		["@scope/legacy@<2"]
+	);
+	assert_eq!(
+		config.apply_package_extensions(&mut new),
+		["@scope/legacy@>=2"]
+	);
+	assert!(config.apply_package_extensions(&mut unversioned).is_empty());
+
+	assert_eq!(old.dependencies.unwrap()["tslib"], "^1.0.0");
+	assert_eq!(new.dependencies.unwrap()["tslib"], "^2.0.0");
+	assert!(unversioned.dependencies.is_none());
+}
@@ -0,0 +1,126 @@
+use std::fs::read_to_string;
+
+use package_json_schema::DependencyKind;
+use package_json_schema::PnpmLock;
+use package_json_schema::Workspace;
+
+fn fixture(directory: &str) -> String {
+	read_to_string(format!(
+		"./tests/fixtures/lockfiles/{directory}/pnpm-lock.yaml"
+	))
+	.unwrap()
+}
+
+#[test]
+fn parse_workspace_lockfile() {
+	let pnpm_lock = PnpmLock::try_from(fixture("pnpm")).unwrap();
+
+	assert_eq!(pnpm_lock.lockfile_version.major(), Some(9));
+	assert_eq!(
+		pnpm_lock.settings.as_ref().unwrap().auto_install_peers,
+		Some(true)
+	);
+	assert_eq!(
+		pnpm_lock.catalogs.as_ref().unwrap()["default"]["react"].version,
+		"18.2.0"
+	);
+	assert_eq!(
+		pnpm_lock.patched_dependencies.as_ref().unwrap()["ms@2.1.3"].hash(),
+		"4bsnchxq3xa4xnm3e4ddqgnvbi"
+	);
+
+	let importers = pnpm_lock.importers();
+	let entries = importers["packages/app"].entries();
+	let summary: Vec<_> = entries
+		.iter()
+		.map(|entry| {
+			(
+				entry.kind,
+				entry.name,
+				entry.specifier.unwrap(),
+				entry.version,
+			)
+		})
+		.collect();
+	assert_eq!(
+		summary,
+		[
+			(DependencyKind::Prod, "debug", "^4.3.4", "4.3.4"),
+			(DependencyKind::Prod, "lib", "workspace:*", "link:../lib"),
+			(DependencyKind::Prod, "react", "catalog:", "18.2.0"),
+			(DependencyKind::Dev, "typescript", "^5.4.0", "5.4.5"),
+		]
+	);
+
+	let ms_version = importers["packages/lib"].entries()[0].version;
+	let (key, ms) = pnpm_lock.package("ms", ms_version).unwrap();
+	assert_eq!(key, "ms@2.1.3");
+	assert!(ms.resolution.as_ref().unwrap().integrity.is_some());
+
+	let snapshots = pnpm_lock.snapshots.as_ref().unwrap();
+	assert_eq!(
+		snapshots["debug@4.3.4"].dependencies.as_ref().unwrap()["ms"],
+		"2.1.2"
+	);
+
+	let workspace = Workspace::discover("./tests/fixtures/lockfiles/pnpm").unwrap();
+	let linked: Vec<_> = pnpm_lock
+		.importer_package_jsons(&workspace)
+		.into_iter()
+		.map(|(path, package_json)| (path, package_json.and_then(|p| p.name.clone())))
+		.collect();
+	assert_eq!(
+		linked,
+		[
+			(".".to_string(), Some("pnpm-lock-fixture".to_string())),
+			("packages/app".to_string(), Some("app".to_string())),
+			("packages/lib".to_string(), Some("lib".to_string())),
+		]
+	);
+}
+
+#[test]
+fn parse_older_lockfiles() {
+	let v5 = PnpmLock::try_from(fixture("pnpm-v5")).unwrap();
+	let importers = v5.importers();
+	let entries = importers["."].entries();
+
+	assert_eq!(v5.lockfile_version.to_string(), "5.4");
+	assert_eq!(v5.lockfile_version.major(), Some(5));
+	assert_eq!(entries[0].specifier, Some("^4.3.4"));
+	assert_eq!(entries[1].kind, DependencyKind::Dev);
+	assert_eq!(v5.package("ms", "2.1.2").unwrap().0, "/ms/2.1.2");
+	assert_eq!(v5.package("typescript", "5.4.5").unwrap().1.dev, Some(true));
+
+	let v6 = PnpmLock::try_from(fixture("pnpm-v6")).unwrap();
+	let version = "2.1.3(patch_hash=4bsnchxq3xa4xnm3e4ddqgnvbi)";
+	let (_, ms) = v6.package("ms", version).unwrap();
+
+	assert_eq!(v6.lockfile_version.major(), Some(6));
+	assert_eq!(ms.patched, Some(true));
+	assert!(v6.snapshots.is_none());
+
+	let unquoted = fixture("pnpm-v6").replacen("'6.0'", "6.0", 1);
+	let unquoted = PnpmLock::try_from(unquoted).unwrap();
+
+	assert_eq!(unquoted.lockfile_version.to_string(), "6.0");
+	assert!(unquoted
+		.try_to_string()
+		.unwrap()
+		.starts_with("lockfileVersion: 6.0\n"));
+	assert_eq!(unquoted.lockfile_version.major(), Some(6));
+}
+
+#[test]
+fn round_trip_without_loss() {
+	for directory in ["pnpm", "pnpm-v6", "pnpm-v5"] {
+		let contents = fixture(directory);
+		let pnpm_lock = PnpmLock::try_from(contents.as_str()).unwrap();
+		let output = pnpm_lock.try_to_string().unwrap();
+
+		assert_eq!(
+			serde_yaml::from_str::<serde_yaml::Value>(&output).unwrap(),
+			serde_yaml::from_str::<serde_yaml::Value>(&contents).unwrap()
+		);
+	}
+}
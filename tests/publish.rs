@@ -0,0 +1,89 @@
+use package_json_schema::Binary;
+use package_json_schema::Error;
+use package_json_schema::PackageJson;
+use package_json_schema::Workspace;
+
+#[test]
+fn create_publish_manifest() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/graph").unwrap();
+	let contents = r#"{
+		"name": "@graph/cli",
+		"version": "1.0.0",
+		"main": "src/index.ts",
+		"publishConfig": {
+			"access": "public",
+			"main": "dist/index.js",
+			"bin": { "graph": "dist/cli.js" },
+			"exports": { ".": { "import": "./dist/index.mjs" } },
+			"unpkg": "dist/index.umd.js"
+		},
+		"dependencies": {
+			"@graph/core": "workspace:*",
+			"@graph/ui": "workspace:~",
+			"config": "workspace:@graph/config@^",
+			"lodash": "^4.17.21"
+		},
+		"devDependencies": {
+			"@graph/app": "workspace:^1.0.0"
+		},
+		"pnpm": { "overrides": { "lodash": "4.17.21" } },
+		"packageManager": "pnpm@9.1.0"
+	}"#;
+	let package_json = PackageJson::try_from(contents).unwrap();
+	let manifest = package_json.to_publish_manifest(&workspace).unwrap();
+
+	assert_eq!(manifest.main.as_deref(), Some("dist/index.js"));
+	assert!(matches!(manifest.bin, Some(Binary::Object(ref bin)) if bin["graph"] == "dist/cli.js"));
+	assert!(manifest.exports.is_some());
+	assert!(manifest.pnpm.is_none());
+	assert!(manifest.package_manager.is_none());
+	assert_eq!(manifest.other.unwrap()["unpkg"], "dist/index.umd.js");
+
+	let publish_config = manifest.publish_config.unwrap();
+	assert!(publish_config.access.is_some());
+	assert!(publish_config.main.is_none());
+
+	let dependencies = manifest.dependencies.unwrap();
+	assert_eq!(dependencies["@graph/core"], "1.2.0");
+	assert_eq!(dependencies["@graph/ui"], "~1.0.0");
+	assert_eq!(dependencies["config"], "npm:@graph/config@^1.0.0");
+	assert_eq!(dependencies["lodash"], "^4.17.21");
+	assert_eq!(manifest.dev_dependencies.unwrap()["@graph/app"], "^1.0.0");
+}
+
+#[test]
+fn fail_on_unresolved_references() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/graph").unwrap();
+
+	let contents = r#"{ "dependencies": { "@graph/missing": "workspace:*" } }"#;
+	let package_json = PackageJson::try_from(contents).unwrap();
+	let error = package_json.to_publish_manifest(&workspace).unwrap_err();
+	assert!(matches!(
+		error,
+		Error::UnresolvedWorkspaceDependency { ref name, .. } if name == "@graph/missing"
+	));
+
+	let contents = r#"{ "peerDependencies": { "react": "catalog:react18" } }"#;
+	let package_json = PackageJson::try_from(contents).unwrap();
+	let error = package_json.to_publish_manifest(&workspace).unwrap_err();
+	assert!(matches!(
+		error,
+		Error::UnresolvedCatalogDependency { ref spec, .. } if spec == "catalog:react18"
+	));
+}
+
+#[test]
+fn resolve_relative_references_of_unnamed_members() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/unnamed").unwrap();
+	let tool = &workspace.members()[2].package_json;
+	let manifest = tool.to_publish_manifest(&workspace).unwrap();
+
+	assert_eq!(workspace.members()[2].relative_path, "tools/c");
+	assert_eq!(manifest.dependencies.unwrap()["b"], "1.0.0");
+
+	let detached = tool.clone();
+	assert!(matches!(
+		detached.to_publish_manifest(&workspace),
+		Err(Error::UnresolvedWorkspaceDependency { .. })
+	));
+}
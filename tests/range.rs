@@ -0,0 +1,114 @@
+use package_json_schema::parse_version;
+use package_json_schema::VersionRange;
+
+fn satisfies(range: &str, version: &str) -> bool {
+	VersionRange::parse(range)
+		.unwrap()
+		.satisfies(&parse_version(version).unwrap())
+}
+
+#[test]
+fn match_node_semver_ranges() {
+	let cases = [
+		("*", "1.2.3", true),
+		("", "0.0.1", true),
+		("1.2.3", "1.2.3", true),
+		("=v1.2.3", "1.2.3", true),
+		("1.2.3", "1.2.4", false),
+		("1.x", "1.9.9", true),
+		("1.x", "2.0.0", false),
+		("1.2.*", "1.2.9", true),
+		("1.2", "1.3.0", false),
+		("~1.2.3", "1.2.9", true),
+		("~1.2.3", "1.3.0", false),
+		("~1", "1.9.0", true),
+		("~> 1.2", "1.2.5", true),
+		("^1.2.3", "1.9.0", true),
+		("^1.2.3", "2.0.0", false),
+		("^0.2.3", "0.2.9", true),
+		("^0.2.3", "0.3.0", false),
+		("^0.0.3", "0.0.4", false),
+		("^0.0.x", "0.0.9", true),
+		("^0.x", "0.9.0", true),
+		(">1.2", "1.2.9", false),
+		(">1.2", "1.3.0", true),
+		(">= 1.2.3 < 2", "1.5.0", true),
+		(">=1.2.3 <2", "2.0.0", false),
+		("<=1.2", "1.2.9", true),
+		("<1.2", "1.1.9", true),
+		("<1.2", "1.2.0", false),
+		("1.2.3 - 2.3.4", "2.3.4", true),
+		("1.2 - 2.3", "2.3.9", true),
+		("1.2 - 2.3", "2.4.0", false),
+		("^1.0.0 || ^2.0.0", "2.1.0", true),
+		("^1.0.0 || ^2.0.0", "3.0.0", false),
+		(">*", "1.0.0", false),
+	];
+
+	for (range, version, expected) in cases {
+		assert_eq!(satisfies(range, version), expected, "{range} {version}");
+	}
+}
+
+#[test]
+fn match_prerelease_versions() {
+	assert!(satisfies("^1.2.3-beta.1", "1.2.3-beta.2"));
+	assert!(satisfies("^1.2.3-beta.1", "1.3.0"));
+	assert!(!satisfies("^1.2.3-beta.1", "1.3.0-beta.1"));
+	assert!(!satisfies("*", "1.0.0-rc.1"));
+	assert!(!satisfies("^1.0.0", "2.0.0-0"));
+
+	let range = VersionRange::parse("^1.0.0").unwrap();
+	let version = parse_version("1.1.0-rc.1").unwrap();
+	assert!(range.satisfies_including_prerelease(&version));
+}
+
+#[test]
+fn select_satisfying_versions() {
+	let versions: Vec<_> = ["1.0.0", "1.2.0", "1.10.0", "2.0.0", "2.1.0-beta.0"]
+		.iter()
+		.map(|version| parse_version(version).unwrap())
+		.collect();
+	let range = VersionRange::parse("^1.1.0 || ^2.0.0").unwrap();
+
+	assert_eq!(
+		range.max_satisfying(&versions).unwrap().to_string(),
+		"2.0.0"
+	);
+	assert_eq!(
+		range.min_satisfying(&versions).unwrap().to_string(),
+		"1.2.0"
+	);
+}
+
+#[test]
+fn reject_invalid_ranges() {
+	assert!(VersionRange::parse("latest").is_err());
+	assert!(VersionRange::parse("^1.2.3.4").is_err());
+	assert!(VersionRange::parse(">=").is_err());
+	assert!(VersionRange::parse("1.2-beta").is_err());
+}
+
+#[test]
+fn intersect_ranges() {
+	let cases = [
+		("^1.2.0", "~1.4.1", true),
+		("^1.2.0", "^2.0.0", false),
+		("1.2.3", ">=1.2.3", true),
+		("1.2.3", ">1.2.3", false),
+		(">=1.0.0 <2.0.0", ">=2.0.0", false),
+		("<=2.0.0", ">=2.0.0", true),
+		("^0.2.0", "0.3.x", false),
+		("*", "^5.0.0", true),
+		("1.x || 3.x", "3.1 - 4", true),
+		("<0.0.0", "*", false),
+	];
+
+	for (a, b, expected) in cases {
+		let a = VersionRange::parse(a).unwrap();
+		let b = VersionRange::parse(b).unwrap();
+
+		assert_eq!(a.intersects(&b), expected, "{a} and {b}");
+		assert_eq!(b.intersects(&a), expected, "{b} and {a}");
+	}
+}
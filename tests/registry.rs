@@ -0,0 +1,258 @@
+#![cfg(feature = "registry")]
+
+use std::collections::HashMap;
+use std::fs::read;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use package_json_schema::Error;
+use package_json_schema::PackageJson;
+use package_json_schema::RegistryClient;
+use package_json_schema::ABBREVIATED_PACKUMENT_ACCEPT;
+use tiny_http::Header;
+use tiny_http::Response;
+use tiny_http::Server;
+
+/// A request received by the [`MockRegistry`] with lowercase header names.
+#[derive(Debug, Clone)]
+struct Received {
+	url: String,
+	headers: HashMap<String, String>,
+}
+
+type Reply = (u16, Vec<(&'static str, String)>, Vec<u8>);
+
+/// A registry stand-in which answers every request on a local port.
+struct MockRegistry {
+	url: String,
+	received: Arc<Mutex<Vec<Received>>>,
+}
+
+impl MockRegistry {
+	/// Serve the replies of a handler called with each request and the number
+	/// of requests received before it.
+	fn serve(handler: impl Fn(&Received, usize) -> Reply + Send + 'static) -> Self {
+		let server = Server::http("127.0.0.1:0").unwrap();
+		let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+		let received = Arc::new(Mutex::new(Vec::new()));
+		let log = Arc::clone(&received);
+
+		thread::spawn(move || {
+			for request in server.incoming_requests() {
+				let current = Received {
+					url: request.url().to_string(),
+					headers: request
+						.headers()
+						.iter()
+						.map(|header| {
+							(
+								header.field.as_str().as_str().to_lowercase(),
+								header.value.to_string(),
+							)
+						})
+						.collect(),
+				};
+				let index = {
+					let mut log = log.lock().unwrap();
+					log.push(current.clone());
+					log.len() - 1
+				};
+				let (status, headers, body) = handler(&current, index);
+				let mut response = Response::from_data(body).with_status_code(status);
+
+				for (name, value) in headers {
+					response.add_header(Header::from_bytes(name, value).unwrap());
+				}
+
+				request.respond(response).unwrap();
+			}
+		});
+
+		Self { url, received }
+	}
+
+	fn received(&self) -> Vec<Received> {
+		self.received.lock().unwrap().clone()
+	}
+
+	fn client(&self) -> RegistryClient {
+		RegistryClient::builder()
+			.registry(self.url.clone())
+			.retry_delay(Duration::ZERO)
+			.build()
+	}
+}
+
+fn fixture(name: &str) -> Vec<u8> {
+	read(format!("./tests/fixtures/packuments/{name}.json")).unwrap()
+}
+
+#[test]
+fn scoped_packument_with_auth_and_etag() {
+	let registry = MockRegistry::serve(|request, _| {
+		if request.headers.get("if-none-match").map(String::as_str) == Some("\"v1\"") {
+			return (304, vec![], vec![]);
+		}
+
+		(200, vec![("ETag", "\"v1\"".into())], fixture("ms"))
+	});
+	let client = RegistryClient::builder()
+		.registry(format!("{}/", registry.url))
+		.token("secret")
+		.build();
+
+	let first = client.packument("@scope/ms").unwrap();
+	let second = client.packument("@scope/ms").unwrap();
+	let received = registry.received();
+
+	assert_eq!(first.versions.len(), 4);
+	assert_eq!(second.versions.len(), 4);
+	assert_eq!(received.len(), 2);
+	assert_eq!(received[0].url, "/@scope%2fms");
+	assert_eq!(received[0].headers["authorization"], "Bearer secret");
+	assert_eq!(received[0].headers["accept"], "application/json");
+	assert!(!received[0].headers.contains_key("if-none-match"));
+	assert_eq!(received[1].headers["if-none-match"], "\"v1\"");
+}
+
+#[test]
+fn abbreviated_packument_and_tarball() {
+	let registry = MockRegistry::serve(|request, _| {
+		match request.url.as_str() {
+			"/debug" => (200, vec![], fixture("debug")),
+			"/debug/-/debug-4.3.4.tgz" => (200, vec![], b"tarball".to_vec()),
+			_ => (404, vec![], vec![]),
+		}
+	});
+	let client = registry.client();
+	let packument = client.abbreviated_packument("debug").unwrap();
+	let mut manifest = packument.dist_tag("latest").unwrap().clone();
+	let dist = manifest.dist.as_mut().unwrap();
+	dist.tarball = Some(format!("{}/debug/-/debug-4.3.4.tgz", registry.url));
+
+	assert_eq!(client.tarball(&manifest).unwrap(), b"tarball");
+	assert_eq!(
+		registry.received()[0].headers["accept"],
+		ABBREVIATED_PACKUMENT_ACCEPT
+	);
+	assert!(!registry.received()[0].headers.contains_key("authorization"));
+
+	let unpublished = PackageJson::builder()
+		.name("debug")
+		.version("0.0.0")
+		.build();
+	assert!(matches!(
+		client.tarball(&unpublished),
+		Err(Error::MissingTarball { .. })
+	));
+}
+
+#[test]
+fn retry_failed_requests() {
+	let flaky = MockRegistry::serve(|_, index| {
+		if index < 2 {
+			(503, vec![], vec![])
+		} else {
+			(200, vec![], fixture("debug"))
+		}
+	});
+
+	assert_eq!(
+		flaky.client().abbreviated_packument("debug").unwrap().name,
+		"debug"
+	);
+	assert_eq!(flaky.received().len(), 3);
+
+	let missing = MockRegistry::serve(|_, _| (404, vec![], vec![]));
+
+	assert!(matches!(
+		missing.client().packument("left-pad"),
+		Err(Error::RegistryStatus { status: 404, .. })
+	));
+	assert_eq!(missing.received().len(), 1);
+
+	let broken = MockRegistry::serve(|_, _| (500, vec![], vec![]));
+	let client = RegistryClient::builder()
+		.registry(broken.url.clone())
+		.retries(1)
+		.retry_delay(Duration::ZERO)
+		.build();
+
+	assert!(matches!(
+		client.packument("ms"),
+		Err(Error::RegistryStatus { status: 500, .. })
+	));
+	assert_eq!(broken.received().len(), 2);
+}
+
+#[test]
+fn send_the_token_only_to_the_registry() {
+	let registry = MockRegistry::serve(|_, _| (200, vec![], b"tarball".to_vec()));
+	let tarball = |registry_url: String, tarball: String| {
+		let client = RegistryClient::builder()
+			.registry(registry_url)
+			.token("secret")
+			.build();
+		let manifest = PackageJson::try_from(format!(
+			r#"{{ "name": "debug", "dist": {{ "tarball": "{tarball}" }} }}"#
+		))
+		.unwrap();
+
+		client.tarball(&manifest).unwrap();
+	};
+	// The port of the registry is a prefix of the port of the mock.
+	let look_alike = registry.url[..registry.url.len() - 1].to_string();
+
+	tarball(
+		format!("{}/npm/", registry.url),
+		format!("{}/npm/debug.tgz", registry.url),
+	);
+	tarball(
+		format!("{}/npm", registry.url),
+		format!("{}/npm-evil/debug.tgz", registry.url),
+	);
+	tarball(look_alike, format!("{}/debug.tgz", registry.url));
+
+	let authorization: Vec<Option<String>> = registry
+		.received()
+		.into_iter()
+		.map(|mut request| request.headers.remove("authorization"))
+		.collect();
+
+	assert_eq!(authorization, [Some("Bearer secret".into()), None, None]);
+}
+
+#[test]
+fn retry_only_connection_failures() {
+	let client = |delay: Duration| {
+		RegistryClient::builder()
+			.retries(2)
+			.retry_delay(delay)
+			.build()
+	};
+	let manifest = |tarball: &str| {
+		PackageJson::try_from(format!(
+			r#"{{ "name": "debug", "dist": {{ "tarball": "{tarball}" }} }}"#
+		))
+		.unwrap()
+	};
+	let closed = {
+		let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+		format!("http://{}/debug.tgz", listener.local_addr().unwrap())
+	};
+
+	let start = Instant::now();
+	let refused = client(Duration::from_millis(100)).tarball(&manifest(&closed));
+
+	assert!(matches!(refused, Err(Error::RegistryRequest { .. })));
+	assert!(start.elapsed() >= Duration::from_millis(300));
+
+	let start = Instant::now();
+	let unsupported = client(Duration::from_secs(60)).tarball(&manifest("ftp://127.0.0.1/x.tgz"));
+
+	assert!(matches!(unsupported, Err(Error::RegistryRequest { .. })));
+	assert!(start.elapsed() < Duration::from_secs(30));
+}
@@ -0,0 +1,81 @@
+use package_json_schema::Error;
+use package_json_schema::ReleaseReason;
+use package_json_schema::ReleaseType;
+use package_json_schema::Workspace;
+
+#[test]
+fn cascade_releases_to_dependents() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/graph").unwrap();
+	let plan = workspace
+		.plan_release(&[("@graph/core", ReleaseType::Major)])
+		.unwrap();
+
+	assert_eq!(
+		plan.to_string(),
+		"@graph/app: 1.0.0 -> 1.0.1 (patch, dependency @graph/core)\n@graph/core: 1.2.0 -> 2.0.0 \
+		 (major)\n@graph/legacy: 0.1.0 -> 0.1.1 (patch, dependency @graph/core)\n@graph/ui: 1.0.0 \
+		 -> 1.0.1 (patch, dependency @graph/core)\n@graph/app: dependencies.@graph/core: ^1.0.0 \
+		 -> ^2.0.0\n@graph/legacy: dependencies.core-alias: npm:@graph/core@^1.0.0 -> \
+		 npm:@graph/core@^2.0.0\n"
+	);
+
+	let updated = plan.apply_to_workspace(&workspace);
+	let indices: Vec<_> = updated.iter().map(|(index, _)| *index).collect();
+	assert_eq!(indices, [0, 2, 3, 4]);
+
+	let app = &updated[0].1;
+	assert_eq!(app.version.as_deref(), Some("1.0.1"));
+	let dependencies = app.dependencies.as_ref().unwrap();
+	assert_eq!(dependencies["@graph/core"], "^2.0.0");
+	assert_eq!(dependencies["@graph/ui"], "workspace:^");
+}
+
+#[test]
+fn merge_intents_and_keep_satisfied_ranges() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/graph").unwrap();
+	let intents = [
+		("@graph/config", ReleaseType::Patch),
+		("@graph/config", ReleaseType::Minor),
+		("@graph/config", ReleaseType::Prepatch),
+	];
+	let plan = workspace.plan_release(&intents).unwrap();
+
+	assert_eq!(plan.releases.len(), 1);
+	assert_eq!(plan.releases[0].release, ReleaseType::Minor);
+	assert_eq!(plan.releases[0].new_version.to_string(), "1.1.0");
+	assert!(plan.range_updates.is_empty());
+
+	let error = workspace
+		.plan_release(&[("@graph/unknown", ReleaseType::Patch)])
+		.unwrap_err();
+	assert!(matches!(error, Error::UnknownWorkspaceMember(name) if name == "@graph/unknown"));
+}
+
+#[test]
+fn release_peer_dependents_as_major() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/cycle").unwrap();
+	let plan = workspace
+		.plan_release(&[("c", ReleaseType::Major)])
+		.unwrap();
+
+	let releases: Vec<_> = plan
+		.releases
+		.iter()
+		.map(|release| (release.name.as_str(), release.new_version.to_string()))
+		.collect();
+	assert_eq!(
+		releases,
+		[
+			("a", "1.0.1".to_string()),
+			("b", "2.0.0".to_string()),
+			("c", "2.0.0".to_string())
+		]
+	);
+	assert_eq!(
+		plan.release("b").unwrap().reason,
+		ReleaseReason::Dependency { name: "c".into() }
+	);
+
+	assert_eq!(plan.range_updates.len(), 1);
+	assert_eq!(plan.range_updates[0].to, "^2.0.0");
+}
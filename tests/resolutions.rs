@@ -0,0 +1,172 @@
+use package_json_schema::DependencyLink;
+use package_json_schema::PackageJson;
+use package_json_schema::PatternSegment;
+use package_json_schema::ResolutionPattern;
+use package_json_schema::ResolutionWarningKind;
+use package_json_schema::YarnFlavor;
+
+#[test]
+fn parse_resolution_patterns() {
+	let pattern = ResolutionPattern::parse("**/@babel/core").unwrap();
+	assert_eq!(pattern.name(), "@babel/core");
+	assert_eq!(pattern.parents(), &[PatternSegment::Globstar]);
+
+	let pattern = ResolutionPattern::parse("foo@npm:1.0.0").unwrap();
+	assert_eq!(pattern.name(), "foo");
+	assert_eq!(pattern.range(), Some("npm:1.0.0"));
+
+	let pattern = ResolutionPattern::parse("parent@1.0.0/child@npm:@scope/child@2.0.0").unwrap();
+	assert_eq!(pattern.name(), "child");
+	assert_eq!(pattern.range(), Some("npm:@scope/child@2.0.0"));
+	assert!(pattern.is_berry_compatible());
+
+	assert!(ResolutionPattern::parse("").is_err());
+	assert!(ResolutionPattern::parse("left-pad/**").is_err());
+	assert!(ResolutionPattern::parse("left-*").is_err());
+	assert!(ResolutionPattern::parse("a//b").is_err());
+}
+
+#[test]
+fn classic_patterns_match_dependency_paths() {
+	let d2 = DependencyLink::new("d2", "^1.0.0");
+	let c = DependencyLink::new("c", "^1.0.0");
+	let left_pad = DependencyLink::new("left-pad", "^1.0.0");
+
+	let bare = ResolutionPattern::parse("left-pad").unwrap();
+	assert!(bare.matches(&[left_pad], YarnFlavor::Classic));
+	assert!(bare.matches(&[c, d2, left_pad], YarnFlavor::Classic));
+
+	let anchored = ResolutionPattern::parse("d2/left-pad").unwrap();
+	assert!(anchored.matches(&[d2, left_pad], YarnFlavor::Classic));
+	assert!(!anchored.matches(&[c, d2, left_pad], YarnFlavor::Classic));
+
+	let nested = ResolutionPattern::parse("c/**/left-pad").unwrap();
+	assert!(nested.matches(&[c, left_pad], YarnFlavor::Classic));
+	assert!(nested.matches(&[c, d2, left_pad], YarnFlavor::Classic));
+	assert!(!nested.matches(&[d2, left_pad], YarnFlavor::Classic));
+
+	let wildcard = ResolutionPattern::parse("*/left-pad").unwrap();
+	assert!(wildcard.matches(&[d2, left_pad], YarnFlavor::Classic));
+	assert!(!wildcard.matches(&[c, d2, left_pad], YarnFlavor::Classic));
+}
+
+#[test]
+fn berry_uses_the_first_matching_resolution() {
+	let contents = r#"{
+		"resolutions": {
+			"legacy@1.2.0/lodash": "2.4.2",
+			"lodash@npm:^3.0.0": "3.10.1",
+			"lodash": "4.17.21",
+			"lodash@npm:^4.0.0": "4.17.20"
+		}
+	}"#;
+	let package_json = PackageJson::try_from(contents).unwrap();
+	let map = package_json.resolution_map(YarnFlavor::Berry);
+	let warnings: Vec<_> = map
+		.warnings()
+		.iter()
+		.map(|warning| (warning.pattern.as_str(), &warning.kind))
+		.collect();
+
+	assert!(matches!(
+		warnings[..],
+		[("lodash@npm:^4.0.0", ResolutionWarningKind::Unreachable { shadowed_by })] if shadowed_by == "lodash"
+	));
+
+	let legacy = DependencyLink::new("legacy", "^1.0.0");
+	let find =
+		|chain: &[DependencyLink<'_>]| map.find(chain).map(|entry| entry.resolution.as_str());
+
+	assert_eq!(
+		find(&[DependencyLink::new("lodash", "^4.0.0")]),
+		Some("4.17.21")
+	);
+	assert_eq!(
+		find(&[DependencyLink::new("lodash", "^3.0.0")]),
+		Some("3.10.1")
+	);
+	assert_eq!(
+		find(&[
+			legacy.with_version("1.2.0"),
+			DependencyLink::new("lodash", "^3.0.0")
+		]),
+		Some("2.4.2")
+	);
+	assert_eq!(
+		find(&[legacy, DependencyLink::new("lodash", "^3.0.0")]),
+		Some("3.10.1")
+	);
+	assert_eq!(
+		find(&[
+			DependencyLink::new("legacy", "1.2.0").with_version("1.2.1"),
+			DependencyLink::new("lodash", "^4.0.0")
+		]),
+		Some("4.17.21")
+	);
+	assert_eq!(find(&[DependencyLink::new("underscore", "^1.0.0")]), None);
+}
+
+#[test]
+fn classic_uses_the_first_matching_resolution() {
+	let contents = r#"{
+		"resolutions": {
+			"webpack/**/terser": "5.26.0",
+			"**/terser": "5.10.0"
+		}
+	}"#;
+	let package_json = PackageJson::try_from(contents).unwrap();
+	let map = package_json.resolution_map(YarnFlavor::Classic);
+	assert!(map.warnings().is_empty());
+
+	let webpack = DependencyLink::new("webpack", "^5.0.0");
+	let terser = DependencyLink::new("terser", "^5.0.0");
+
+	assert_eq!(map.find(&[webpack, terser]).unwrap().resolution, "5.26.0");
+	assert_eq!(map.find(&[terser]).unwrap().resolution, "5.10.0");
+}
+
+#[test]
+fn warn_on_invalid_unsupported_and_unreachable_resolutions() {
+	let contents = r#"{
+		"resolutions": {
+			"**/left-pad": "1.3.0",
+			"d2/**/left-pad": "1.1.1",
+			"foo/": "1.0.0",
+			"foo@npm:1.0.0": "1.0.1",
+			"a/b/c": "1.0.0"
+		}
+	}"#;
+	let package_json = PackageJson::try_from(contents).unwrap();
+
+	let classic = package_json.resolution_map(YarnFlavor::Classic);
+	let kinds: Vec<_> = classic
+		.warnings()
+		.iter()
+		.map(|warning| (warning.pattern.as_str(), &warning.kind))
+		.collect();
+
+	assert_eq!(classic.entries().len(), 4);
+	assert!(matches!(
+		kinds[0],
+		("foo/", ResolutionWarningKind::Invalid(_))
+	));
+	assert!(matches!(
+		kinds[1],
+		("d2/**/left-pad", ResolutionWarningKind::Unreachable { shadowed_by }) if shadowed_by == "**/left-pad"
+	));
+	assert!(matches!(
+		kinds[2],
+		("foo@npm:1.0.0", ResolutionWarningKind::Unsupported(_))
+	));
+	assert_eq!(kinds.len(), 3);
+
+	let berry = package_json.resolution_map(YarnFlavor::Berry);
+	let unsupported: Vec<_> = berry
+		.warnings()
+		.iter()
+		.filter(|warning| matches!(warning.kind, ResolutionWarningKind::Unsupported(_)))
+		.map(|warning| warning.pattern.as_str())
+		.collect();
+
+	assert_eq!(unsupported, ["d2/**/left-pad", "a/b/c"]);
+}
@@ -0,0 +1,100 @@
+use common::read;
+use package_json_schema::BunLock;
+use package_json_schema::DependencyKind;
+use package_json_schema::LockfileGraph;
+use package_json_schema::PackageJson;
+use package_json_schema::PackageLock;
+use package_json_schema::PnpmLock;
+use package_json_schema::SyncProblem;
+use package_json_schema::Workspace;
+
+mod common;
+
+#[test]
+fn package_lock_out_of_sync() {
+	let package_lock = PackageLock::try_from(read("npm-v3/package-lock.json")).unwrap();
+	let graph = LockfileGraph::from_package_lock(&package_lock);
+	let mut package_json = PackageJson::try_from(read("npm-v3/package.json")).unwrap();
+
+	assert!(graph.check_sync(&[(".", &package_json)]).is_in_sync());
+
+	let dependencies = package_json.dependencies.as_mut().unwrap();
+	dependencies.insert("debug".into(), "^3.0.0".into());
+	dependencies.insert("semver".into(), "^7.6.0".into());
+	dependencies.shift_remove("ms");
+	let typescript = package_json
+		.dev_dependencies
+		.as_mut()
+		.unwrap()
+		.shift_remove("typescript")
+		.unwrap();
+	package_json
+		.dependencies
+		.as_mut()
+		.unwrap()
+		.insert("typescript".into(), typescript);
+
+	let report = graph.check_sync(&[(".", &package_json)]);
+
+	assert_eq!(
+		report.importer("."),
+		[
+			&SyncProblem::Unsatisfied {
+				kind: DependencyKind::Prod,
+				name: "debug".into(),
+				spec: "^3.0.0".into(),
+				locked: "4.3.4".into(),
+			},
+			&SyncProblem::Missing {
+				kind: DependencyKind::Prod,
+				name: "semver".into(),
+				spec: "^7.6.0".into(),
+			},
+			&SyncProblem::KindMismatch {
+				name: "typescript".into(),
+				manifest: DependencyKind::Prod,
+				lockfile: DependencyKind::Dev,
+			},
+			&SyncProblem::Extraneous {
+				kind: DependencyKind::Prod,
+				name: "ms".into(),
+			},
+		]
+	);
+}
+
+#[test]
+fn pnpm_workspace_in_sync() {
+	let workspace = Workspace::discover("./tests/fixtures/lockfiles/pnpm").unwrap();
+	let pnpm_lock = PnpmLock::try_from(read("pnpm/pnpm-lock.yaml")).unwrap();
+	let graph = LockfileGraph::from_pnpm_lock(&pnpm_lock);
+
+	assert_eq!(workspace.check_lockfile_sync(&graph).issues, []);
+
+	let mut package_json = workspace.package_json_at("packages/app").unwrap().clone();
+	package_json
+		.dependencies
+		.as_mut()
+		.unwrap()
+		.insert("lib".into(), "workspace:^".into());
+	let report = graph.check_sync(&[("packages/app", &package_json)]);
+
+	assert_eq!(
+		report.issues[0].problem,
+		SyncProblem::Unsatisfied {
+			kind: DependencyKind::Prod,
+			name: "lib".into(),
+			spec: "workspace:^".into(),
+			locked: "workspace:*".into(),
+		}
+	);
+}
+
+#[test]
+fn bun_workspace_in_sync() {
+	let workspace = Workspace::discover("./tests/fixtures/lockfiles/bun").unwrap();
+	let bun_lock = BunLock::try_from(read("bun/bun.lock")).unwrap();
+	let graph = LockfileGraph::from_bun_lock(&bun_lock);
+
+	assert!(workspace.check_lockfile_sync(&graph).is_in_sync());
+}
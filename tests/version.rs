@@ -0,0 +1,109 @@
+use package_json_schema::increment_version;
+use package_json_schema::Error;
+use package_json_schema::PackageJson;
+use package_json_schema::ReleaseType;
+use semver::Version;
+
+fn increment(version: &str, release: &str, preid: Option<&str>) -> String {
+	let version = Version::parse(version).unwrap();
+	let release: ReleaseType = release.parse().unwrap();
+	let next = increment_version(&version, release, preid).unwrap();
+	next.to_string()
+}
+
+#[test]
+fn increment_versions_like_npm() {
+	let cases = [
+		("1.2.3", "major", None, "2.0.0"),
+		("1.2.3", "minor", None, "1.3.0"),
+		("1.2.3", "patch", None, "1.2.4"),
+		("1.2.3-beta.1", "major", None, "2.0.0"),
+		("2.0.0-beta.1", "major", None, "2.0.0"),
+		("1.3.0-beta.1", "minor", None, "1.3.0"),
+		("1.2.4-beta.1", "patch", None, "1.2.4"),
+		("1.2.3+build.5", "patch", None, "1.2.4"),
+		("1.2.3", "premajor", None, "2.0.0-0"),
+		("1.2.3-beta.1", "premajor", Some("rc"), "2.0.0-rc.0"),
+		("1.2.3", "preminor", Some("alpha"), "1.3.0-alpha.0"),
+		("1.2.3", "prepatch", None, "1.2.4-0"),
+		("1.2.3", "prerelease", None, "1.2.4-0"),
+		("1.2.3", "prerelease", Some("beta"), "1.2.4-beta.0"),
+		("1.2.3-beta.1", "prerelease", Some("beta"), "1.2.3-beta.2"),
+		("1.2.3-beta.1", "prerelease", None, "1.2.3-beta.2"),
+		("1.2.3-alpha.1", "prerelease", Some("beta"), "1.2.3-beta.0"),
+		("1.2.3-beta", "prerelease", None, "1.2.3-beta.0"),
+		("1.2.3-beta", "prerelease", Some("beta"), "1.2.3-beta.0"),
+		("1.2.3-1.beta", "prerelease", None, "1.2.3-2.beta"),
+		("1.2.3-0", "prerelease", Some("next"), "1.2.3-next.0"),
+	];
+
+	for (version, release, preid, expected) in cases {
+		assert_eq!(
+			increment(version, release, preid),
+			expected,
+			"{release} {version} {preid:?}"
+		);
+	}
+
+	assert!(matches!(
+		"micro".parse::<ReleaseType>(),
+		Err(Error::InvalidReleaseType(_))
+	));
+	assert!(matches!(
+		increment_version(
+			&Version::parse("1.0.0").unwrap(),
+			ReleaseType::Prerelease,
+			Some("be ta")
+		),
+		Err(Error::InvalidPrereleaseIdentifier(_))
+	));
+
+	for version in [
+		"1.0.0-beta.18446744073709551615",
+		"1.0.0-beta.99999999999999999999",
+	] {
+		assert!(matches!(
+			increment_version(&Version::parse(version).unwrap(), ReleaseType::Prerelease, Some("beta")),
+			Err(Error::PrereleaseOverflow { version: ref overflowing }) if overflowing == version
+		));
+	}
+}
+
+#[test]
+fn bump_and_set_package_versions() {
+	let mut package_json = PackageJson::try_from(r#"{ "version": "v1.2.3" }"#).unwrap();
+
+	let version = package_json
+		.bump_with_preid(ReleaseType::Preminor, "beta")
+		.unwrap();
+	assert_eq!(version.to_string(), "1.3.0-beta.0");
+	assert_eq!(package_json.version.as_deref(), Some("1.3.0-beta.0"));
+
+	package_json.bump(ReleaseType::Minor).unwrap();
+	assert_eq!(package_json.version.as_deref(), Some("1.3.0"));
+
+	let error = package_json.set_version("1.3.0").unwrap_err();
+	assert_eq!(
+		error.to_string(),
+		"the version `1.3.0` must be greater than the current version `1.3.0`"
+	);
+	assert!(matches!(
+		package_json.set_version("1.3.0-rc.1"),
+		Err(Error::VersionNotIncreased { .. })
+	));
+	assert!(matches!(
+		package_json.set_version("next"),
+		Err(Error::InvalidVersion { .. })
+	));
+
+	package_json.set_version("2.0.0-rc.1").unwrap();
+	assert_eq!(package_json.version.as_deref(), Some("2.0.0-rc.1"));
+
+	let mut empty = PackageJson::try_from("{}").unwrap();
+	assert!(matches!(
+		empty.bump(ReleaseType::Patch),
+		Err(Error::MissingVersion)
+	));
+	empty.set_version("0.1.0").unwrap();
+	assert_eq!(empty.version.as_deref(), Some("0.1.0"));
+}
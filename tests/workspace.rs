@@ -0,0 +1,81 @@
+use package_json_schema::Workspace;
+use package_json_schema::WorkspaceDiagnostic;
+
+fn relative_paths(workspace: &Workspace) -> Vec<&str> {
+	workspace
+		.members()
+		.iter()
+		.map(|member| member.relative_path.as_str())
+		.collect()
+}
+
+#[test]
+fn discover_npm_workspace() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/npm").unwrap();
+
+	assert!(workspace.pnpm_workspace().is_none());
+	assert_eq!(workspace.package_json().name.as_deref(), Some("npm-root"));
+	assert_eq!(
+		relative_paths(&workspace),
+		[
+			"packages/a",
+			"packages/b",
+			"tools/nested/deep/c",
+			"tools/nested/duplicate"
+		]
+	);
+	assert_eq!(
+		workspace.member("@fixture/c").unwrap().relative_path,
+		"tools/nested/deep/c"
+	);
+}
+
+#[test]
+fn report_workspace_diagnostics() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/npm").unwrap();
+
+	assert_eq!(
+		workspace.diagnostics(),
+		[
+			WorkspaceDiagnostic::UnmatchedPattern {
+				pattern: "missing/*".into()
+			},
+			WorkspaceDiagnostic::UnmatchedPattern {
+				pattern: "!packages/ignored".into()
+			},
+			WorkspaceDiagnostic::DuplicateName {
+				name: "@fixture/b".into(),
+				relative_paths: vec!["packages/b".into(), "tools/nested/duplicate".into()],
+			},
+		]
+	);
+}
+
+#[test]
+fn discover_pnpm_workspace() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/pnpm").unwrap();
+
+	assert_eq!(
+		workspace.patterns(),
+		["apps/*", "packages/**", "!**/test/**"]
+	);
+	assert_eq!(
+		relative_paths(&workspace),
+		["apps/web", "packages/core", "packages/utils"]
+	);
+	assert!(workspace.diagnostics().is_empty());
+}
+
+#[test]
+fn default_pnpm_patterns_without_packages() {
+	let workspace = Workspace::discover("./tests/fixtures/workspaces/pnpm-default").unwrap();
+
+	assert_eq!(workspace.patterns(), [".", "**"]);
+	assert_eq!(relative_paths(&workspace), ["packages/core", "tools/cli"]);
+	assert!(workspace.diagnostics().is_empty());
+}
+
+#[test]
+fn fail_without_a_root_manifest() {
+	assert!(Workspace::discover("./tests/fixtures/workspaces/missing").is_err());
+}
@@ -0,0 +1,213 @@
+use std::fs::read_to_string;
+
+use package_json_schema::PackageJson;
+use package_json_schema::YarnLock;
+use package_json_schema::YarnLockFlavor;
+
+fn fixture(directory: &str) -> (YarnLock, PackageJson) {
+	let directory = format!("./tests/fixtures/lockfiles/{directory}");
+	let yarn_lock = read_to_string(format!("{directory}/yarn.lock")).unwrap();
+	let package_json = read_to_string(format!("{directory}/package.json")).unwrap();
+
+	(
+		YarnLock::try_from(yarn_lock).unwrap(),
+		PackageJson::try_from(package_json).unwrap(),
+	)
+}
+
+fn locked_versions(yarn_lock: &YarnLock, package_json: &PackageJson) -> Vec<(String, String)> {
+	yarn_lock
+		.locked_dependencies(package_json)
+		.into_iter()
+		.map(|locked| {
+			let version = locked.entry.unwrap().version.clone().unwrap();
+			(locked.name.to_string(), version)
+		})
+		.collect()
+}
+
+#[test]
+fn parse_classic_lockfile() {
+	let (yarn_lock, package_json) = fixture("yarn-classic");
+	let debug = yarn_lock.find("debug", "^4.3.4").unwrap();
+
+	assert_eq!(yarn_lock.flavor(), YarnLockFlavor::Classic);
+	assert!(yarn_lock.metadata().is_none());
+	assert_eq!(yarn_lock.entries().len(), 5);
+	assert_eq!(debug.name(), Some("debug"));
+	assert_eq!(debug.dependencies.as_ref().unwrap()["ms"], "2.1.2");
+	assert!(debug.integrity.as_ref().unwrap().starts_with("sha512-"));
+	assert!(debug
+		.resolved
+		.as_ref()
+		.unwrap()
+		.ends_with(".tgz#1319f6579357f2338d3337d2cdd4914bb5dcc865"));
+	assert!(yarn_lock.find("debug", "npm:^4.3.4").is_none());
+	assert_eq!(
+		locked_versions(&yarn_lock, &package_json),
+		[
+			("debug".to_string(), "4.3.4".to_string()),
+			("ms".to_string(), "2.0.0".to_string()),
+			("typescript".to_string(), "5.4.5".to_string()),
+			("fsevents".to_string(), "2.3.3".to_string()),
+		]
+	);
+}
+
+#[test]
+fn parse_berry_lockfile() {
+	let (yarn_lock, package_json) = fixture("yarn-berry");
+	let metadata = yarn_lock.metadata().unwrap();
+	let debug = yarn_lock.find("debug", "^4.3.4").unwrap();
+	let typescript = yarn_lock.find("typescript", "npm:^5.4.0").unwrap();
+	let root = yarn_lock.find("lock-fixture", "workspace:.").unwrap();
+
+	assert_eq!(yarn_lock.flavor(), YarnLockFlavor::Berry);
+	assert_eq!(metadata.version, Some(8));
+	assert_eq!(metadata.cache_key.as_deref(), Some("10c0"));
+	assert_eq!(debug.resolution.as_deref(), Some("debug@npm:4.3.4"));
+	assert_eq!(debug.dependencies.as_ref().unwrap()["ms"], "npm:2.1.2");
+	assert!(debug.checksum.as_ref().unwrap().starts_with("10c0/"));
+	assert_eq!(debug.language_name.as_deref(), Some("node"));
+	assert_eq!(typescript.bin.as_ref().unwrap()["tsc"], "bin/tsc");
+	assert_eq!(root.link_type.as_deref(), Some("soft"));
+	assert_eq!(
+		yarn_lock
+			.find("fsevents", "~2.3.3")
+			.unwrap()
+			.conditions
+			.as_deref(),
+		Some("os=darwin")
+	);
+	assert_eq!(
+		locked_versions(&yarn_lock, &package_json),
+		[
+			("debug".to_string(), "4.3.4".to_string()),
+			("ms".to_string(), "2.0.0".to_string()),
+			("typescript".to_string(), "5.4.5".to_string()),
+			("fsevents".to_string(), "2.3.3".to_string()),
+		]
+	);
+}
+
+#[test]
+fn classic_syntax() {
+	let contents = r#"
+"@babel/code-frame@^7.0.0", "@babel/code-frame@^7.10.4":
+  version "7.12.13"
+  dependencies:
+    "@babel/highlight" "^7.12.13"
+  optionalDependencies:
+    fsevents "~2.3.3"
+"#;
+	let yarn_lock = YarnLock::try_from(contents).unwrap();
+	let entry = yarn_lock.find("@babel/code-frame", "^7.10.4").unwrap();
+
+	assert_eq!(entry.name(), Some("@babel/code-frame"));
+	assert_eq!(entry.descriptors.len(), 2);
+	assert_eq!(
+		entry.dependencies.as_ref().unwrap()["@babel/highlight"],
+		"^7.12.13"
+	);
+	assert_eq!(
+		entry.optional_dependencies.as_ref().unwrap()["fsevents"],
+		"~2.3.3"
+	);
+
+	let invalid = YarnLock::try_from("ms@^2.0.0:\n  version \"2.0.0\"\n      nested \"1\"\n");
+	let error = invalid.unwrap_err().to_string();
+
+	assert_eq!(error, "invalid yarn.lock at line 3: unexpected indentation");
+
+	let misaligned =
+		YarnLock::try_from("ms@^2.0.0:\n    dependencies:\n        a \"1\"\n      b \"1\"\n");
+	let error = misaligned.unwrap_err().to_string();
+
+	assert_eq!(error, "invalid yarn.lock at line 4: unexpected indentation");
+
+	for (contents, line) in [
+		(
+			"a@^1.0.0:\n  dependencies \"b\"\n\nb@^1.0.0:\n  version \"1.0.0\"\n",
+			1,
+		),
+		(
+			"a@^1.0.0:\n  version \"1.0.0\"\n\nb@^1.0.0:\n  dependencies \"a\"\n",
+			4,
+		),
+	] {
+		let error = YarnLock::try_from(contents).unwrap_err().to_string();
+
+		assert!(
+			error.starts_with(&format!("invalid yarn.lock at line {line}: ")),
+			"{error}"
+		);
+	}
+}
+
+#[test]
+fn classic_syntax_with_other_indentation() {
+	let (yarn_lock, _) = fixture("yarn-classic");
+	let original = read_to_string("./tests/fixtures/lockfiles/yarn-classic/yarn.lock").unwrap();
+
+	for indentation in ["    ", "\t"] {
+		let lines: Vec<String> = original
+			.lines()
+			.map(|line| {
+				let trimmed = line.trim_start_matches(' ');
+				let depth = (line.len() - trimmed.len()) / 2;
+				format!("{}{trimmed}", indentation.repeat(depth))
+			})
+			.collect();
+		let reindented = YarnLock::try_from(lines.join("\n")).unwrap();
+
+		assert_eq!(
+			reindented.try_to_string().unwrap(),
+			yarn_lock.try_to_string().unwrap(),
+			"{indentation:?}"
+		);
+	}
+}
+
+#[test]
+fn write_both_flavors() {
+	for directory in ["yarn-classic", "yarn-berry"] {
+		let (yarn_lock, package_json) = fixture(directory);
+		let contents = yarn_lock.try_to_string().unwrap();
+		let reparsed = YarnLock::try_from(contents).unwrap();
+
+		assert_eq!(reparsed.flavor(), yarn_lock.flavor(), "{directory}");
+		assert_eq!(reparsed.metadata(), yarn_lock.metadata(), "{directory}");
+		assert_eq!(
+			reparsed.entries().len(),
+			yarn_lock.entries().len(),
+			"{directory}"
+		);
+		assert_eq!(
+			locked_versions(&reparsed, &package_json),
+			locked_versions(&yarn_lock, &package_json),
+			"{directory}"
+		);
+	}
+}
+
+#[test]
+fn write_unknown_classic_fields() {
+	let contents = concat!(
+		"# THIS IS AN AUTOGENERATED FILE. DO NOT EDIT THIS FILE DIRECTLY.\n",
+		"# yarn lockfile v1\n\n\n\n",
+		"react-dom@^18.2.0:\n",
+		"  version \"18.2.0\"\n",
+		"  resolved \"https://registry.yarnpkg.com/react-dom/-/react-dom-18.2.0.tgz\"\n",
+		"  dependencies:\n",
+		"    scheduler \"^0.23.0\"\n",
+		"  peerDependencies:\n",
+		"    react \"^18.2.0\"\n",
+		"  uid abc123\n",
+		"  bundled true\n",
+		"  engines:\n",
+		"    node \">=0.10.0\"\n",
+	);
+	let yarn_lock = YarnLock::try_from(contents).unwrap();
+
+	assert_eq!(yarn_lock.try_to_string().unwrap(), contents);
+}